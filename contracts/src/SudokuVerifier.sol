@@ -0,0 +1,105 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+import {ISP1Verifier} from "./fixtures/ISP1Verifier.sol";
+import {PublicValuesStruct} from "./fixtures/PublicValuesStruct.sol";
+
+/// @title SudokuVerifier
+/// @notice Accepts SP1 proofs of the zk-sudoku verifier program and records, per reward
+/// address, the puzzles it solved. Every (puzzle, address) pair counts once, through the
+/// nullifier the program commits.
+contract SudokuVerifier {
+    /// @notice The address of the SP1 verifier contract, usually an SP1VerifierGateway.
+    address public verifier;
+
+    /// @notice The verification key of the zk-sudoku verifier program.
+    bytes32 public sudokuProgramVKey;
+
+    /// @notice The sudoku_io::LOGIC_VERSION proofs have to commit. Proofs from guests built
+    /// with other generation or validation logic are rejected.
+    uint32 public logicVersion;
+
+    /// @notice The sha256 of the sudoku_io::Policy proofs have to commit. The guest only marks a
+    /// puzzle valid if it meets the policy it was given, so pinning the hash here is what makes
+    /// the minimum difficulty and maximum clue count hold for this contract.
+    bytes32 public policyHash;
+
+    /// @notice The ed25519 key of the organizer whose signed puzzles count. Anyone can generate
+    /// puzzles, and each gives a fresh nullifier, so only an organizer's key limits completions
+    /// to the puzzles it issued. Zero accepts unsigned puzzles only.
+    bytes32 public organizerKey;
+
+    /// @notice The sudoku::core::variant kind proofs have to commit (0 classic, 1 X, 2 killer).
+    uint8 public variant;
+
+    /// @notice Nullifiers (sha256(puzzleHash || rewardAddress)) of the accepted proofs.
+    mapping(bytes32 => bool) public nullifierUsed;
+
+    /// @notice Number of puzzles each address has solved.
+    mapping(address => uint256) public completions;
+
+    /// @notice Whether an address has solved a puzzle, by puzzle hash.
+    mapping(address => mapping(bytes32 => bool)) public completed;
+
+    event PuzzleCompleted(address indexed solver, bytes32 indexed puzzleHash, uint64 epoch);
+
+    error InvalidSolution(uint8 status);
+    error LogicVersionMismatch(uint32 committed);
+    error PolicyMismatch(bytes32 committed);
+    error OrganizerMismatch(bytes32 committed);
+    error VariantMismatch(uint8 committed);
+    error NullifierUsed(bytes32 nullifier);
+
+    constructor(
+        address _verifier,
+        bytes32 _sudokuProgramVKey,
+        uint32 _logicVersion,
+        bytes32 _policyHash,
+        bytes32 _organizerKey,
+        uint8 _variant
+    ) {
+        verifier = _verifier;
+        sudokuProgramVKey = _sudokuProgramVKey;
+        logicVersion = _logicVersion;
+        policyHash = _policyHash;
+        organizerKey = _organizerKey;
+        variant = _variant;
+    }
+
+    /// @notice Verifies a proof of a solved puzzle and records the completion for the reward
+    /// address committed in it.
+    /// @param _publicValues The abi encoded sudoku_io::PublicValues the program committed.
+    /// @param _proofBytes The encoded Groth16 or PLONK proof.
+    function submitSolution(bytes calldata _publicValues, bytes calldata _proofBytes)
+        public
+        returns (PublicValuesStruct memory)
+    {
+        ISP1Verifier(verifier).verifyProof(sudokuProgramVKey, _publicValues, _proofBytes);
+        PublicValuesStruct memory values = abi.decode(_publicValues, (PublicValuesStruct));
+
+        if (values.logicVersion != logicVersion) {
+            revert LogicVersionMismatch(values.logicVersion);
+        }
+        if (values.policyHash != policyHash) {
+            revert PolicyMismatch(values.policyHash);
+        }
+        if (values.organizerKey != organizerKey) {
+            revert OrganizerMismatch(values.organizerKey);
+        }
+        if (values.variant != variant) {
+            revert VariantMismatch(values.variant);
+        }
+        if (!values.valid) {
+            revert InvalidSolution(values.status);
+        }
+        if (nullifierUsed[values.nullifier]) {
+            revert NullifierUsed(values.nullifier);
+        }
+
+        nullifierUsed[values.nullifier] = true;
+        completed[values.rewardAddress][values.puzzleHash] = true;
+        completions[values.rewardAddress] += 1;
+        emit PuzzleCompleted(values.rewardAddress, values.puzzleHash, values.epoch);
+        return values;
+    }
+}
@@ -1,110 +1,316 @@
-// ANCHOR: dependencies
-use std::{fs, sync::Arc};
+//! Runs the OpenVM guest in `openvm_proof` through the SDK: build the guest and transpile it,
+//! execute it, generate an app proof and verify it. Every stage needs the ones before it, and
+//! building always happens.
+//!
+//! ```shell
+//! cargo run --release -- --execute
+//! cargo run --release -- --prove --verify
+//! cargo run --release -- --prove --evm --verify --calldata calldata.bin
+//! ```
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use eyre::Result;
-use openvm::platform::memory::MEM_SIZE;
+use clap::Parser;
+use eyre::{bail, Result, WrapErr};
 use openvm_build::GuestOptions;
+use openvm_circuit::arch::SystemConfig;
+use openvm_native_recursion::halo2::utils::CacheHalo2ParamsReader;
 use openvm_sdk::{
-    config::{AppConfig, SdkVmConfig},
-    prover::AppProver,
-    Sdk, StdIn,
+    commit::NonRootCommittedExe,
+    config::{AggConfig, AppConfig, SdkVmConfig},
+    keygen::AppProvingKey,
+    DefaultStaticVerifierPvHandler, Sdk, StdIn,
 };
 use openvm_stark_sdk::config::FriParameters;
-use openvm_transpiler::elf::Elf;
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-#[derive(Serialize, Deserialize)]
-pub struct SomeStruct {
-    pub a: u64,
-    pub b: u64,
-}
-// ANCHOR_END: dependencies
-
-#[allow(dead_code, unused_variables)]
-fn read_elf() -> Result<(), Box<dyn std::error::Error>> {
-    // ANCHOR: read_elf
-    // 2b. Load the ELF from a file
-    let elf_bytes = fs::read("your_path_to_elf")?;
-    let elf = Elf::decode(&elf_bytes, MEM_SIZE as u32)?;
-    // ANCHOR_END: read_elf
-    Ok(())
+use openvm_stark_sdk::openvm_stark_backend::p3_field::PrimeField32;
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::commitment::sha256;
+use sudoku::core::solver::DancingLinks;
+use sudoku_io::{
+    from_reveal_words, ProofInput, PublicValues, PuzzleSource, LOGIC_VERSION, REVEAL_SLOTS,
+};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Build the guest and transpile it into an OpenVM executable.
+    #[clap(long)]
+    build: bool,
+
+    /// Execute the guest and print the public values it reveals.
+    #[clap(long)]
+    execute: bool,
+
+    /// Generate the app proving key and an app proof.
+    #[clap(long)]
+    prove: bool,
+
+    /// Verify the app proof.
+    #[clap(long, requires = "prove")]
+    verify: bool,
+
+    /// Generate an EVM proof instead of an app proof: the app proof is aggregated and wrapped
+    /// in a halo2 SNARK, which the generated Solidity verifier checks. Needs the halo2 KZG
+    /// params (`cargo openvm setup`) and a lot of memory.
+    #[clap(long, requires = "prove")]
+    evm: bool,
+
+    /// Directory of the halo2 KZG params for --evm.
+    #[clap(long, default_value = concat!(env!("HOME"), "/.openvm/params/"))]
+    params_dir: PathBuf,
+
+    /// Write the calldata for the Solidity verifier of the --evm proof to this file.
+    #[clap(long, requires = "evm")]
+    calldata: Option<PathBuf>,
+
+    /// Seed of the puzzle, the solver's solution is submitted for it.
+    #[clap(long, default_value = "666")]
+    seed: u32,
+
+    /// Log blowup of the app's FRI parameters, higher proves slower with smaller proofs.
+    #[clap(long, default_value = "2")]
+    log_blowup: usize,
 }
 
-#[allow(unused_variables, unused_doc_comments)]
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // ANCHOR: vm_config
+fn main() -> Result<()> {
+    let args = Args::parse();
+    if !(args.build || args.execute || args.prove) {
+        bail!("nothing to do, pass --build, --execute or --prove");
+    }
+
+    // the guest needs the io and sha256 extensions next to rv32i/rv32m (see its openvm.toml),
+    // and every reveal slot is 4 bytes of public values.
     let vm_config = SdkVmConfig::builder()
-        .system(Default::default())
+        .system(
+            SystemConfig::default()
+                .with_public_values(REVEAL_SLOTS * 4)
+                .into(),
+        )
         .rv32i(Default::default())
         .rv32m(Default::default())
         .io(Default::default())
+        .sha256(Default::default())
         .build();
-    // ANCHOR_END: vm_config
-
-    // /// to import example guest code in crate replace `target_path` for:
-    // /// ```
-    // /// use std::path::PathBuf;
-    // ///
-    //  let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).to_path_buf();
-    //  path.pop();
-    //  path.push("zk_sudoku");
-    //  let target_path = path.to_str().unwrap();
-    //  // ```
-    // // ANCHOR: build
-    // // 1. Build the VmConfig with the extensions needed.
     let sdk = Sdk;
 
-    // // 2a. Build the ELF with guest options and a target filter.
-    // let guest_opts = GuestOptions::default();
-    // // let target_path = "/home/von/openvm-sudoku/zk_sudoku";
-    // let elf = sdk.build(guest_opts, target_path, &Default::default())?;
-    // // ANCHOR_END: build
-
-    // // ANCHOR: transpilation
-    // // 3. Transpile the ELF into a VmExe
-    // let exe = sdk.transpile(elf, vm_config.transpiler())?;
-    // // ANCHOR_END: transpilation
-
-    // // ANCHOR: execution
-    // // 4. Format your input into StdIn
-    // let my_input = SomeStruct { a: 1, b: 2 }; // anything that can be serialized
-    // let mut stdin = StdIn::default();
-    // stdin.write(&my_input);
-
-    // // 5. Run the program
-    // let output = sdk.execute(exe.clone(), vm_config.clone(), stdin.clone())?;
-    // println!("public values output: {:?}", output);
-    // // ANCHOR_END: execution
-
-
-    // // ANCHOR: proof_generation
-    // // 6. Set app configuration
-   let app_log_blowup = 2;
-    let app_fri_params = FriParameters::standard_with_100_bits_conjectured_security(app_log_blowup);
-    let app_config = AppConfig::new(app_fri_params, vm_config);
-    // println!("app configured");
-    // // 7. Commit the exe
-    // let app_committed_exe = sdk.commit_app_exe(app_fri_params, exe)?;
-
-    // println!("app commited");
-    // 8. Generate an AppProvingKey
-    let app_pk = Arc::new(sdk.app_keygen(app_config)?);
-
-    // // 9a. Generate a proof
-    // println!("about to generate a proof");
-    // let proof = sdk.generate_app_proof(app_pk.clone(), app_committed_exe.clone(), stdin.clone())?;
-    // println!("proof generated");
-    // // 9b. Generate a proof with an AppProver with custom fields
-    // let app_prover = AppProver::new(app_pk.app_vm_pk.clone(), app_committed_exe.clone())
-    //     .with_program_name("test_program");
-    // let proof = app_prover.generate_app_proof(stdin.clone());
-    // // ANCHOR_END: proof_generation
-
-    // // ANCHOR: verification
-    // // 10. Verify your program
-    // let app_vk = app_pk.get_app_vk();
-    // sdk.verify_app_proof(&app_vk, &proof)?;
-    // // ANCHOR_END: verification
+    // build the guest crate next to this one and transpile it.
+    let mut guest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    guest_path.pop();
+    guest_path.push("openvm_proof");
+    let mut timings = Timings::default();
+    let exe = timings.time("build", || {
+        let elf = sdk
+            .build(
+                GuestOptions::default(),
+                guest_path.to_str().unwrap(),
+                &Default::default(),
+            )
+            .wrap_err("failed to build the guest")?;
+        sdk.transpile(elf, vm_config.transpiler())
+            .wrap_err("failed to transpile the guest")
+    })?;
+
+    // the same digest the host saves in its proof envelopes, see sudoku_io::envelope.
+    let exe_bytes = bincode::serialize(&exe).wrap_err("failed to serialize the executable")?;
+    println!("program digest: 0x{}", hex(&sha256(&exe_bytes)));
+
+    let puzzle = Board::from_seed(args.seed, Some(Difficulty::Medium));
+    let solution = DancingLinks::solve_board(&puzzle)
+        .map_err(|e| eyre::eyre!("can't solve seed {}: {}", args.seed, e))?;
+    let input = ProofInput::new(
+        PuzzleSource::Seed {
+            seed: args.seed,
+            difficulty: Difficulty::Medium as u8,
+        },
+        solution.cells.to_vec(),
+    );
+    let mut stdin = StdIn::default();
+    // the same encoding the host sends, see zk_sudoku_host::stdin.
+    for bytes in zk_sudoku_host::stdin::openvm(&input).map_err(|e| eyre::eyre!(e))? {
+        stdin.write_bytes(&bytes);
+    }
+
+    if args.execute {
+        let output = timings.time("execute", || {
+            sdk.execute(exe.clone(), vm_config.clone(), stdin.clone())
+                .wrap_err("failed to execute the guest")
+        })?;
+
+        let values = decode_public_values(&output)?;
+        if values.logic_version != LOGIC_VERSION {
+            bail!(
+                "the guest was built with logic version {}, this host expects {}",
+                values.logic_version,
+                LOGIC_VERSION
+            );
+        }
+        print_public_values(&values);
+    }
+
+    if args.prove {
+        let app_fri_params =
+            FriParameters::standard_with_100_bits_conjectured_security(args.log_blowup);
+        let app_config = AppConfig::new(app_fri_params, vm_config);
 
+        let (app_committed_exe, app_pk) = timings.time("keygen", || {
+            let app_committed_exe = sdk
+                .commit_app_exe(app_fri_params, exe)
+                .wrap_err("failed to commit the executable")?;
+            let app_pk = sdk
+                .app_keygen(app_config)
+                .wrap_err("failed to generate the app proving key")?;
+            Ok::<_, eyre::Report>((app_committed_exe, Arc::new(app_pk)))
+        })?;
+        let app_vk = bincode::serialize(&app_pk.get_app_vk())
+            .wrap_err("failed to serialize the app verifying key")?;
+        println!("vkey hash: 0x{}", hex(&sha256(&app_vk)));
+
+        if args.evm {
+            prove_evm(&args, &sdk, &mut timings, app_pk, app_committed_exe, stdin)?;
+        } else {
+            // trace generation happens segment by segment as part of proving.
+            let proof = timings.time("prove", || {
+                sdk.generate_app_proof(app_pk.clone(), app_committed_exe, stdin)
+                    .wrap_err("failed to generate the proof")
+            })?;
+            println!("segments: {}", proof.per_segment.len());
+
+            if args.verify {
+                let app_vk = app_pk.get_app_vk();
+                timings.time("verify", || {
+                    sdk.verify_app_proof(&app_vk, &proof)
+                        .wrap_err("the proof doesn't verify")
+                })?;
+                println!("proof verified");
+            }
+        }
+    }
+
+    timings.report();
+    Ok(())
+}
+
+// wall clock time of each stage, printed as the stages finish and summed up at the end, to see
+// where a long proving run spends its time and tune --log-blowup against it.
+#[derive(Default)]
+struct Timings(Vec<(&'static str, Duration)>);
+
+impl Timings {
+    fn time<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        println!("{}...", stage);
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        println!("{} took {:.2?}", stage, elapsed);
+        self.0.push((stage, elapsed));
+        result
+    }
+
+    fn report(&self) {
+        for (stage, elapsed) in &self.0 {
+            println!("{:<10} {:>12.2?}", stage, elapsed);
+        }
+        let total: Duration = self.0.iter().map(|(_, elapsed)| *elapsed).sum();
+        println!("{:<10} {:>12.2?}", "total", total);
+    }
+}
+
+// aggregate the app proof and wrap it in a halo2 proof an EVM contract can verify, the same
+// role as SP1's --groth16/--plonk. the verifier contract is generated from the aggregation key
+// and run in a local EVM, which reports the gas a verification costs.
+fn prove_evm(
+    args: &Args,
+    sdk: &Sdk,
+    timings: &mut Timings,
+    app_pk: Arc<AppProvingKey<SdkVmConfig>>,
+    app_committed_exe: Arc<NonRootCommittedExe>,
+    stdin: StdIn,
+) -> Result<()> {
+    let params_reader = CacheHalo2ParamsReader::new(&args.params_dir);
+
+    let (agg_pk, verifier) = timings.time("agg keygen", || {
+        let agg_pk = sdk
+            .agg_keygen(
+                AggConfig::default(),
+                &params_reader,
+                &DefaultStaticVerifierPvHandler,
+            )
+            .wrap_err("failed to generate the aggregation proving key")?;
+        let verifier = sdk
+            .generate_snark_verifier_contract(&params_reader, &agg_pk)
+            .wrap_err("failed to generate the verifier contract")?;
+        Ok::<_, eyre::Report>((agg_pk, verifier))
+    })?;
+
+    let proof = timings.time("evm prove", || {
+        sdk.generate_evm_proof(&params_reader, app_pk, app_committed_exe, agg_pk, stdin)
+            .wrap_err("failed to generate the evm proof")
+    })?;
+
+    let calldata = proof.verifier_calldata();
+    println!("calldata: {} bytes", calldata.len());
+    if let Some(path) = &args.calldata {
+        std::fs::write(path, &calldata)
+            .wrap_err_with(|| format!("failed to write {}", path.display()))?;
+        println!("calldata written to {}", path.display());
+    }
+
+    if args.verify {
+        let gas = timings.time("verify", || {
+            sdk.verify_evm_proof(&verifier, &proof)
+                .wrap_err("the evm proof doesn't verify")
+        })?;
+        println!("evm proof verified, {} gas", gas);
+    }
     Ok(())
 }
+
+// the revealed public values are one field element per byte, four per reveal slot in little
+// endian order.
+fn decode_public_values<F: PrimeField32>(output: &[F]) -> Result<PublicValues> {
+    if output.len() < REVEAL_SLOTS * 4 {
+        bail!(
+            "the guest revealed {} bytes, expected {}",
+            output.len(),
+            REVEAL_SLOTS * 4
+        );
+    }
+    let mut words = [0u32; REVEAL_SLOTS];
+    for (word, bytes) in words.iter_mut().zip(output.chunks_exact(4)) {
+        *word = bytes
+            .iter()
+            .rev()
+            .fold(0, |word, byte| (word << 8) | byte.as_canonical_u32());
+    }
+    Ok(from_reveal_words(&words))
+}
+
+fn print_public_values(values: &PublicValues) {
+    println!("puzzle hash: 0x{}", hex(&values.puzzle_hash));
+    println!(
+        "solution commitment: 0x{}",
+        hex(&values.solution_commitment)
+    );
+    println!("valid: {}", values.valid);
+    println!(
+        "difficulty: {}, clues: {}",
+        values.difficulty, values.clue_count
+    );
+    println!("seed: {}", values.seed);
+    println!("nullifier: 0x{}", hex(&values.nullifier));
+    println!("epoch: {}", values.epoch);
+    println!("reward address: 0x{}", hex(&values.reward_address));
+    println!("status: {}", values.status);
+    println!("policy hash: 0x{}", hex(&values.policy_hash));
+    println!("organizer key: 0x{}", hex(&values.organizer_key));
+    println!("variant: {}", values.variant);
+    println!("logic version: {}", values.logic_version);
+    println!("cells root: 0x{}", hex(&values.cells_root));
+    println!("givens hash: 0x{}", hex(&values.givens_hash));
+    println!("nonce: 0x{}", hex(&values.nonce));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
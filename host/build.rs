@@ -0,0 +1,23 @@
+fn main() {
+    // the sp1 backend embeds the verifier program's ELF, see backend::sp1. ZK_SUDOKU_BENCH=1
+    // builds it with cycle-tracker markers, so execute prints the cycles of every phase, and
+    // takes extra guest features like sp1_proof/script's build does.
+    #[cfg(feature = "sp1")]
+    {
+        println!("cargo:rerun-if-env-changed=ZK_SUDOKU_BENCH");
+        let features = match std::env::var("ZK_SUDOKU_BENCH") {
+            Ok(extra) => std::iter::once("bench")
+                .chain(extra.split(',').filter(|f| !f.is_empty() && *f != "1"))
+                .map(String::from)
+                .collect(),
+            Err(_) => vec![],
+        };
+        sp1_build::build_program_with_args(
+            "../sp1_proof/program",
+            sp1_build::BuildArgs {
+                features,
+                ..Default::default()
+            },
+        );
+    }
+}
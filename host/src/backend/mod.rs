@@ -0,0 +1,107 @@
+// the zkVMs the host proves with. each one runs the same verifier guest on a
+// sudoku_io::ProofInput and hands back sudoku_io::PublicValues, however it encodes them, so the
+// commands above don't care which one they talk to.
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use sudoku_io::{ProofInput, PublicValues};
+
+use crate::config::ProverConfig;
+
+pub mod mock;
+#[cfg(feature = "openvm")]
+pub mod openvm;
+#[cfg(feature = "sp1")]
+pub mod sp1;
+
+pub struct Execution {
+    pub values: PublicValues,
+    // not every backend reports it.
+    pub cycles: Option<u64>,
+    // cycles per guest phase (generate, decode, apply, validate, ...) in name order, empty
+    // unless the backend counts them and the guest was built with cycle-tracker markers.
+    pub phases: Vec<(String, u64)>,
+}
+
+// what a verifier pins: the backend's hash of the verifying key and the sha256 of the guest
+// (see ProofEnvelope::program_digest).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program {
+    pub vkey_hash: Vec<u8>,
+    pub digest: Vec<u8>,
+}
+
+impl Program {
+    // refuse a proof made for another program before verifying it.
+    pub fn check(&self, envelope: &ProofEnvelope) -> Result<(), String> {
+        if envelope.program_digest != self.digest {
+            return Err(format!(
+                "the proof is for program 0x{}, this build runs 0x{}",
+                hex::encode(&envelope.program_digest),
+                hex::encode(&self.digest)
+            ));
+        }
+        if envelope.vkey_hash != self.vkey_hash {
+            return Err(format!(
+                "the proof is for verifying key 0x{}, this build has 0x{}",
+                hex::encode(&envelope.vkey_hash),
+                hex::encode(&self.vkey_hash)
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub trait Prover {
+    fn execute(&self, input: &ProofInput) -> Result<Execution, String>;
+
+    // the program this build proves, needs the verifying key.
+    fn program(&self) -> Result<Program, String>;
+
+    // prove and verify once, the envelope is what gets saved.
+    fn prove(&self, input: &ProofInput) -> Result<ProofEnvelope, String>;
+
+    // check the proof against this build's guest and return the public values it proves.
+    fn verify(&self, envelope: &ProofEnvelope) -> Result<PublicValues, String>;
+
+    // hand the proof off to a remote prover and return the id to fetch it with.
+    fn submit(&self, _input: &ProofInput) -> Result<String, String> {
+        Err("this backend only proves locally".into())
+    }
+
+    // wait for a submitted proof and return it the way prove does.
+    fn fetch(&self, _request: &str) -> Result<ProofEnvelope, String> {
+        Err("this backend only proves locally".into())
+    }
+}
+
+// the backend comes separately from the config's, verify takes it from the proof. the config
+// is expected to have been validated. with config.mock the backend only executes.
+#[cfg_attr(
+    not(any(feature = "sp1", feature = "openvm")),
+    allow(unused_variables, unreachable_code)
+)]
+pub fn prover(backend: Backend, config: &ProverConfig) -> Result<Box<dyn Prover>, String> {
+    let prover: Box<dyn Prover> = match backend {
+        #[cfg(feature = "sp1")]
+        Backend::Sp1 => Box::new(sp1::Sp1::new(config)?),
+        #[cfg(feature = "openvm")]
+        Backend::OpenVm => Box::new(openvm::OpenVm::new(config)?),
+        _ => {
+            return Err(format!(
+                "{:?} isn't available, build with its feature (sp1, openvm)",
+                backend
+            ))
+        }
+    };
+    if config.mock {
+        return Ok(Box::new(mock::Mock::new(backend, prover)));
+    }
+    Ok(prover)
+}
+
+pub fn parse_backend(arg: &str) -> Result<Backend, String> {
+    match arg {
+        "sp1" => Ok(Backend::Sp1),
+        "openvm" => Ok(Backend::OpenVm),
+        _ => Err(format!("unknown backend '{}', expected sp1 or openvm", arg)),
+    }
+}
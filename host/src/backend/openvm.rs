@@ -0,0 +1,219 @@
+// the guest in openvm_proof, built and transpiled through the SDK when the backend is created
+// unless guest_cache has it from an earlier run.
+// it reveals the public values word by word, see sudoku_io::to_reveal_words.
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use openvm_build::GuestOptions;
+use openvm_circuit::arch::{instructions::exe::VmExe, ContinuationVmProof, SystemConfig};
+use openvm_sdk::{
+    config::{AppConfig, SdkVmConfig},
+    keygen::AppProvingKey,
+    Sdk, StdIn, F, SC,
+};
+use openvm_stark_sdk::config::FriParameters;
+use openvm_stark_sdk::openvm_stark_backend::p3_field::PrimeField32;
+use sudoku::core::commitment::sha256;
+use sudoku_io::envelope::{Backend, ProofEnvelope, ProvedOn};
+use sudoku_io::{from_reveal_words, ProofInput, PublicValues, REVEAL_SLOTS};
+
+use super::{Execution, Program, Prover};
+use crate::config::{LocalProver, ProverConfig};
+use crate::guest_cache;
+
+pub struct OpenVm {
+    sdk: Sdk,
+    vm_config: SdkVmConfig,
+    exe: VmExe<F>,
+    // sha256 of the serialized executable, the guest as it runs.
+    digest: Vec<u8>,
+    log_blowup: usize,
+    cache_dir: PathBuf,
+    rebuild: bool,
+}
+
+impl OpenVm {
+    // config.rebuild skips the cache, for changes the cache key doesn't see.
+    pub fn new(config: &ProverConfig) -> Result<Self, String> {
+        // this SDK only proves on the cpu.
+        if config.local_prover == LocalProver::Cuda {
+            eprintln!("OpenVM has no CUDA prover here, proving on the CPU");
+        }
+        // the guest needs the io and sha256 extensions next to rv32i/rv32m (see its
+        // openvm.toml), and every reveal slot is 4 bytes of public values.
+        let vm_config = SdkVmConfig::builder()
+            .system(
+                SystemConfig::default()
+                    .with_public_values(REVEAL_SLOTS * 4)
+                    .into(),
+            )
+            .rv32i(Default::default())
+            .rv32m(Default::default())
+            .io(Default::default())
+            .sha256(Default::default())
+            .build();
+        let sdk = Sdk;
+
+        let mut guest_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        guest_path.pop();
+        guest_path.push("openvm_proof");
+        let key = guest_cache::source_key(&guest_path)?;
+        let cached = match config.rebuild {
+            true => None,
+            false => guest_cache::load(&config.cache_dir, "openvm", &key),
+        };
+        let exe = match cached.and_then(|bytes| bincode::deserialize::<VmExe<F>>(&bytes).ok()) {
+            Some(exe) => exe,
+            None => {
+                let elf = sdk
+                    .build(
+                        GuestOptions::default(),
+                        guest_path.to_str().unwrap(),
+                        &Default::default(),
+                    )
+                    .map_err(|e| format!("failed to build the guest ({})", e))?;
+                let exe = sdk
+                    .transpile(elf, vm_config.transpiler())
+                    .map_err(|e| format!("failed to transpile the guest ({})", e))?;
+                let bytes = bincode::serialize(&exe).map_err(|e| e.to_string())?;
+                guest_cache::store(&config.cache_dir, "openvm", &key, &bytes)?;
+                exe
+            }
+        };
+        let digest = sha256(&bincode::serialize(&exe).map_err(|e| e.to_string())?).to_vec();
+        Ok(OpenVm {
+            sdk,
+            vm_config,
+            exe,
+            digest,
+            log_blowup: config.log_blowup,
+            cache_dir: config.cache_dir.clone(),
+            rebuild: config.rebuild,
+        })
+    }
+
+    // the app key only depends on the config, it's cached under the config's hash.
+    fn keygen(&self) -> Result<(Arc<AppProvingKey<SdkVmConfig>>, Vec<u8>), String> {
+        let config = AppConfig::new(fri_params(self.log_blowup), self.vm_config.clone());
+        let key = sha256(&bincode::serialize(&config).map_err(|e| e.to_string())?);
+        let cached = match self.rebuild {
+            true => None,
+            false => guest_cache::load(&self.cache_dir, "openvm-app-pk", &key),
+        };
+        let app_pk = match cached.and_then(|bytes| bincode::deserialize(&bytes).ok()) {
+            Some(app_pk) => app_pk,
+            None => {
+                let app_pk = self
+                    .sdk
+                    .app_keygen(config)
+                    .map_err(|e| format!("failed to generate the app proving key ({})", e))?;
+                let bytes = bincode::serialize(&app_pk).map_err(|e| e.to_string())?;
+                guest_cache::store(&self.cache_dir, "openvm-app-pk", &key, &bytes)?;
+                app_pk
+            }
+        };
+        let vk = bincode::serialize(&app_pk.get_app_vk()).map_err(|e| e.to_string())?;
+        Ok((Arc::new(app_pk), sha256(&vk).to_vec()))
+    }
+}
+
+fn fri_params(log_blowup: usize) -> FriParameters {
+    FriParameters::standard_with_100_bits_conjectured_security(log_blowup)
+}
+
+fn stdin(input: &ProofInput) -> Result<StdIn, String> {
+    let mut stdin = StdIn::default();
+    for bytes in crate::stdin::openvm(input)? {
+        stdin.write_bytes(&bytes);
+    }
+    Ok(stdin)
+}
+
+// the revealed public values are one field element per byte, four per reveal slot in little
+// endian order.
+fn decode(output: &[F]) -> Result<PublicValues, String> {
+    if output.len() < REVEAL_SLOTS * 4 {
+        return Err(format!(
+            "the guest revealed {} bytes, expected {}",
+            output.len(),
+            REVEAL_SLOTS * 4
+        ));
+    }
+    let mut words = [0u32; REVEAL_SLOTS];
+    for (word, bytes) in words.iter_mut().zip(output.chunks_exact(4)) {
+        *word = bytes
+            .iter()
+            .rev()
+            .fold(0, |word, byte| (word << 8) | byte.as_canonical_u32());
+    }
+    Ok(from_reveal_words(&words))
+}
+
+impl Prover for OpenVm {
+    fn execute(&self, input: &ProofInput) -> Result<Execution, String> {
+        let output = self
+            .sdk
+            .execute(self.exe.clone(), self.vm_config.clone(), stdin(input)?)
+            .map_err(|e| format!("failed to execute the guest ({})", e))?;
+        Ok(Execution {
+            values: decode(&output)?,
+            cycles: None,
+            phases: Vec::new(),
+        })
+    }
+
+    // the app key only depends on the vm config, the digest is what tells guests apart.
+    fn program(&self) -> Result<Program, String> {
+        let (_, vkey_hash) = self.keygen()?;
+        Ok(Program {
+            vkey_hash,
+            digest: self.digest.clone(),
+        })
+    }
+
+    fn prove(&self, input: &ProofInput) -> Result<ProofEnvelope, String> {
+        let (app_pk, vkey_hash) = self.keygen()?;
+        let committed_exe = self
+            .sdk
+            .commit_app_exe(fri_params(self.log_blowup), self.exe.clone())
+            .map_err(|e| format!("failed to commit the executable ({})", e))?;
+        let proof = self
+            .sdk
+            .generate_app_proof(app_pk.clone(), committed_exe, stdin(input)?)
+            .map_err(|e| format!("failed to generate the proof ({})", e))?;
+        self.sdk
+            .verify_app_proof(&app_pk.get_app_vk(), &proof)
+            .map_err(|e| format!("the proof doesn't verify ({})", e))?;
+
+        // saved abi encoded like every other backend's, verify checks them against the proof.
+        let values = decode(&proof.user_public_values.public_values)?;
+        Ok(ProofEnvelope {
+            backend: Backend::OpenVm,
+            proved_on: ProvedOn::Cpu,
+            vkey_hash,
+            program_digest: self.digest.clone(),
+            public_values: values.abi_encode().to_vec(),
+            proof: bincode::serialize(&proof).map_err(|e| e.to_string())?,
+        })
+    }
+
+    fn verify(&self, envelope: &ProofEnvelope) -> Result<PublicValues, String> {
+        let (app_pk, vkey_hash) = self.keygen()?;
+        Program {
+            vkey_hash,
+            digest: self.digest.clone(),
+        }
+        .check(envelope)?;
+        let proof: ContinuationVmProof<SC> = bincode::deserialize(&envelope.proof)
+            .map_err(|e| format!("malformed proof ({})", e))?;
+        self.sdk
+            .verify_app_proof(&app_pk.get_app_vk(), &proof)
+            .map_err(|e| format!("the proof doesn't verify ({})", e))?;
+
+        let values = decode(&proof.user_public_values.public_values)?;
+        if values.abi_encode()[..] != envelope.public_values[..] {
+            return Err("the saved public values don't match the proof's".into());
+        }
+        Ok(values)
+    }
+}
@@ -0,0 +1,285 @@
+// the verifier program in sp1_proof/program, built by build.rs. it commits the abi encoded
+// public values.
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use alloy_primitives::B256;
+use sp1_sdk::{
+    include_elf, CpuProver, CudaProver, ExecutionReport, HashableKey, NetworkProver, Prover as _,
+    ProverClient, SP1ProofWithPublicValues, SP1ProvingKey, SP1PublicValues, SP1Stdin,
+    SP1VerifyingKey,
+};
+use sudoku::core::commitment::sha256;
+use sudoku_io::envelope::{Backend, ProofEnvelope, ProvedOn};
+use sudoku_io::{ProofInput, PublicValues};
+
+use super::{Execution, Program, Prover};
+use crate::config::{LocalProver, ProverConfig};
+use crate::guest_cache;
+
+pub const VERIFIER_ELF: &[u8] = include_elf!("fibonacci-program");
+
+pub struct Sp1 {
+    client: LocalClient,
+    // set with --network, proofs are requested from the prover network instead of generated
+    // here. executing and verifying stay local.
+    network: Option<Network>,
+    cache_dir: PathBuf,
+    rebuild: bool,
+}
+
+struct Network {
+    prover: NetworkProver,
+    timeout: Duration,
+}
+
+impl Sp1 {
+    pub fn new(config: &ProverConfig) -> Result<Self, String> {
+        let args = &config.network;
+        let network = if args.enabled {
+            let key = args
+                .private_key
+                .as_deref()
+                .ok_or("the prover network needs NETWORK_PRIVATE_KEY or --private-key")?;
+            let builder = ProverClient::builder().network().private_key(key);
+            let builder = match &args.rpc_url {
+                Some(url) => builder.rpc_url(url),
+                None => builder,
+            };
+            Some(Network {
+                prover: builder.build(),
+                timeout: Duration::from_secs(args.timeout),
+            })
+        } else {
+            None
+        };
+        Ok(Sp1 {
+            client: LocalClient::new(config),
+            network,
+            cache_dir: config.cache_dir.clone(),
+            rebuild: config.rebuild,
+        })
+    }
+
+    // keygen takes longer than most executions, the keys are cached per ELF.
+    fn setup(&self) -> Result<(SP1ProvingKey, SP1VerifyingKey), String> {
+        let key = sha256(VERIFIER_ELF);
+        if !self.rebuild {
+            let cached = guest_cache::load(&self.cache_dir, "sp1-keys", &key);
+            if let Some(keys) = cached.and_then(|bytes| bincode::deserialize(&bytes).ok()) {
+                return Ok(keys);
+            }
+        }
+        let keys = self.client.setup(VERIFIER_ELF);
+        let bytes = bincode::serialize(&keys).map_err(|e| e.to_string())?;
+        guest_cache::store(&self.cache_dir, "sp1-keys", &key, &bytes)?;
+        Ok(keys)
+    }
+
+    fn network(&self) -> Result<&Network, String> {
+        self.network
+            .as_ref()
+            .ok_or_else(|| "requests go through the prover network, pass --network".into())
+    }
+
+    // verify a freshly generated proof and pack it up for saving.
+    fn envelope(
+        &self,
+        proof: SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+        proved_on: ProvedOn,
+    ) -> Result<ProofEnvelope, String> {
+        self.client.verify(&proof, vk)?;
+        let program = program(vk);
+        Ok(ProofEnvelope {
+            backend: Backend::Sp1,
+            proved_on,
+            vkey_hash: program.vkey_hash,
+            program_digest: program.digest,
+            public_values: proof.public_values.to_vec(),
+            proof: bincode::serialize(&proof).map_err(|e| e.to_string())?,
+        })
+    }
+}
+
+// the prover local proofs are made with, built from the config rather than SP1_PROVER so batch
+// workers never touch the environment. the CUDA prover runs SP1's GPU container in docker
+// (its image is the SDK's, or SP1_GPU_IMAGE), so it's only picked when docker and an NVIDIA
+// driver answer, proofs are made on the cpu otherwise.
+enum LocalClient {
+    Cpu(CpuProver),
+    Cuda(CudaProver),
+}
+
+impl LocalClient {
+    fn new(config: &ProverConfig) -> Self {
+        if config.local_prover == LocalProver::Cuda {
+            match cuda_missing() {
+                None => return LocalClient::Cuda(ProverClient::builder().cuda().build()),
+                Some(missing) => eprintln!("{}, proving on the CPU", missing),
+            }
+        }
+        LocalClient::Cpu(ProverClient::builder().cpu().build())
+    }
+
+    fn proved_on(&self) -> ProvedOn {
+        match self {
+            LocalClient::Cpu(_) => ProvedOn::Cpu,
+            LocalClient::Cuda(_) => ProvedOn::Cuda,
+        }
+    }
+
+    fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
+        match self {
+            LocalClient::Cpu(client) => client.setup(elf),
+            LocalClient::Cuda(client) => client.setup(elf),
+        }
+    }
+
+    fn execute(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+    ) -> Result<(SP1PublicValues, ExecutionReport), String> {
+        let result = match self {
+            LocalClient::Cpu(client) => sp1_sdk::Prover::execute(client, elf, stdin),
+            LocalClient::Cuda(client) => sp1_sdk::Prover::execute(client, elf, stdin),
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn prove(
+        &self,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+    ) -> Result<SP1ProofWithPublicValues, String> {
+        let result = match self {
+            LocalClient::Cpu(client) => client.prove(pk, stdin).run(),
+            LocalClient::Cuda(client) => client.prove(pk, stdin).run(),
+        };
+        result.map_err(|e| e.to_string())
+    }
+
+    fn verify(&self, proof: &SP1ProofWithPublicValues, vk: &SP1VerifyingKey) -> Result<(), String> {
+        let result = match self {
+            LocalClient::Cpu(client) => client.verify(proof, vk),
+            LocalClient::Cuda(client) => client.verify(proof, vk),
+        };
+        result.map_err(|e| e.to_string())
+    }
+}
+
+// why the CUDA prover can't run here, if it can't.
+fn cuda_missing() -> Option<&'static str> {
+    let answers = |program: &str, arg: &str| {
+        Command::new(program)
+            .arg(arg)
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    if !answers("nvidia-smi", "-L") {
+        Some("no NVIDIA driver for the CUDA prover")
+    } else if !answers("docker", "info") {
+        Some("the CUDA prover needs docker, which isn't running")
+    } else {
+        None
+    }
+}
+
+fn stdin(input: &ProofInput) -> Result<SP1Stdin, String> {
+    let mut stdin = SP1Stdin::new();
+    for buffer in crate::stdin::sp1(input)? {
+        stdin.write_vec(buffer);
+    }
+    Ok(stdin)
+}
+
+fn decode(public_values: &[u8]) -> Result<PublicValues, String> {
+    PublicValues::abi_decode(public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))
+}
+
+// the vkey hash is the 32 bytes a contract gets from `HashableKey::bytes32`.
+fn program(vk: &SP1VerifyingKey) -> Program {
+    Program {
+        vkey_hash: hex::decode(vk.bytes32().trim_start_matches("0x")).unwrap(),
+        digest: sha256(VERIFIER_ELF).to_vec(),
+    }
+}
+
+impl Prover for Sp1 {
+    fn execute(&self, input: &ProofInput) -> Result<Execution, String> {
+        let (output, report) = self.client.execute(VERIFIER_ELF, &stdin(input)?)?;
+        let mut phases: Vec<(String, u64)> = report
+            .cycle_tracker
+            .iter()
+            .map(|(phase, cycles)| (phase.clone(), *cycles))
+            .collect();
+        phases.sort();
+        Ok(Execution {
+            values: decode(output.as_slice())?,
+            cycles: Some(report.total_instruction_count()),
+            phases,
+        })
+    }
+
+    fn program(&self) -> Result<Program, String> {
+        let (_, vk) = self.setup()?;
+        Ok(program(&vk))
+    }
+
+    fn prove(&self, input: &ProofInput) -> Result<ProofEnvelope, String> {
+        if self.network.is_some() {
+            let request = self.submit(input)?;
+            return self.fetch(&request);
+        }
+        let (pk, vk) = self.setup()?;
+        let proof = self.client.prove(&pk, &stdin(input)?)?;
+        self.envelope(proof, &vk, self.client.proved_on())
+    }
+
+    fn verify(&self, envelope: &ProofEnvelope) -> Result<PublicValues, String> {
+        let (_, vk) = self.setup()?;
+        program(&vk).check(envelope)?;
+        let proof: SP1ProofWithPublicValues = bincode::deserialize(&envelope.proof)
+            .map_err(|e| format!("malformed proof ({})", e))?;
+        if proof.public_values.as_slice() != envelope.public_values {
+            return Err("the saved public values don't match the proof's".into());
+        }
+        self.client
+            .verify(&proof, &vk)
+            .map_err(|e| format!("the proof doesn't verify ({})", e))?;
+        decode(&envelope.public_values)
+    }
+
+    fn submit(&self, input: &ProofInput) -> Result<String, String> {
+        let network = self.network()?;
+        let (pk, _) = self.setup()?;
+        let request = network
+            .prover
+            .prove(&pk, &stdin(input)?)
+            .request()
+            .map_err(|e| format!("the prover network refused the request ({})", e))?;
+        println!("proof requested: {}", request);
+        Ok(request.to_string())
+    }
+
+    // polls the network until the request is fulfilled, or fails after --timeout seconds.
+    fn fetch(&self, request: &str) -> Result<ProofEnvelope, String> {
+        let network = self.network()?;
+        let request: B256 = request
+            .parse()
+            .map_err(|e| format!("bad request id '{}' ({})", request, e))?;
+        println!("waiting for {} (up to {:?})", request, network.timeout);
+
+        let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+        let proof: SP1ProofWithPublicValues = runtime
+            .block_on(network.prover.wait_proof(request, Some(network.timeout)))
+            .map_err(|e| format!("request {} didn't complete ({})", request, e))?;
+        let (_, vk) = self.setup()?;
+        self.envelope(proof, &vk, ProvedOn::Network)
+    }
+}
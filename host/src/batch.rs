@@ -0,0 +1,477 @@
+// proving many submissions at once: a directory of puzzle/solution files or a JSONL file of
+// them, proved by a fixed number of workers with their own prover each. every submission gets
+// its proof file, and summary.json lists how each one went, with the salt needed to open its
+// solution commitment.
+//
+// job.json in the output directory records every step as it happens (the salt picked, the
+// prover network request, the proof saved), so --resume can pick an interrupted run up: proved
+// submissions are skipped, requests already on the network are fetched instead of paid for
+// again, and the rest are proved with the salt they were first given.
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use serde_json::{json, Value};
+use sudoku::core::board::Board;
+use sudoku::core::commitment::puzzle_hash;
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use zk_sudoku_host::backend::{prover, Prover};
+use zk_sudoku_host::config::ProverConfig;
+use zk_sudoku_host::input::{
+    parse_digits, parse_json_board, puzzle_from_id, read_board, solve, SubmissionArgs,
+};
+use zk_sudoku_host::{check_proof, checked_input};
+
+#[derive(clap::Args, Debug)]
+pub struct BatchArgs {
+    /// A directory of <name>.puzzle files, each with an optional <name>.solution next to it, or
+    /// a .jsonl file of {"name", "puzzle", "solution"} objects (boards as digit strings or
+    /// arrays, puzzles also as puzzle ids, the name and solution optional). Missing solutions are solved.
+    #[clap(long)]
+    pub input: PathBuf,
+
+    /// Directory the proofs (<name>.bin), summary.json and job.json are written to, relative to
+    /// the output directory.
+    #[clap(long, default_value = "proofs")]
+    pub out_dir: PathBuf,
+
+    /// Continue the run whose --out-dir this is from its job.json: submissions already proved
+    /// for the same --address and --epoch are skipped and pending network requests fetched. Starts afresh if there's no job yet,
+    /// so rerunning the same command until it succeeds is safe.
+    #[clap(long, conflicts_with = "out_dir")]
+    pub resume: Option<PathBuf>,
+
+    /// Submissions proved at the same time. Every worker holds its own prover, so memory use
+    /// grows with it.
+    #[clap(long, default_value = "2")]
+    pub workers: usize,
+
+    #[clap(flatten)]
+    pub submission: SubmissionArgs,
+}
+
+struct Entry {
+    name: String,
+    puzzle: Board,
+    solution: Option<Board>,
+}
+
+struct Report {
+    proof: Result<PathBuf, String>,
+    salt: Option<[u8; 32]>,
+    seconds: f64,
+    // proved by an earlier run.
+    resumed: bool,
+}
+
+// job.json, what's been done for each submission by name.
+struct Job {
+    path: PathBuf,
+    backend: Backend,
+    entries: Mutex<HashMap<String, JobEntry>>,
+}
+
+#[derive(Clone, Default)]
+struct JobEntry {
+    // a submission renamed onto another's puzzle starts over.
+    puzzle_hash: [u8; 32],
+    salt: Option<[u8; 32]>,
+    request: Option<String>,
+    proof: Option<PathBuf>,
+}
+
+impl Job {
+    fn new(path: PathBuf, backend: Backend) -> Self {
+        Job {
+            path,
+            backend,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // a job made with another backend can't be resumed with this one.
+    fn load(path: PathBuf, backend: Backend) -> Result<Self, String> {
+        let job = Job::new(path, backend);
+        if !job.path.exists() {
+            return Ok(job);
+        }
+        let error = |e: String| format!("{}: {}", job.path.display(), e);
+        let text = std::fs::read_to_string(&job.path).map_err(|e| error(e.to_string()))?;
+        let value: Value = serde_json::from_str(&text).map_err(|e| error(e.to_string()))?;
+        let proved_with = value["backend"].as_str().unwrap_or("an unknown backend");
+        if proved_with != backend.name() {
+            return Err(error(format!(
+                "the job was proved with {}, not {}",
+                proved_with,
+                backend.name()
+            )));
+        }
+        let hex32 = |value: &Value| -> Result<Option<[u8; 32]>, String> {
+            match value {
+                Value::Null => Ok(None),
+                Value::String(text) => hex::decode(text.trim_start_matches("0x"))
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .map(Some)
+                    .ok_or_else(|| format!("'{}' isn't 32 bytes of hex", text)),
+                _ => Err("expected a hex string".into()),
+            }
+        };
+        let Value::Object(entries) = &value["entries"] else {
+            return Err(error("no entries".into()));
+        };
+        let mut loaded = job.entries.lock().unwrap();
+        for (name, entry) in entries {
+            let entry = JobEntry {
+                puzzle_hash: hex32(&entry["puzzleHash"])
+                    .map_err(&error)?
+                    .ok_or_else(|| error(format!("{} has no puzzle hash", name)))?,
+                salt: hex32(&entry["salt"]).map_err(&error)?,
+                request: entry["request"].as_str().map(String::from),
+                proof: entry["proof"].as_str().map(PathBuf::from),
+            };
+            loaded.insert(name.clone(), entry);
+        }
+        drop(loaded);
+        Ok(job)
+    }
+
+    // what was done for the entry, if it was for the same puzzle.
+    fn get(&self, entry: &Entry) -> JobEntry {
+        let puzzle_hash = puzzle_hash(&entry.puzzle);
+        match self.entries.lock().unwrap().get(&entry.name) {
+            Some(done) if done.puzzle_hash == puzzle_hash => done.clone(),
+            _ => JobEntry {
+                puzzle_hash,
+                ..Default::default()
+            },
+        }
+    }
+
+    // record a step and save the whole job, written to a temporary file first so an
+    // interruption leaves the previous state rather than half a file.
+    fn update(&self, name: &str, entry: JobEntry) -> Result<(), String> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.insert(name.to_string(), entry);
+        let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+        let json = json!({
+            "backend": self.backend.name(),
+            "entries": entries
+                .iter()
+                .map(|(name, entry)| {
+                    let value = json!({
+                        "puzzleHash": hex(&entry.puzzle_hash),
+                        "salt": entry.salt.map(|salt| hex(&salt)),
+                        "request": entry.request,
+                        "proof": entry.proof,
+                    });
+                    (name.clone(), value)
+                })
+                .collect::<serde_json::Map<_, _>>(),
+        });
+        let text = serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?;
+        let partial = self.path.with_extension("json.partial");
+        std::fs::write(&partial, text)
+            .and_then(|_| std::fs::rename(&partial, &self.path))
+            .map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+}
+
+pub fn run(args: &BatchArgs, config: &ProverConfig) -> Result<(), String> {
+    let entries = read_entries(&args.input)?;
+    if entries.is_empty() {
+        return Err(format!("no submissions in {}", args.input.display()));
+    }
+    let out_dir = config.output_path(args.resume.as_ref().unwrap_or(&args.out_dir));
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("{}: {}", out_dir.display(), e))?;
+    let job_path = out_dir.join("job.json");
+    let job = match args.resume {
+        Some(_) => Job::load(job_path, config.backend)?,
+        None => Job::new(job_path, config.backend),
+    };
+    println!(
+        "proving {} submissions with {} workers",
+        entries.len(),
+        args.workers
+    );
+
+    // workers take the next entry until none are left. a worker whose prover can't be created
+    // stops, its error is reported for whatever no other worker got to.
+    let next = AtomicUsize::new(0);
+    let reports: Mutex<Vec<Option<Report>>> = Mutex::new(entries.iter().map(|_| None).collect());
+    let startup_error = Mutex::new(None);
+    std::thread::scope(|scope| {
+        for _ in 0..args.workers.clamp(1, entries.len()) {
+            scope.spawn(|| {
+                let prover = match prover(config.backend, config) {
+                    Ok(prover) => prover,
+                    Err(e) => {
+                        *startup_error.lock().unwrap() = Some(e);
+                        return;
+                    }
+                };
+                loop {
+                    let index = next.fetch_add(1, Ordering::SeqCst);
+                    let Some(entry) = entries.get(index) else {
+                        break;
+                    };
+                    let report = prove_entry(prover.as_ref(), entry, args, config, &job, &out_dir);
+                    match &report.proof {
+                        Ok(path) if report.resumed => {
+                            println!("{}: already proved in {}", entry.name, path.display())
+                        }
+                        Ok(path) => println!("{}: saved to {}", entry.name, path.display()),
+                        Err(e) => println!("{}: failed ({})", entry.name, e),
+                    }
+                    reports.lock().unwrap()[index] = Some(report);
+                }
+            });
+        }
+    });
+
+    let startup_error = startup_error.into_inner().unwrap();
+    let reports: Vec<Report> = reports
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|report| {
+            report.unwrap_or_else(|| Report {
+                proof: Err(format!(
+                    "no worker started ({})",
+                    startup_error.as_deref().unwrap_or("unknown error")
+                )),
+                salt: None,
+                seconds: 0.0,
+                resumed: false,
+            })
+        })
+        .collect();
+    write_summary(&out_dir, &entries, &reports)?;
+
+    let failed = reports
+        .iter()
+        .filter(|report| report.proof.is_err())
+        .count();
+    println!(
+        "{} proved, {} failed, summary in {}",
+        reports.len() - failed,
+        failed,
+        out_dir.join("summary.json").display()
+    );
+    if failed > 0 {
+        return Err(format!(
+            "{} of {} submissions failed",
+            failed,
+            reports.len()
+        ));
+    }
+    Ok(())
+}
+
+fn prove_entry(
+    prover: &dyn Prover,
+    entry: &Entry,
+    args: &BatchArgs,
+    config: &ProverConfig,
+    job: &Job,
+    out_dir: &Path,
+) -> Report {
+    let start = Instant::now();
+    let mut done = job.get(entry);
+    if let Some(path) = done
+        .proof
+        .as_ref()
+        .filter(|path| proved(path, &done.puzzle_hash, &args.submission))
+    {
+        return Report {
+            proof: Ok(path.clone()),
+            salt: done.salt,
+            seconds: 0.0,
+            resumed: true,
+        };
+    }
+    let proof = (|| {
+        let solution = match &entry.solution {
+            Some(solution) => solution.clone(),
+            None => solve(&entry.puzzle)?,
+        };
+        // the salt an earlier run committed to, a network request already proves it.
+        let submission = SubmissionArgs {
+            salt: done.salt.or(args.submission.salt),
+            ..args.submission.clone()
+        };
+        let (envelope, input) = match &done.request {
+            Some(request) => {
+                if !config.network.enabled {
+                    return Err(format!(
+                        "requested from the prover network ({}), resume with --network",
+                        request
+                    ));
+                }
+                let source = submission.puzzle_source(&entry.puzzle);
+                let input = submission.proof_input(source, &entry.puzzle, &solution)?;
+                (prover.fetch(request)?, input)
+            }
+            None => {
+                let input = checked_input(prover, &entry.puzzle, &solution, &submission)?;
+                done.salt = Some(input.salt);
+                if config.network.enabled {
+                    let request = prover.submit(&input)?;
+                    done.request = Some(request.clone());
+                    job.update(&entry.name, done.clone())?;
+                    (prover.fetch(&request)?, input)
+                } else {
+                    job.update(&entry.name, done.clone())?;
+                    (prover.prove(&input)?, input)
+                }
+            }
+        };
+        let proof = check_proof(envelope, &entry.puzzle, &input)?;
+        let path = out_dir.join(format!("{}.bin", entry.name));
+        std::fs::write(&path, proof.envelope.encode())
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        done.proof = Some(path.clone());
+        job.update(&entry.name, done.clone())?;
+        Ok(path)
+    })();
+    Report {
+        proof,
+        salt: done.salt,
+        seconds: start.elapsed().as_secs_f64(),
+        resumed: false,
+    }
+}
+
+// a proof an earlier run saved, still there and for the puzzle, with the reward address and
+// epoch of this run: resuming with another --address or --epoch proves it again.
+fn proved(path: &Path, puzzle_hash: &[u8; 32], submission: &SubmissionArgs) -> bool {
+    std::fs::read(path)
+        .ok()
+        .and_then(|bytes| ProofEnvelope::decode(&bytes).ok())
+        .and_then(|envelope| sudoku_io::PublicValues::abi_decode(&envelope.public_values).ok())
+        .is_some_and(|values| {
+            values.puzzle_hash == *puzzle_hash
+                && values.reward_address == submission.address
+                && values.epoch == submission.epoch
+        })
+}
+
+fn write_summary(out_dir: &Path, entries: &[Entry], reports: &[Report]) -> Result<(), String> {
+    let summary: Vec<Value> = entries
+        .iter()
+        .zip(reports)
+        .map(|(entry, report)| {
+            json!({
+                "name": entry.name,
+                "puzzleHash": format!("0x{}", hex::encode(puzzle_hash(&entry.puzzle))),
+                "proved": report.proof.is_ok(),
+                "proof": report.proof.as_ref().ok(),
+                "error": report.proof.as_ref().err(),
+                "salt": report.salt.map(|salt| format!("0x{}", hex::encode(salt))),
+                "seconds": report.seconds,
+                "resumed": report.resumed,
+            })
+        })
+        .collect();
+    let path = out_dir.join("summary.json");
+    let text = serde_json::to_string_pretty(&summary).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn read_entries(path: &Path) -> Result<Vec<Entry>, String> {
+    if path.is_dir() {
+        read_dir_entries(path)
+    } else {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        read_jsonl_entries(&text)
+    }
+}
+
+// names key the proof files and job.json, two entries with one name would overwrite each
+// other's proof. compared ignoring case, as the file system may.
+fn check_names(entries: &[Entry]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for entry in entries {
+        if !seen.insert(entry.name.to_lowercase()) {
+            return Err(format!("duplicate name '{}'", entry.name));
+        }
+    }
+    Ok(())
+}
+
+fn read_dir_entries(dir: &Path) -> Result<Vec<Entry>, String> {
+    let mut puzzles = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|ext| ext == "puzzle") {
+            puzzles.push(path);
+        }
+    }
+    puzzles.sort();
+
+    let entries = puzzles
+        .into_iter()
+        .map(|path| {
+            let solution = path.with_extension("solution");
+            Ok(Entry {
+                name: path.file_stem().unwrap().to_string_lossy().into_owned(),
+                puzzle: read_board(&path)?,
+                solution: match solution.exists() {
+                    true => Some(read_board(&solution)?),
+                    false => None,
+                },
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    check_names(&entries).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    Ok(entries)
+}
+
+// blank lines are skipped, unnamed entries are called after their line number.
+fn read_jsonl_entries(text: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_error = |e: String| format!("line {}: {}", number + 1, e);
+        let value: Value = serde_json::from_str(line).map_err(|e| line_error(e.to_string()))?;
+        let name = match &value["name"] {
+            Value::String(name) => name.clone(),
+            Value::Null => format!("entry-{}", number + 1),
+            _ => return Err(line_error("the name isn't a string".into())),
+        };
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(line_error(format!("'{}' can't name a proof file", name)));
+        }
+        let puzzle = match &value["puzzle"] {
+            Value::Null => return Err(line_error("no puzzle".into())),
+            puzzle => board_value(puzzle).map_err(line_error)?,
+        };
+        let solution = match &value["solution"] {
+            Value::Null => None,
+            solution => Some(board_value(solution).map_err(line_error)?),
+        };
+        entries.push(Entry {
+            name,
+            puzzle,
+            solution,
+        });
+    }
+    check_names(&entries)?;
+    Ok(entries)
+}
+
+fn board_value(value: &Value) -> Result<Board, String> {
+    match value {
+        Value::String(text) if text.trim_start().to_ascii_lowercase().starts_with("zk") => {
+            puzzle_from_id(text)
+        }
+        Value::String(digits) => parse_digits(digits),
+        Value::Array(_) => parse_json_board(value.to_string().as_bytes()),
+        _ => Err("a board is a string of digits, an array of cells or a puzzle id".into()),
+    }
+}
@@ -0,0 +1,210 @@
+// proving a campaign: an ordered list of puzzle ids with the player's solution to each, proved
+// one after the other. the proofs are saved as <index>-<id>.bin, and attestation.json sums the
+// campaign up in what the batch guest commits (sudoku_io::aggregate): the number of puzzles,
+// how many were solved and the merkle root over each proof's result leaf, in campaign order.
+// a batch proof of the same submissions commits the same root, and the root with the ordered
+// leaves is what a contract needs to check any one puzzle of the campaign was solved.
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use sudoku::core::board::Board;
+use sudoku_io::puzzle_id::PuzzleId;
+use sudoku_io::{aggregate, merkle_path, result_leaf, PuzzleSource, LOGIC_VERSION};
+use zk_sudoku_host::backend::prover;
+use zk_sudoku_host::config::ProverConfig;
+use zk_sudoku_host::input::{
+    parse_digits, parse_json_board, parse_puzzle_id, solve, SubmissionArgs,
+};
+use zk_sudoku_host::{check_input, check_proof, SolutionProof};
+
+#[derive(clap::Args, Debug)]
+pub struct CampaignArgs {
+    /// JSON file with the campaign: {"name": ..., "puzzles": [{"id": "zk1-...", "solution":
+    /// ...}, ...]}, solutions as digit strings or arrays. A puzzle without a solution is solved,
+    /// the name is optional.
+    #[clap(long)]
+    pub manifest: PathBuf,
+
+    /// Directory the proofs (<index>-<id>.bin) and attestation.json are written to, relative to
+    /// the output directory.
+    #[clap(long, default_value = "campaign")]
+    pub out_dir: PathBuf,
+
+    #[clap(flatten)]
+    pub submission: SubmissionArgs,
+}
+
+struct Manifest {
+    name: Option<String>,
+    puzzles: Vec<Entry>,
+}
+
+struct Entry {
+    id: PuzzleId,
+    puzzle: Board,
+    solution: Option<Board>,
+}
+
+pub fn run(args: &CampaignArgs, config: &ProverConfig) -> Result<(), String> {
+    let text = std::fs::read_to_string(&args.manifest)
+        .map_err(|e| format!("{}: {}", args.manifest.display(), e))?;
+    let manifest =
+        read_manifest(&text).map_err(|e| format!("{}: {}", args.manifest.display(), e))?;
+    if manifest.puzzles.is_empty() {
+        return Err(format!("no puzzles in {}", args.manifest.display()));
+    }
+    let out_dir = config.output_path(&args.out_dir);
+    std::fs::create_dir_all(&out_dir).map_err(|e| format!("{}: {}", out_dir.display(), e))?;
+
+    // in order, and the first failure ends the campaign: an attestation leaving a puzzle out
+    // would attest to another campaign.
+    let prover = prover(config.backend, config)?;
+    let count = manifest.puzzles.len();
+    let mut proofs = Vec::new();
+    for (index, entry) in manifest.puzzles.iter().enumerate() {
+        let id = entry.id.encode();
+        println!("[{}/{}] proving {}", index + 1, count, id);
+        let proof = (|| {
+            let solution = match &entry.solution {
+                Some(solution) => solution.clone(),
+                None => solve(&entry.puzzle)?,
+            };
+            // the guest regenerates the puzzle from its seed, so the proof commits the id's
+            // seed and difficulty.
+            let source = PuzzleSource::Seed {
+                seed: entry.id.seed,
+                difficulty: entry.id.difficulty as u8,
+            };
+            let mut input = args
+                .submission
+                .proof_input(source, &entry.puzzle, &solution)?;
+            input.variant = entry.id.variant().to_bytes();
+            check_input(prover.as_ref(), &input, &entry.puzzle)?;
+            let envelope = prover.prove(&input)?;
+            check_proof(envelope, &entry.puzzle, &input)
+        })()
+        .map_err(|e| format!("puzzle {} ({}): {}", index + 1, id, e))?;
+        let path = out_dir.join(format!("{}-{}.bin", index + 1, id));
+        std::fs::write(&path, proof.envelope.encode())
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!(
+            "[{}/{}] {} in {}",
+            index + 1,
+            count,
+            if proof.values.valid {
+                "solved"
+            } else {
+                "not solved"
+            },
+            path.display()
+        );
+        proofs.push((path, proof));
+    }
+
+    let attestation = attestation(&manifest, &proofs, config);
+    let path = out_dir.join("attestation.json");
+    let text = serde_json::to_string_pretty(&attestation).map_err(|e| e.to_string())?;
+    std::fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    println!(
+        "results root: {}",
+        attestation["resultsRoot"].as_str().unwrap()
+    );
+    println!(
+        "{} of {} solved, attestation in {}",
+        attestation["validCount"],
+        count,
+        path.display()
+    );
+    Ok(())
+}
+
+// the aggregate of the campaign's results and, for each puzzle, its leaf and the merkle path
+// from it to the root.
+fn attestation(
+    manifest: &Manifest,
+    proofs: &[(PathBuf, SolutionProof)],
+    config: &ProverConfig,
+) -> Value {
+    let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+    let values: Vec<_> = proofs
+        .iter()
+        .map(|(_, proof)| proof.values.clone())
+        .collect();
+    let leaves: Vec<[u8; 32]> = values.iter().map(result_leaf).collect();
+    let batch = aggregate(&values);
+    json!({
+        "name": manifest.name,
+        "backend": config.backend.name(),
+        "logicVersion": LOGIC_VERSION,
+        "count": batch.count,
+        "validCount": batch.valid_count,
+        "resultsRoot": hex(&batch.results_root),
+        "puzzles": manifest
+            .puzzles
+            .iter()
+            .zip(proofs)
+            .enumerate()
+            .map(|(index, (entry, (path, proof)))| {
+                json!({
+                    "index": index,
+                    "id": entry.id.encode(),
+                    "puzzleHash": hex(&proof.values.puzzle_hash),
+                    "valid": proof.values.valid,
+                    "status": proof.values.status,
+                    "solutionCommitment": hex(&proof.values.solution_commitment),
+                    "salt": hex(&proof.salt),
+                    "leaf": hex(&leaves[index]),
+                    "path": merkle_path(&leaves, index)
+                        .iter()
+                        .map(|node| hex(node))
+                        .collect::<Vec<_>>(),
+                    "proof": path,
+                })
+            })
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn read_manifest(text: &str) -> Result<Manifest, String> {
+    let value: Value = serde_json::from_str(text).map_err(|e| e.to_string())?;
+    let name = match &value["name"] {
+        Value::String(name) => Some(name.clone()),
+        Value::Null => None,
+        _ => return Err("the name isn't a string".into()),
+    };
+    let Value::Array(puzzles) = &value["puzzles"] else {
+        return Err("no puzzles list".into());
+    };
+    let puzzles = puzzles
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let error = |e: String| format!("puzzle {}: {}", index + 1, e);
+            let id = match &value["id"] {
+                Value::String(id) => parse_puzzle_id(id).map_err(error)?,
+                _ => return Err(error("no puzzle id".into())),
+            };
+            let puzzle = id
+                .puzzle()
+                .map_err(|e| error(format!("can't generate the puzzle ({:?})", e)))?;
+            let solution = match &value["solution"] {
+                Value::Null => None,
+                Value::String(digits) => Some(parse_digits(digits).map_err(error)?),
+                Value::Array(_) => Some(
+                    parse_json_board(value["solution"].to_string().as_bytes()).map_err(error)?,
+                ),
+                _ => {
+                    return Err(error(
+                        "a solution is a string of digits or an array of cells".into(),
+                    ))
+                }
+            };
+            Ok(Entry {
+                id,
+                puzzle,
+                solution,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(Manifest { name, puzzles })
+}
@@ -0,0 +1,351 @@
+// everything the commands need to set up a prover, read once from a config file, then the
+// environment (and a .env file) and then overridden by command line flags, so every subcommand
+// and the library see the same settings and a bad value fails up front with the variable it
+// came from.
+//
+//     ZK_SUDOKU_BACKEND          sp1 or openvm
+//     ZK_SUDOKU_NETWORK          true to prove on the Succinct prover network (sp1)
+//     NETWORK_PRIVATE_KEY        0x-prefixed 32 byte key paying for network proofs
+//     NETWORK_RPC_URL            prover network endpoint, the SDK's default when unset
+//     ZK_SUDOKU_NETWORK_TIMEOUT  seconds to wait for a network proof
+//     ZK_SUDOKU_LOG_BLOWUP       log blowup of OpenVM's app FRI parameters
+//     ZK_SUDOKU_CACHE_DIR        built guests and proving keys
+//     ZK_SUDOKU_OUTPUT_DIR       where proofs without an explicit path are written
+//     ZK_SUDOKU_MOCK             true to only execute and save unverifiable mock proofs
+//     ZK_SUDOKU_PROVER           cpu or cuda, where local proofs are made (SP1_PROVER if unset)
+//
+// the config file (ConfigFile::load) holds defaults for the same settings and the difficulty of
+// seeded puzzles, any of them left out:
+//
+//     backend = "sp1"
+//     difficulty = "hard"
+//     output_dir = "/home/me/proofs"
+//     cache_dir = "/home/me/.cache/zk-sudoku"
+//
+//     [network]
+//     enabled = true
+//     private_key_env = "MY_NETWORK_KEY"
+//     rpc_url = "https://rpc.production.succinct.xyz"
+//     timeout = 600
+//
+// the network key itself doesn't go in it, only where it's read from: private_key_env names a
+// variable holding it, private_key_file a file.
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sudoku::core::board::Difficulty;
+use sudoku_io::envelope::Backend;
+
+use crate::backend::parse_backend;
+use crate::input::parse_difficulty;
+
+// where the config file is looked for unless --config names one, under $XDG_CONFIG_HOME or
+// ~/.config.
+pub const CONFIG_PATH: &str = "zk-sudoku/config.toml";
+
+// proving on the Succinct prover network instead of locally, sp1 only.
+#[derive(Debug, Clone)]
+pub struct NetworkConfig {
+    pub enabled: bool,
+    pub private_key: Option<String>,
+    pub rpc_url: Option<String>,
+    // the request keeps running after it, and can be fetched later.
+    pub timeout: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        NetworkConfig {
+            enabled: false,
+            private_key: None,
+            rpc_url: None,
+            timeout: 3600,
+        }
+    }
+}
+
+// where local proofs are made. cuda falls back to the cpu when there's no NVIDIA driver or
+// docker to run the GPU prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalProver {
+    Cpu,
+    Cuda,
+}
+
+pub fn parse_local_prover(arg: &str) -> Result<LocalProver, String> {
+    match arg {
+        "cpu" => Ok(LocalProver::Cpu),
+        "cuda" => Ok(LocalProver::Cuda),
+        _ => Err(format!("unknown prover '{}', expected cpu or cuda", arg)),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProverConfig {
+    pub backend: Backend,
+    pub network: NetworkConfig,
+    // proving slows down as it grows, proofs get smaller. it changes OpenVM's app key.
+    pub log_blowup: usize,
+    pub cache_dir: PathBuf,
+    pub output_dir: PathBuf,
+    // build the guest and run keygen even when they're cached.
+    pub rebuild: bool,
+    // execute instead of proving and save mock envelopes (see sudoku_io::envelope).
+    pub mock: bool,
+    // verify accepts mock envelopes. only ever set by a flag, never the environment.
+    pub allow_mock: bool,
+    pub local_prover: LocalProver,
+}
+
+impl Default for ProverConfig {
+    fn default() -> Self {
+        ProverConfig {
+            backend: Backend::Sp1,
+            network: NetworkConfig::default(),
+            log_blowup: 2,
+            cache_dir: Path::new(env!("CARGO_MANIFEST_DIR")).join("target/guest-cache"),
+            output_dir: PathBuf::from("."),
+            rebuild: false,
+            mock: false,
+            allow_mock: false,
+            local_prover: LocalProver::Cpu,
+        }
+    }
+}
+
+// defaults from a config file, between the built in ones and the environment.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigFile {
+    // where it was read from, None when there's no file.
+    pub path: Option<PathBuf>,
+    pub backend: Option<Backend>,
+    // of seeded puzzles, when --difficulty isn't given.
+    pub difficulty: Option<Difficulty>,
+    pub output_dir: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+    pub network: NetworkFile,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkFile {
+    pub enabled: Option<bool>,
+    // the variable the private key is read from.
+    pub private_key_env: Option<String>,
+    // or the file, its contents trimmed.
+    pub private_key_file: Option<PathBuf>,
+    pub rpc_url: Option<String>,
+    pub timeout: Option<u64>,
+}
+
+// the file as written, before its values are parsed.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawConfigFile {
+    backend: Option<String>,
+    difficulty: Option<String>,
+    output_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    #[serde(default)]
+    network: NetworkFile,
+}
+
+impl ConfigFile {
+    // the file at path, or at the default path if there's one there. a path that was asked for
+    // has to exist, the default one doesn't.
+    pub fn load(path: Option<&Path>) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match default_path()? {
+                Some(path) if path.exists() => path,
+                _ => return Ok(ConfigFile::default()),
+            },
+        };
+        let text =
+            std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let file = Self::parse(&text).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(ConfigFile {
+            path: Some(path),
+            ..file
+        })
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let raw: RawConfigFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        let backend = match raw.backend {
+            Some(backend) => Some(parse_backend(&backend).map_err(invalid("backend"))?),
+            None => None,
+        };
+        let difficulty = match raw.difficulty {
+            Some(difficulty) => Some(parse_difficulty(&difficulty).map_err(invalid("difficulty"))?),
+            None => None,
+        };
+        if raw.network.private_key_env.is_some() && raw.network.private_key_file.is_some() {
+            return Err("network has both private_key_env and private_key_file, keep one".into());
+        }
+        Ok(ConfigFile {
+            path: None,
+            backend,
+            difficulty,
+            output_dir: raw.output_dir,
+            cache_dir: raw.cache_dir,
+            network: raw.network,
+        })
+    }
+
+    // the file's values over config's.
+    fn apply(&self, config: &mut ProverConfig) -> Result<(), String> {
+        if let Some(backend) = self.backend {
+            config.backend = backend;
+        }
+        if let Some(dir) = &self.output_dir {
+            config.output_dir = dir.clone();
+        }
+        if let Some(dir) = &self.cache_dir {
+            config.cache_dir = dir.clone();
+        }
+        let network = &self.network;
+        if let Some(enabled) = network.enabled {
+            config.network.enabled = enabled;
+        }
+        if let Some(name) = &network.private_key_env {
+            config.network.private_key = var(name)?;
+        }
+        if let Some(path) = &network.private_key_file {
+            let key = std::fs::read_to_string(path)
+                .map_err(|e| format!("network private key {}: {}", path.display(), e))?;
+            config.network.private_key = Some(key.trim().to_string());
+        }
+        if let Some(url) = &network.rpc_url {
+            config.network.rpc_url = Some(url.clone());
+        }
+        if let Some(timeout) = network.timeout {
+            config.network.timeout = timeout;
+        }
+        Ok(())
+    }
+}
+
+// $XDG_CONFIG_HOME/zk-sudoku/config.toml, or ~/.config/zk-sudoku/config.toml. None without a
+// home directory.
+fn default_path() -> Result<Option<PathBuf>, String> {
+    if let Some(dir) = var("XDG_CONFIG_HOME")? {
+        return Ok(Some(Path::new(&dir).join(CONFIG_PATH)));
+    }
+    Ok(var("HOME")?.map(|home| Path::new(&home).join(".config").join(CONFIG_PATH)))
+}
+
+impl ProverConfig {
+    // the defaults overridden by whatever is set. a .env file in the working directory fills in
+    // variables the environment doesn't have.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_file_and_env(&ConfigFile::default())
+    }
+
+    // from_env with the config file's values in between the defaults and the environment.
+    pub fn from_file_and_env(file: &ConfigFile) -> Result<Self, String> {
+        dotenv::dotenv().ok();
+        let mut config = ProverConfig::default();
+        file.apply(&mut config)?;
+        if let Some(backend) = var("ZK_SUDOKU_BACKEND")? {
+            config.backend = parse_backend(&backend).map_err(invalid("ZK_SUDOKU_BACKEND"))?;
+        }
+        if let Some(enabled) = var("ZK_SUDOKU_NETWORK")? {
+            config.network.enabled = parse_bool(&enabled).map_err(invalid("ZK_SUDOKU_NETWORK"))?;
+        }
+        if let Some(key) = var("NETWORK_PRIVATE_KEY")? {
+            config.network.private_key = Some(key);
+        }
+        if let Some(url) = var("NETWORK_RPC_URL")? {
+            config.network.rpc_url = Some(url);
+        }
+        if let Some(timeout) = var("ZK_SUDOKU_NETWORK_TIMEOUT")? {
+            config.network.timeout = timeout
+                .parse()
+                .map_err(|_| invalid("ZK_SUDOKU_NETWORK_TIMEOUT")("not a number of seconds"))?;
+        }
+        if let Some(log_blowup) = var("ZK_SUDOKU_LOG_BLOWUP")? {
+            config.log_blowup = log_blowup
+                .parse()
+                .map_err(|_| invalid("ZK_SUDOKU_LOG_BLOWUP")("not a number"))?;
+        }
+        if let Some(dir) = var("ZK_SUDOKU_CACHE_DIR")? {
+            config.cache_dir = dir.into();
+        }
+        if let Some(dir) = var("ZK_SUDOKU_OUTPUT_DIR")? {
+            config.output_dir = dir.into();
+        }
+        if let Some(mock) = var("ZK_SUDOKU_MOCK")? {
+            config.mock = parse_bool(&mock).map_err(invalid("ZK_SUDOKU_MOCK"))?;
+        }
+        // the SDK's own variable still picks the local prover, mock and network have their own
+        // settings here.
+        if let Some(prover) = var("SP1_PROVER")? {
+            config.local_prover = parse_local_prover(&prover).map_err(invalid("SP1_PROVER"))?;
+        }
+        if let Some(prover) = var("ZK_SUDOKU_PROVER")? {
+            config.local_prover =
+                parse_local_prover(&prover).map_err(invalid("ZK_SUDOKU_PROVER"))?;
+        }
+        Ok(config)
+    }
+
+    // the checks that hold however the values were set, run once flags have been applied.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.network.enabled {
+            if self.mock {
+                return Err("mock proofs are made locally, drop --network or --mock".into());
+            }
+            if self.backend != Backend::Sp1 {
+                return Err(format!("{:?} has no prover network", self.backend));
+            }
+            let key = self.network.private_key.as_deref().ok_or(
+                "the prover network needs a key, set NETWORK_PRIVATE_KEY, pass --private-key or \
+                 name one in the config file's [network]",
+            )?;
+            let hex = key.strip_prefix("0x").unwrap_or(key);
+            if hex.len() != 64 || hex::decode(hex).is_err() {
+                return Err("the network private key isn't 32 bytes of hex".into());
+            }
+        }
+        if let Some(url) = &self.network.rpc_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(format!("the network rpc url '{}' isn't http(s)", url));
+            }
+        }
+        if !(1..=6).contains(&self.log_blowup) {
+            return Err(format!(
+                "a log blowup of {} is out of range, expected 1 to 6",
+                self.log_blowup
+            ));
+        }
+        Ok(())
+    }
+
+    // relative output paths land in output_dir.
+    pub fn output_path(&self, path: &Path) -> PathBuf {
+        self.output_dir.join(path)
+    }
+}
+
+// unset and empty are the same, a value that isn't unicode is an error.
+fn var(name: &str) -> Result<Option<String>, String> {
+    match std::env::var(name) {
+        Ok(value) if value.is_empty() => Ok(None),
+        Ok(value) => Ok(Some(value)),
+        Err(std::env::VarError::NotPresent) => Ok(None),
+        Err(std::env::VarError::NotUnicode(_)) => Err(format!("{} isn't valid unicode", name)),
+    }
+}
+
+fn invalid<E: std::fmt::Display>(name: &'static str) -> impl Fn(E) -> String {
+    move |e| format!("{} is invalid ({})", name, e)
+}
+
+fn parse_bool(value: &str) -> Result<bool, &'static str> {
+    match value {
+        "1" | "true" => Ok(true),
+        "0" | "false" => Ok(false),
+        _ => Err("expected true or false"),
+    }
+}
@@ -0,0 +1,98 @@
+// built guests and their proving keys kept between runs, so the host doesn't rebuild,
+// re-transpile or rerun keygen for a guest that hasn't changed. a built guest is keyed by the
+// sha256 of everything the build depends on: the guest crate's sources and manifests, the
+// sudoku and sudoku_io crates it pulls in, and the toolchain that builds it (`rustc -vV` run
+// from the guest, so rust-toolchain.toml applies). keys are keyed by the sha256 of what keygen
+// reads, the ELF for sp1 and the app config for openvm.
+use std::path::{Path, PathBuf};
+#[cfg(feature = "openvm")]
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[cfg(feature = "openvm")]
+use sudoku::core::commitment::sha256;
+
+// tells apart the partial files of stores running at the same time in this process.
+static PARTIAL: AtomicU64 = AtomicU64::new(0);
+
+// the workspace crates a guest depends on, relative to the repository root.
+#[cfg(feature = "openvm")]
+const GUEST_DEPS: [&str; 2] = ["sudoku", "sudoku_io"];
+
+// the key of the guest crate at `guest`, a directory next to the host.
+#[cfg(feature = "openvm")]
+pub fn source_key(guest: &Path) -> Result<[u8; 32], String> {
+    let root = guest.parent().ok_or("the guest has no parent directory")?;
+    let mut files = Vec::new();
+    collect(guest, &mut files)?;
+    for dep in GUEST_DEPS {
+        collect(&root.join(dep), &mut files)?;
+    }
+    files.sort();
+
+    let mut input = Vec::new();
+    for file in &files {
+        input.extend_from_slice(
+            file.strip_prefix(root)
+                .unwrap_or(file)
+                .to_string_lossy()
+                .as_bytes(),
+        );
+        input.push(0);
+        input.extend(std::fs::read(file).map_err(|e| format!("{}: {}", file.display(), e))?);
+    }
+    let rustc = Command::new("rustc")
+        .arg("-vV")
+        .current_dir(guest)
+        .output()
+        .map_err(|e| format!("can't run rustc for the guest's toolchain ({})", e))?;
+    input.extend(rustc.stdout);
+    Ok(sha256(&input))
+}
+
+// everything but build output and version control.
+#[cfg(feature = "openvm")]
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        let name = path.file_name().unwrap_or_default();
+        if name == "target" || name == ".git" {
+            continue;
+        }
+        if path.is_dir() {
+            collect(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// the cached bytes in `dir` for `name` built from sources with `key`, if there are any.
+pub fn load(dir: &Path, name: &str, key: &[u8; 32]) -> Option<Vec<u8>> {
+    std::fs::read(entry(dir, name, key)).ok()
+}
+
+// entries of older sources stay behind until the cache directory is cleared. the bytes go to a
+// file of their own first and are renamed into place, so batch workers storing the same entry,
+// or a run killed halfway, never leave a truncated entry for `load` to hand out.
+pub fn store(dir: &Path, name: &str, key: &[u8; 32], bytes: &[u8]) -> Result<(), String> {
+    let path = entry(dir, name, key);
+    std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let partial = path.with_extension(format!(
+        "{}-{}.partial",
+        std::process::id(),
+        PARTIAL.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::write(&partial, bytes)
+        .and_then(|_| std::fs::rename(&partial, &path))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&partial);
+            format!("{}: {}", path.display(), e)
+        })
+}
+
+fn entry(dir: &Path, name: &str, key: &[u8; 32]) -> PathBuf {
+    dir.join(format!("{}-{}", name, hex::encode(&key[..8])))
+}
@@ -0,0 +1,358 @@
+// the submission every backend proves: where the puzzle comes from, the user's board and the
+// values bound to it, read from the command line and checked before any guest runs.
+use std::path::PathBuf;
+
+use sudoku::core::board::{Board, Difficulty, PACKED_LEN};
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::solver::DancingLinks;
+use sudoku::core::variant::{Variant, VARIANT_CLASSIC};
+use sudoku_io::puzzle_id::PuzzleId;
+use sudoku_io::{
+    check_givens, givens_hash, ProofInput, PublicValues, PuzzleSource, DIFFICULTY_UNRATED,
+};
+
+// seeded puzzles are generated with it unless --difficulty or the config file say otherwise.
+pub const DEFAULT_DIFFICULTY: Difficulty = Difficulty::Medium;
+
+#[derive(clap::Args, Debug)]
+pub struct InputArgs {
+    /// Seed the guest regenerates the puzzle from.
+    #[clap(long, default_value = "666")]
+    pub seed: u32,
+
+    /// Difficulty the guest generates the seeded puzzle with: easy, medium or hard. The config
+    /// file's difficulty, or medium, by default.
+    #[clap(long, value_parser = parse_difficulty)]
+    pub difficulty: Option<Difficulty>,
+
+    /// File with the puzzle to use instead of a seeded one: 81 digits (0 or . for empty cells,
+    /// whitespace ignored), a JSON array of 81 cells (or of 9 rows of 9), the 41 byte packed
+    /// encoding or a puzzle id.
+    #[clap(long)]
+    pub puzzle: Option<PathBuf>,
+
+    /// Puzzle id (zk1-...) naming the seed, difficulty and variant instead of --seed and
+    /// --difficulty.
+    #[clap(long, conflicts_with_all = ["puzzle", "seed", "difficulty"], value_parser = parse_puzzle_id)]
+    pub puzzle_id: Option<PuzzleId>,
+
+    /// File with the user's board, in any of the --puzzle formats. Defaults to the solver's
+    /// solution of the puzzle.
+    #[clap(long)]
+    pub solution: Option<PathBuf>,
+
+    /// Generate the seeded puzzle here and send the guest its cells instead of the seed. The
+    /// guest then commits seed 0 and no difficulty, like for a --puzzle file.
+    #[clap(long)]
+    pub host_puzzle: bool,
+
+    #[clap(flatten)]
+    pub submission: SubmissionArgs,
+}
+
+// what's committed alongside a puzzle and board, shared by single and batch submissions.
+// Default matches the command line defaults.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SubmissionArgs {
+    /// Send boards to the guest in the 41 byte packed encoding instead of 81 raw cells.
+    #[clap(long)]
+    pub packed: bool,
+
+    /// Salt (32 bytes, 0x-prefixed hex) for the committed solution hash. A random one is picked
+    /// and printed (or recorded in the batch summary) when it isn't given.
+    #[clap(long, value_parser = parse_salt)]
+    pub salt: Option<[u8; 32]>,
+
+    /// EVM address (0x-prefixed hex) committed in the proof as the reward recipient.
+    #[clap(long, default_value = "0x0000000000000000000000000000000000000000", value_parser = parse_address)]
+    pub address: [u8; 20],
+
+    /// Epoch (e.g. tournament round or date) the guest echoes into the public values.
+    #[clap(long, default_value = "0")]
+    pub epoch: u64,
+
+    /// Nonce (32 bytes, 0x-prefixed hex) the guest echoes into the public values, to bind the
+    /// proof to the request it answers. All zeros when it isn't given.
+    #[clap(long, value_parser = parse_nonce)]
+    pub nonce: Option<[u8; 32]>,
+}
+
+impl InputArgs {
+    // the puzzle as the host sees it, regenerated from the seed unless it was supplied.
+    pub fn puzzle(&self) -> Result<Board, String> {
+        match &self.puzzle {
+            Some(path) => read_board(path),
+            None => {
+                let id = self.puzzle_id();
+                Board::try_from_seed(id.seed, Some(id.difficulty))
+                    .map_err(|e| format!("can't generate seed {} ({:?})", id.seed, e))
+            }
+        }
+    }
+
+    // the seeded puzzle's id, --puzzle-id or the one of --seed and --difficulty.
+    pub fn puzzle_id(&self) -> PuzzleId {
+        self.puzzle_id.unwrap_or(PuzzleId {
+            seed: self.seed,
+            difficulty: self.difficulty.unwrap_or(DEFAULT_DIFFICULTY),
+            variant: VARIANT_CLASSIC,
+        })
+    }
+
+    // the input along with the puzzle hash the host derived itself, for check_puzzle_hash.
+    pub fn proof_input(&self) -> Result<(ProofInput, [u8; 32]), String> {
+        let puzzle = self.puzzle()?;
+        let solution = match &self.solution {
+            Some(path) => read_board(path)?,
+            None => solve(&puzzle)?,
+        };
+        let id = self.puzzle_id();
+        let source = if self.puzzle.is_some() || self.host_puzzle {
+            self.submission.puzzle_source(&puzzle)
+        } else {
+            PuzzleSource::Seed {
+                seed: id.seed,
+                difficulty: id.difficulty as u8,
+            }
+        };
+        let mut input = self.submission.proof_input(source, &puzzle, &solution)?;
+        if self.puzzle.is_none() {
+            input.variant = id.variant().to_bytes();
+        }
+        Ok((input, puzzle_hash(&puzzle)))
+    }
+}
+
+impl SubmissionArgs {
+    // the givens sent as cells, the guest checks them against their hash.
+    pub fn puzzle_source(&self, puzzle: &Board) -> PuzzleSource {
+        PuzzleSource::Puzzle {
+            cells: encode_board(puzzle, self.packed),
+            expected_hash: puzzle_hash(puzzle),
+        }
+    }
+
+    pub fn proof_input(
+        &self,
+        source: PuzzleSource,
+        puzzle: &Board,
+        solution: &Board,
+    ) -> Result<ProofInput, String> {
+        check_inputs(puzzle, solution, false)?;
+        Ok(ProofInput {
+            salt: self.salt.unwrap_or_else(rand::random),
+            reward_address: self.address,
+            epoch: self.epoch,
+            nonce: self.nonce.unwrap_or_default(),
+            ..ProofInput::new(source, encode_board(solution, self.packed))
+        })
+    }
+}
+
+pub fn solve(puzzle: &Board) -> Result<Board, String> {
+    DancingLinks::solve_board(puzzle).map_err(|e| {
+        format!(
+            "can't solve the puzzle ({}), pass a board with --solution",
+            e
+        )
+    })
+}
+
+pub fn encode_board(board: &Board, packed: bool) -> Vec<u8> {
+    if packed {
+        board.to_packed().to_vec()
+    } else {
+        board.cells.to_vec()
+    }
+}
+
+// the format is told apart by the contents: JSON starts with '[', which can't be the first
+// byte of a packed board (its low nibble would be cell value 11), puzzle ids start with "zk"
+// (a low nibble of 10), packed boards are exactly PACKED_LEN bytes, and anything else has to
+// be the digits.
+pub fn read_board(path: &std::path::Path) -> Result<Board, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_board(&bytes)
+}
+
+pub fn parse_board(bytes: &[u8]) -> Result<Board, String> {
+    let start = bytes.trim_ascii_start();
+    if start.starts_with(b"[") {
+        parse_json_board(bytes)
+    } else if start.len() >= 2 && start[..2].eq_ignore_ascii_case(b"zk") {
+        puzzle_from_id(std::str::from_utf8(bytes).map_err(|e| e.to_string())?)
+    } else if bytes.len() == PACKED_LEN {
+        Board::from_packed(bytes).map_err(|e| format!("{:?}", e))
+    } else {
+        parse_digits(std::str::from_utf8(bytes).map_err(|e| e.to_string())?)
+    }
+}
+
+pub fn parse_digits(text: &str) -> Result<Board, String> {
+    let cells = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '.' => Ok(0),
+            _ => c
+                .to_digit(10)
+                .map(|d| d as u8)
+                .ok_or(format!("invalid cell '{}'", c)),
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    board_from_cells(cells)
+}
+
+// a JSON board is either a flat array of 81 cells or 9 rows of 9.
+pub fn parse_json_board(bytes: &[u8]) -> Result<Board, String> {
+    let cells = match serde_json::from_slice::<Vec<Vec<u8>>>(bytes) {
+        Ok(rows) => rows.concat(),
+        Err(_) => serde_json::from_slice::<Vec<u8>>(bytes).map_err(|e| e.to_string())?,
+    };
+    board_from_cells(cells)
+}
+
+fn board_from_cells(cells: Vec<u8>) -> Result<Board, String> {
+    let cells: [u8; 81] = cells
+        .try_into()
+        .map_err(|cells: Vec<u8>| format!("expected 81 cells, got {}", cells.len()))?;
+    Board::from_array(cells).map_err(|e| format!("{:?}", e))
+}
+
+// reject inputs the guest would only turn into a failed proof: a puzzle that breaks the rules,
+// a board that overwrites its givens and, unless it's `partial` (a progress board), one that
+// isn't a complete solution.
+pub fn check_inputs(puzzle: &Board, board: &Board, partial: bool) -> Result<(), String> {
+    if !puzzle.is_conflict_free() {
+        return Err("the puzzle repeats a value in a row, column or box".into());
+    }
+    check_givens(puzzle, &board.cells).map_err(|_| "the board overwrites a given".to_string())?;
+    if !board.is_conflict_free() {
+        return Err("the board repeats a value in a row, column or box".into());
+    }
+    if !partial && board.clue_count() != 81 {
+        return Err(format!(
+            "the board has {} empty cells",
+            81 - board.clue_count()
+        ));
+    }
+    Ok(())
+}
+
+// the guest regenerates seeded puzzles with its own copy of the generator, and hashes supplied
+// ones after decoding them, so a guest built from other sources than this host commits a
+// different puzzle hash. checked after executing, before anything gets proved.
+pub fn check_puzzle_hash(committed: &[u8; 32], expected: &[u8; 32]) -> Result<(), String> {
+    if committed != expected {
+        return Err(format!(
+            "the guest committed puzzle hash 0x{}, the host derived 0x{}, rebuild the guest",
+            hex::encode(committed),
+            hex::encode(expected)
+        ));
+    }
+    Ok(())
+}
+
+// everything a proof commits about its puzzle, recomputed from the host's own copy of the
+// board and compared with the proof's values: the hash, the givens, the clue count, the
+// variant and the seed and difficulty tier the source implies. a proof of another puzzle, or
+// from a guest (or inputs) other than the host meant, fails with every value that's off.
+pub fn check_puzzle(
+    values: &PublicValues,
+    puzzle: &Board,
+    source: &PuzzleSource,
+    variant: &Variant,
+) -> Result<(), String> {
+    let (seed, difficulty) = match source {
+        PuzzleSource::Seed { seed, difficulty } => (*seed, *difficulty),
+        PuzzleSource::Puzzle { .. } => (0, DIFFICULTY_UNRATED),
+    };
+    let hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+    let mut mismatches = Vec::new();
+    let mut check = |name: &str, committed: String, expected: String| {
+        if committed != expected {
+            mismatches.push(format!("{} {} (expected {})", name, committed, expected));
+        }
+    };
+    check(
+        "puzzle hash",
+        hex(&values.puzzle_hash),
+        hex(&variant.puzzle_hash(puzzle)),
+    );
+    check(
+        "givens hash",
+        hex(&values.givens_hash),
+        hex(&givens_hash(puzzle)),
+    );
+    check(
+        "clues",
+        values.clue_count.to_string(),
+        puzzle.clue_count().to_string(),
+    );
+    check(
+        "variant",
+        values.variant.to_string(),
+        variant.kind().to_string(),
+    );
+    check("seed", values.seed.to_string(), seed.to_string());
+    check(
+        "difficulty",
+        values.difficulty.to_string(),
+        difficulty.to_string(),
+    );
+    if !mismatches.is_empty() {
+        return Err(format!(
+            "the proof's public values don't match the puzzle: {}",
+            mismatches.join(", ")
+        ));
+    }
+    Ok(())
+}
+
+pub fn parse_hex(arg: &str) -> Result<Vec<u8>, String> {
+    hex::decode(arg.trim_start_matches("0x")).map_err(|e| e.to_string())
+}
+
+pub fn parse_salt(arg: &str) -> Result<[u8; 32], String> {
+    parse_hex(arg)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 salt bytes, got {}", bytes.len()))
+}
+
+pub fn puzzle_from_id(text: &str) -> Result<Board, String> {
+    let id = parse_puzzle_id(text)?;
+    id.puzzle()
+        .map_err(|e| format!("can't generate puzzle {} ({:?})", id.encode(), e))
+}
+
+pub fn parse_puzzle_id(arg: &str) -> Result<PuzzleId, String> {
+    PuzzleId::decode(arg).map_err(|e| format!("not a puzzle id ({:?})", e))
+}
+
+pub fn parse_nonce(arg: &str) -> Result<[u8; 32], String> {
+    parse_hex(arg)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 nonce bytes, got {}", bytes.len()))
+}
+
+// an organizer's ed25519 key.
+pub fn parse_key(arg: &str) -> Result<[u8; 32], String> {
+    parse_hex(arg)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 32 key bytes, got {}", bytes.len()))
+}
+
+pub fn parse_address(arg: &str) -> Result<[u8; 20], String> {
+    parse_hex(arg)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected 20 address bytes, got {}", bytes.len()))
+}
+
+pub fn parse_difficulty(arg: &str) -> Result<Difficulty, String> {
+    match arg {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!("unknown difficulty '{}'", arg)),
+    }
+}
@@ -0,0 +1,189 @@
+//! Proving and verifying sudoku solutions from Rust, for services that embed the prover instead
+//! of running the `zk-sudoku` binary. The backends are the same: SP1 by default, OpenVM with
+//! the `openvm` feature.
+//!
+//! ```ignore
+//! let options = ProveOptions {
+//!     config: ProverConfig::from_env()?,
+//!     ..Default::default()
+//! };
+//! let proof = zk_sudoku_host::prove_solution(&puzzle, &solution, &options)?;
+//! std::fs::write("proof.bin", proof.envelope.encode())?;
+//!
+//! let verified = zk_sudoku_host::verify(&std::fs::read("proof.bin")?, &options.config)?;
+//! ```
+use sudoku::core::board::Board;
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::variant::Variant;
+use sudoku_io::envelope::{Backend, ProofEnvelope, ProvedOn};
+use sudoku_io::{ProofInput, PublicValues, LOGIC_VERSION};
+
+pub mod backend;
+pub mod config;
+#[cfg(any(feature = "sp1", feature = "openvm"))]
+mod guest_cache;
+pub mod input;
+pub mod stdin;
+
+use backend::{prover, Program, Prover};
+use config::ProverConfig;
+use input::{check_puzzle, check_puzzle_hash, SubmissionArgs};
+
+#[derive(Debug, Clone, Default)]
+pub struct ProveOptions {
+    // salt, reward address, epoch and encoding of the submission.
+    pub submission: SubmissionArgs,
+    // usually ProverConfig::from_env().
+    pub config: ProverConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct SolutionProof {
+    // what `verify` takes back, encoded.
+    pub envelope: ProofEnvelope,
+    pub values: PublicValues,
+    // keep it to open the solution commitment later.
+    pub salt: [u8; 32],
+}
+
+/// Prove that `solution` solves `puzzle`. The puzzle is sent to the guest as its cells.
+pub fn prove_solution(
+    puzzle: &Board,
+    solution: &Board,
+    options: &ProveOptions,
+) -> Result<SolutionProof, String> {
+    options.config.validate()?;
+    let prover = prover(options.config.backend, &options.config)?;
+    prove_with(prover.as_ref(), puzzle, solution, &options.submission)
+}
+
+/// `prove_solution` with a prover that's already set up, for callers proving many solutions.
+pub fn prove_with(
+    prover: &dyn Prover,
+    puzzle: &Board,
+    solution: &Board,
+    submission: &SubmissionArgs,
+) -> Result<SolutionProof, String> {
+    let input = checked_input(prover, puzzle, solution, submission)?;
+    let envelope = prover.prove(&input)?;
+    check_proof(envelope, puzzle, &input)
+}
+
+/// The guest input for a submission, executed once so a guest out of step with this host fails
+/// before anything is proved. `prove_with` is this, `Prover::prove` and `check_proof`, for
+/// callers that prove the input some other way (submitting it to the prover network, say).
+pub fn checked_input(
+    prover: &dyn Prover,
+    puzzle: &Board,
+    solution: &Board,
+    submission: &SubmissionArgs,
+) -> Result<ProofInput, String> {
+    let source = submission.puzzle_source(puzzle);
+    let input = submission.proof_input(source, puzzle, solution)?;
+    check_input(prover, &input, puzzle)?;
+    Ok(input)
+}
+
+/// Execute an input built some other way (a seeded puzzle, say) and check the guest commits
+/// this host's logic version and puzzle hash, as `checked_input` does.
+pub fn check_input(prover: &dyn Prover, input: &ProofInput, puzzle: &Board) -> Result<(), String> {
+    // executing is cheap next to proving, catch a guest out of step with this host before
+    // paying for a proof.
+    let execution = prover.execute(input)?;
+    check_logic_version(&execution.values)?;
+    check_puzzle_hash(&execution.values.puzzle_hash, &puzzle_hash(puzzle))
+}
+
+/// Check a proof of `input` commits what the host expects for `puzzle`, and return it with its
+/// public values and salt.
+pub fn check_proof(
+    envelope: ProofEnvelope,
+    puzzle: &Board,
+    input: &ProofInput,
+) -> Result<SolutionProof, String> {
+    let values = PublicValues::abi_decode(&envelope.public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    let variant = Variant::from_bytes(&input.variant).map_err(|e| e.to_string())?;
+    check_puzzle(&values, puzzle, &input.puzzle, &variant)?;
+    Ok(SolutionProof {
+        envelope,
+        values,
+        salt: input.salt,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct VerifiedProof {
+    pub backend: Backend,
+    pub proved_on: ProvedOn,
+    // only executed, see sudoku_io::envelope::MOCK_PROOF.
+    pub mock: bool,
+    pub program: Program,
+    pub values: PublicValues,
+}
+
+/// Verify an encoded `ProofEnvelope` against the guest this crate was built with and return the
+/// program and public values it proves. The backend is the proof's, whatever `config` says. Mock
+/// proofs are refused unless `config.allow_mock` is set.
+pub fn verify(proof: &[u8], config: &ProverConfig) -> Result<VerifiedProof, String> {
+    let envelope =
+        ProofEnvelope::decode(proof).map_err(|e| format!("not a saved proof ({:?})", e))?;
+    let prover = prover(envelope.backend, config)?;
+    let values = if envelope.is_mock() {
+        if !config.allow_mock {
+            return Err("a mock proof proves nothing, pass --allow-mock to accept it".into());
+        }
+        backend::mock::check(prover.as_ref(), &envelope)?
+    } else {
+        prover.verify(&envelope)?
+    };
+    check_logic_version(&values)?;
+    Ok(VerifiedProof {
+        backend: envelope.backend,
+        proved_on: envelope.proved_on,
+        mock: envelope.is_mock(),
+        program: Program {
+            vkey_hash: envelope.vkey_hash,
+            digest: envelope.program_digest,
+        },
+        values,
+    })
+}
+
+// a guest built from other generation or validation logic doesn't prove what this host
+// checks for.
+pub fn check_logic_version(values: &PublicValues) -> Result<(), String> {
+    if values.logic_version != LOGIC_VERSION {
+        return Err(format!(
+            "the guest was built with logic version {}, this host expects {}, rebuild it",
+            values.logic_version, LOGIC_VERSION
+        ));
+    }
+    Ok(())
+}
+
+// every public value, one per line, byte strings as 0x-prefixed hex.
+pub fn print_public_values(values: &PublicValues) {
+    println!("puzzle hash: 0x{}", hex::encode(values.puzzle_hash));
+    println!(
+        "solution commitment: 0x{}",
+        hex::encode(values.solution_commitment)
+    );
+    println!("valid: {}", values.valid);
+    println!(
+        "difficulty: {}, clues: {}",
+        values.difficulty, values.clue_count
+    );
+    println!("seed: {}", values.seed);
+    println!("nullifier: 0x{}", hex::encode(values.nullifier));
+    println!("epoch: {}", values.epoch);
+    println!("reward address: 0x{}", hex::encode(values.reward_address));
+    println!("status: {}", values.status);
+    println!("policy hash: 0x{}", hex::encode(values.policy_hash));
+    println!("organizer key: 0x{}", hex::encode(values.organizer_key));
+    println!("variant: {}", values.variant);
+    println!("logic version: {}", values.logic_version);
+    println!("cells root: 0x{}", hex::encode(values.cells_root));
+    println!("givens hash: 0x{}", hex::encode(values.givens_hash));
+    println!("nonce: 0x{}", hex::encode(values.nonce));
+}
@@ -0,0 +1,749 @@
+//! One host for every backend: execute the verifier guest, prove a submission or verify a saved
+//! proof, with SP1 or OpenVM.
+//!
+//! ```shell
+//! cargo run --release -- execute --seed 42
+//! cargo run --release -- prove --solution board.txt --output proof.bin
+//! cargo run --release -- verify --proof proof.bin
+//! cargo run --release -- bundle --proof proof.bin --output solve.zkproof
+//! cargo run --release -- verify-bundle --bundle solve.zkproof
+//! cargo run --release -- badge --bundle solve.zkproof --out-dir badge
+//! cargo run --release -- program
+//! cargo run --release -- decode-id zk1-aaaafgqcaawqi
+//! cargo run --release -- prove --puzzle-id zk1-aaaafgqcaawqi --output proof.bin
+//! cargo run --release -- encode --seed 42 --output inputs.json
+//! cargo run --release -- batch --input submissions.jsonl --out-dir proofs --workers 4
+//! cargo run --release -- campaign --manifest campaign.json --out-dir campaign
+//! cargo run --release --features tui -- play --difficulty hard
+//! NETWORK_PRIVATE_KEY=... cargo run --release -- prove --network --output proof.bin
+//! cargo run --release -- --config ci.toml execute --seed 42
+//! cargo run --release --features openvm -- --backend openvm execute
+//! cargo run --release --features openvm -- diff --seed 42
+//! ```
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::daily::Date;
+use sudoku::core::variant::{Variant, VARIANT_CLASSIC};
+use sudoku_io::badge::{silhouette_svg, BadgeMetadata};
+use sudoku_io::bundle::{ProofBundle, BUNDLE_EXTENSION};
+use sudoku_io::document::PublicValuesDocument;
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use sudoku_io::puzzle_id::PuzzleId;
+use sudoku_io::{ProofInput, PublicValues, PuzzleSource};
+use zk_sudoku_host::backend::{parse_backend, prover, Program};
+use zk_sudoku_host::config::{parse_local_prover, ConfigFile, LocalProver, ProverConfig};
+use zk_sudoku_host::input::{
+    check_puzzle, check_puzzle_hash, parse_difficulty, parse_puzzle_id, read_board, InputArgs,
+    DEFAULT_DIFFICULTY,
+};
+use zk_sudoku_host::{check_logic_version, print_public_values};
+
+mod batch;
+mod campaign;
+#[cfg(feature = "tui")]
+mod tui;
+
+use batch::BatchArgs;
+use campaign::CampaignArgs;
+
+#[derive(Parser, Debug)]
+#[clap(name = "zk-sudoku", author, version, about, long_about = None)]
+struct Cli {
+    #[clap(flatten)]
+    config: ConfigArgs,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+// overrides of the environment's ProverConfig, see zk_sudoku_host::config for the variables.
+#[derive(clap::Args, Debug)]
+struct ConfigArgs {
+    /// Config file with defaults for the settings below and --difficulty, instead of
+    /// ~/.config/zk-sudoku/config.toml. The environment and flags override it.
+    #[clap(long = "config", global = true)]
+    config_file: Option<PathBuf>,
+
+    /// zkVM to run the guest on: sp1 (default) or openvm. verify takes it from the proof file
+    /// instead.
+    #[clap(long, global = true, value_parser = parse_backend)]
+    backend: Option<Backend>,
+
+    /// Build the guest (OpenVM) and generate its keys even if they're cached.
+    #[clap(long, global = true)]
+    rebuild: bool,
+
+    /// Prove on the Succinct prover network instead of locally (SP1).
+    #[clap(long, global = true)]
+    network: bool,
+
+    /// Private key of the account paying for network proofs, instead of NETWORK_PRIVATE_KEY.
+    #[clap(long, global = true)]
+    private_key: Option<String>,
+
+    /// Prover network RPC endpoint, instead of NETWORK_RPC_URL.
+    #[clap(long, global = true)]
+    rpc_url: Option<String>,
+
+    /// Seconds to wait for a network proof before giving up (the request keeps running and
+    /// can be fetched later). 3600 by default.
+    #[clap(long, global = true)]
+    timeout: Option<u64>,
+
+    /// Log blowup of OpenVM's app FRI parameters, 2 by default.
+    #[clap(long, global = true)]
+    log_blowup: Option<usize>,
+
+    /// Directory of built guests and proving keys, host/target/guest-cache by default.
+    #[clap(long, global = true)]
+    cache_dir: Option<PathBuf>,
+
+    /// Directory relative proof paths are written to, the working directory by default.
+    #[clap(long, global = true)]
+    output_dir: Option<PathBuf>,
+
+    /// Where local proofs are made: cpu (default) or cuda, which falls back to the CPU without an
+    /// NVIDIA driver or docker for the GPU prover. The proof file records which one made it.
+    #[clap(long, global = true, value_parser = parse_local_prover)]
+    prover: Option<LocalProver>,
+
+    /// Only execute the guest and save a mock proof, which proves nothing and which verify
+    /// refuses without --allow-mock. Same as ZK_SUDOKU_MOCK=true.
+    #[clap(long, global = true)]
+    mock: bool,
+}
+
+impl ConfigArgs {
+    fn file(&self) -> Result<ConfigFile, String> {
+        ConfigFile::load(self.config_file.as_deref())
+    }
+
+    fn config(&self, file: &ConfigFile) -> Result<ProverConfig, String> {
+        let mut config = ProverConfig::from_file_and_env(file)?;
+        if let Some(backend) = self.backend {
+            config.backend = backend;
+        }
+        config.rebuild |= self.rebuild;
+        config.mock |= self.mock;
+        if let Some(prover) = self.prover {
+            config.local_prover = prover;
+        }
+        config.network.enabled |= self.network;
+        if let Some(key) = &self.private_key {
+            config.network.private_key = Some(key.clone());
+        }
+        if let Some(url) = &self.rpc_url {
+            config.network.rpc_url = Some(url.clone());
+        }
+        if let Some(timeout) = self.timeout {
+            config.network.timeout = timeout;
+        }
+        if let Some(log_blowup) = self.log_blowup {
+            config.log_blowup = log_blowup;
+        }
+        if let Some(dir) = &self.cache_dir {
+            config.cache_dir = dir.clone();
+        }
+        if let Some(dir) = &self.output_dir {
+            config.output_dir = dir.clone();
+        }
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the guest without proving and print the public values it commits.
+    Execute {
+        #[clap(flatten)]
+        input: InputArgs,
+
+        /// Print the cycles, the salt and the public values as one JSON object instead.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Prove a submission and save the proof with its public values (sudoku_io::envelope).
+    Prove {
+        #[clap(flatten)]
+        input: InputArgs,
+
+        /// File the proof is saved to.
+        #[clap(long, default_value = "proof.bin")]
+        output: PathBuf,
+
+        /// With --network, only submit the request and print its id instead of waiting for
+        /// the proof, fetch it later.
+        #[clap(long)]
+        no_wait: bool,
+    },
+
+    /// Prove many submissions concurrently, one proof file each plus a summary.
+    Batch(BatchArgs),
+
+    /// Play a seeded puzzle full screen, with pencil marks and a timer, and prove the solve
+    /// once it's done. Needs the tui feature.
+    #[cfg(feature = "tui")]
+    Play(tui::PlayArgs),
+
+    /// Prove a campaign's puzzles in order and attest to all of them with the merkle root of
+    /// their results.
+    Campaign(CampaignArgs),
+
+    /// Wait for a proof requested from the prover network and save it like prove does.
+    Fetch {
+        /// Request id printed by prove --network.
+        #[clap(long)]
+        request: String,
+
+        /// File the proof is saved to.
+        #[clap(long, default_value = "proof.bin")]
+        output: PathBuf,
+    },
+
+    /// Print the input streams the guest reads for a submission, as JSON: one 0x-prefixed hex
+    /// buffer per value for SP1Stdin::write_vec and, with the openvm feature, for OpenVM's
+    /// StdIn::write_bytes.
+    Encode {
+        #[clap(flatten)]
+        input: InputArgs,
+
+        /// File the JSON is written to instead of stdout.
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Expand a puzzle id to what it names and the puzzle's board, one row per line (a valid
+    /// --puzzle file).
+    DecodeId {
+        /// The id, zk1-...
+        #[clap(value_parser = parse_puzzle_id)]
+        id: PuzzleId,
+    },
+
+    /// Print the verifying key hash and digest of the guest this build proves, the values a
+    /// verifier pins to accept only this program.
+    Program,
+
+    /// Execute a submission on the SP1 and the OpenVM guest and compare what they commit, to
+    /// catch the two implementations diverging before anything is proved. Needs both features.
+    Diff {
+        #[clap(flatten)]
+        input: InputArgs,
+    },
+
+    /// Verify a saved proof against this build's guest and print its public values.
+    Verify {
+        /// The proof, as saved by prove.
+        #[clap(long)]
+        proof: PathBuf,
+
+        #[clap(flatten)]
+        puzzle: ExpectedPuzzle,
+
+        /// Accept a mock proof made with --mock, checking only that it names this build's
+        /// program.
+        #[clap(long)]
+        allow_mock: bool,
+    },
+
+    /// Pack a saved proof into a .zkproof bundle (sudoku_io::bundle), one file to share as
+    /// evidence of a solve.
+    Bundle {
+        /// The proof, as saved by prove.
+        #[clap(long)]
+        proof: PathBuf,
+
+        /// File the bundle is saved to, the proof's path with a .zkproof extension by default.
+        #[clap(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Verify a .zkproof bundle against this build's guest and, when it names a puzzle id,
+    /// check the proof is for that puzzle.
+    VerifyBundle {
+        /// The bundle, as saved by bundle.
+        #[clap(long)]
+        bundle: PathBuf,
+
+        #[clap(flatten)]
+        puzzle: ExpectedPuzzle,
+
+        /// Accept a bundled mock proof, see verify.
+        #[clap(long)]
+        allow_mock: bool,
+    },
+
+    /// Verify a .zkproof bundle and write the ERC-721 metadata of its completion badge
+    /// (badge.json) and an SVG of the puzzle's silhouette (badge.svg), for minting.
+    Badge {
+        /// The bundle, as saved by bundle.
+        #[clap(long)]
+        bundle: PathBuf,
+
+        /// The puzzle, needed when the bundle names no puzzle id.
+        #[clap(flatten)]
+        puzzle: ExpectedPuzzle,
+
+        /// Directory badge.json and badge.svg are written to, the output directory by default.
+        #[clap(long)]
+        out_dir: Option<PathBuf>,
+
+        /// Where badge.svg will be hosted (ipfs://..., https://...), the metadata's image.
+        /// Without it the SVG goes into the metadata itself, as image_data.
+        #[clap(long)]
+        image_uri: Option<String>,
+
+        /// Day of the solve, YYYY-MM-DD, today (UTC) by default.
+        #[clap(long, value_parser = parse_date)]
+        date: Option<Date>,
+    },
+}
+
+// the puzzle verify recomputes a proof's puzzle values from, when one is given.
+#[derive(clap::Args, Debug)]
+struct ExpectedPuzzle {
+    /// Check the proof is for this puzzle, supplied as a file in any of prove's --puzzle formats.
+    #[clap(long, conflicts_with_all = ["puzzle_id", "seed"])]
+    puzzle: Option<PathBuf>,
+
+    /// Check the proof is for the puzzle with this id.
+    #[clap(long, conflicts_with = "seed", value_parser = parse_puzzle_id)]
+    puzzle_id: Option<PuzzleId>,
+
+    /// Check the proof is for the puzzle generated from this seed.
+    #[clap(long)]
+    seed: Option<u32>,
+
+    /// Difficulty the --seed puzzle was generated with, the config file's or medium by default.
+    #[clap(long, value_parser = parse_difficulty)]
+    difficulty: Option<Difficulty>,
+}
+
+impl ExpectedPuzzle {
+    // the board, and the source and variant the prover would have sent it with.
+    fn load(&self) -> Result<Option<(Board, PuzzleSource, Variant)>, String> {
+        let id = match (&self.puzzle, self.puzzle_id, self.seed) {
+            (Some(path), _, _) => {
+                let puzzle = read_board(path)?;
+                let source = PuzzleSource::Puzzle {
+                    cells: puzzle.cells.to_vec(),
+                    expected_hash: puzzle_hash(&puzzle),
+                };
+                return Ok(Some((puzzle, source, Variant::Classic)));
+            }
+            (None, Some(id), _) => id,
+            (None, None, Some(seed)) => PuzzleId {
+                seed,
+                difficulty: self.difficulty.unwrap_or(DEFAULT_DIFFICULTY),
+                variant: VARIANT_CLASSIC,
+            },
+            (None, None, None) => return Ok(None),
+        };
+        expected_from_id(&id).map(Some)
+    }
+}
+
+// the puzzle an id names, sent the way prove --puzzle-id sends it.
+fn expected_from_id(id: &PuzzleId) -> Result<(Board, PuzzleSource, Variant), String> {
+    let puzzle = id
+        .puzzle()
+        .map_err(|e| format!("can't generate the puzzle ({:?})", e))?;
+    let source = PuzzleSource::Seed {
+        seed: id.seed,
+        difficulty: id.difficulty as u8,
+    };
+    Ok((puzzle, source, id.variant()))
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(mut cli: Cli) -> Result<(), String> {
+    let file = cli.config.file()?;
+    let config = cli.config.config(&file)?;
+    if let Some(difficulty) = file.difficulty {
+        default_difficulty(&mut cli.command, difficulty);
+    }
+    match cli.command {
+        Command::Execute { input, json } => {
+            let id = input.puzzle.is_none().then(|| input.puzzle_id());
+            let (input, puzzle_hash) = input.proof_input()?;
+            let execution = prover(config.backend, &config)?.execute(&input)?;
+            check_logic_version(&execution.values)?;
+            check_puzzle_hash(&execution.values.puzzle_hash, &puzzle_hash)?;
+            if json {
+                let report = serde_json::json!({
+                    "backend": format!("{:?}", config.backend).to_lowercase(),
+                    "cycles": execution.cycles,
+                    "phases": execution
+                        .phases
+                        .iter()
+                        .map(|(phase, cycles)| (phase.clone(), (*cycles).into()))
+                        .collect::<serde_json::Map<_, _>>(),
+                    "valid": execution.values.valid,
+                    "salt": format!("0x{}", hex::encode(input.salt)),
+                    "publicValues": public_values_json(&execution.values),
+                });
+                println!("{}", report);
+                return Ok(());
+            }
+            if let Some(id) = id {
+                println!("puzzle id: {}", id.encode());
+            }
+            println!("salt: 0x{}", hex::encode(input.salt));
+            print_public_values(&execution.values);
+            if let Some(cycles) = execution.cycles {
+                println!("cycles: {}", cycles);
+            }
+            if !execution.phases.is_empty() {
+                println!("{:<10} {:>12}", "phase", "cycles");
+                for (phase, cycles) in &execution.phases {
+                    println!("{:<10} {:>12}", phase, cycles);
+                }
+            }
+        }
+        Command::Prove {
+            input,
+            output,
+            no_wait,
+        } => {
+            let puzzle = input.puzzle()?;
+            let (input, puzzle_hash) = input.proof_input()?;
+            println!("salt: 0x{}", hex::encode(input.salt));
+            let prover = prover(config.backend, &config)?;
+            // executing is cheap next to proving, catch a guest out of step with this host
+            // before paying for a proof.
+            let execution = prover.execute(&input)?;
+            check_logic_version(&execution.values)?;
+            check_puzzle_hash(&execution.values.puzzle_hash, &puzzle_hash)?;
+            if no_wait {
+                if !config.network.enabled {
+                    return Err("--no-wait only applies to the prover network".into());
+                }
+                let request = prover.submit(&input)?;
+                println!(
+                    "fetch it with: zk-sudoku fetch --network --request {}",
+                    request
+                );
+                return Ok(());
+            }
+            let envelope = prover.prove(&input)?;
+            let values = PublicValues::abi_decode(&envelope.public_values)
+                .map_err(|e| format!("malformed public values ({:?})", e))?;
+            let variant = Variant::from_bytes(&input.variant).map_err(|e| e.to_string())?;
+            check_puzzle(&values, &puzzle, &input.puzzle, &variant)?;
+            save_proof(&envelope, &config.output_path(&output))?;
+        }
+        Command::Batch(args) => batch::run(&args, &config)?,
+        Command::Campaign(args) => campaign::run(&args, &config)?,
+        #[cfg(feature = "tui")]
+        Command::Play(args) => {
+            if let Some(envelope) = tui::run(&args, &config)? {
+                save_proof(&envelope, &config.output_path(&args.output))?;
+            }
+        }
+        Command::Fetch { request, output } => {
+            let envelope = prover(config.backend, &config)?.fetch(&request)?;
+            save_proof(&envelope, &config.output_path(&output))?;
+        }
+        Command::Encode { input, output } => {
+            let (input, puzzle_hash) = input.proof_input()?;
+            let encoded = encode(&input, &puzzle_hash)?;
+            match output {
+                Some(output) => {
+                    let output = config.output_path(&output);
+                    std::fs::write(&output, format!("{:#}\n", encoded))
+                        .map_err(|e| format!("{}: {}", output.display(), e))?;
+                    println!("inputs saved to {}", output.display());
+                }
+                None => println!("{:#}", encoded),
+            }
+        }
+        Command::DecodeId { id } => {
+            let puzzle = id
+                .puzzle()
+                .map_err(|e| format!("can't generate the puzzle ({:?})", e))?;
+            println!("seed: {}", id.seed);
+            println!("difficulty: {:?}", id.difficulty);
+            println!("variant: {:?}", id.variant());
+            println!("clues: {}", puzzle.clue_count());
+            println!("puzzle hash: 0x{}", hex::encode(puzzle_hash(&puzzle)));
+            for row in puzzle.cells.chunks(9) {
+                println!(
+                    "{}",
+                    row.iter().map(|cell| cell.to_string()).collect::<String>()
+                );
+            }
+        }
+        Command::Program => {
+            print_program(&prover(config.backend, &config)?.program()?);
+        }
+        Command::Diff { input } => {
+            let (input, puzzle_hash) = input.proof_input()?;
+            println!("salt: 0x{}", hex::encode(input.salt));
+            // both execute the same input, salt included, so every value has to agree.
+            let mut committed = Vec::new();
+            for backend in [Backend::Sp1, Backend::OpenVm] {
+                let execution = prover(backend, &config)?.execute(&input)?;
+                check_puzzle_hash(&execution.values.puzzle_hash, &puzzle_hash)
+                    .map_err(|e| format!("{:?}: {}", backend, e))?;
+                committed.push(public_values_json(&execution.values));
+            }
+            let (sp1, openvm) = (&committed[0], &committed[1]);
+            let fields = sp1.as_object().unwrap();
+            let diverging: Vec<&String> = fields
+                .keys()
+                .filter(|field| sp1[field.as_str()] != openvm[field.as_str()])
+                .collect();
+            for (field, value) in fields {
+                match diverging.contains(&field) {
+                    true => println!(
+                        "{}: sp1 {} openvm {} DIFFERENT",
+                        field,
+                        value,
+                        openvm[field.as_str()]
+                    ),
+                    false => println!("{}: {}", field, value),
+                }
+            }
+            if !diverging.is_empty() {
+                return Err(format!(
+                    "the guests diverge on {}",
+                    diverging
+                        .iter()
+                        .map(|field| field.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            println!("the guests agree");
+        }
+        Command::Verify {
+            proof,
+            puzzle,
+            allow_mock,
+        } => {
+            let expected = puzzle.load()?;
+            let bytes = std::fs::read(&proof).map_err(|e| format!("{}: {}", proof.display(), e))?;
+            let config = ProverConfig {
+                allow_mock,
+                ..config
+            };
+            verify_proof(&bytes, &config, expected.as_slice())
+                .map_err(|e| format!("{}: {}", proof.display(), e))?;
+        }
+        Command::Bundle { proof, output } => {
+            let bytes = std::fs::read(&proof).map_err(|e| format!("{}: {}", proof.display(), e))?;
+            let envelope = ProofEnvelope::decode(&bytes)
+                .map_err(|e| format!("{}: not a saved proof ({:?})", proof.display(), e))?;
+            let bundle = ProofBundle::new(envelope)
+                .map_err(|e| format!("{}: can't bundle the proof ({:?})", proof.display(), e))?;
+            let output = config
+                .output_path(&output.unwrap_or_else(|| proof.with_extension(BUNDLE_EXTENSION)));
+            std::fs::write(&output, bundle.encode())
+                .map_err(|e| format!("{}: {}", output.display(), e))?;
+            println!("backend: {}", bundle.envelope.backend.name());
+            println!("logic version: {}", bundle.logic_version);
+            if let Some(id) = &bundle.puzzle_id {
+                println!("puzzle id: {}", id.encode());
+            }
+            println!("bundle saved to {}", output.display());
+        }
+        Command::VerifyBundle {
+            bundle: path,
+            puzzle,
+            allow_mock,
+        } => {
+            let (bundle, expected) = read_bundle(&path, &puzzle)?;
+            let config = ProverConfig {
+                allow_mock,
+                ..config
+            };
+            verify_proof(&bundle.envelope.encode(), &config, &expected)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+        }
+        Command::Badge {
+            bundle: path,
+            puzzle,
+            out_dir,
+            image_uri,
+            date,
+        } => {
+            let (bundle, expected) = read_bundle(&path, &puzzle)?;
+            // a badge is for a real proof, never a mock.
+            let config = ProverConfig {
+                allow_mock: false,
+                ..config
+            };
+            let values = verify_proof(&bundle.envelope.encode(), &config, &expected)
+                .map_err(|e| format!("{}: {}", path.display(), e))?;
+            let Some((board, _, _)) = expected.first() else {
+                return Err(
+                    "the bundle names no puzzle, give it with --puzzle or --puzzle-id".into(),
+                );
+            };
+            let date = date.unwrap_or_else(today);
+            let metadata = BadgeMetadata::new(
+                &values,
+                board,
+                bundle.puzzle_id.as_ref(),
+                &date,
+                image_uri.as_deref(),
+            )
+            .map_err(|e| format!("no badge for this proof ({:?})", e))?;
+
+            let out_dir = config.output_path(&out_dir.unwrap_or_else(|| PathBuf::from(".")));
+            std::fs::create_dir_all(&out_dir)
+                .map_err(|e| format!("{}: {}", out_dir.display(), e))?;
+            for (name, contents) in [
+                ("badge.json", metadata.to_json() + "\n"),
+                ("badge.svg", silhouette_svg(board) + "\n"),
+            ] {
+                let file = out_dir.join(name);
+                std::fs::write(&file, contents)
+                    .map_err(|e| format!("{}: {}", file.display(), e))?;
+                println!("{} saved to {}", name, file.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+// a bundle and the puzzles it has to be for: its own id's, and whatever the caller expects on
+// top of it.
+fn read_bundle(
+    path: &Path,
+    puzzle: &ExpectedPuzzle,
+) -> Result<(ProofBundle, Vec<(Board, PuzzleSource, Variant)>), String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let bundle = ProofBundle::decode(&bytes)
+        .map_err(|e| format!("{}: not a proof bundle ({:?})", path.display(), e))?;
+    let mut expected = Vec::new();
+    if let Some(id) = &bundle.puzzle_id {
+        println!("puzzle id: {}", id.encode());
+        expected.push(expected_from_id(id)?);
+    }
+    expected.extend(puzzle.load()?);
+    Ok((bundle, expected))
+}
+
+fn parse_date(arg: &str) -> Result<Date, String> {
+    Date::parse(arg).ok_or_else(|| format!("not a date '{}', expected YYYY-MM-DD", arg))
+}
+
+// today in UTC.
+fn today() -> Date {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    Date::from_days((now.as_secs() / 86_400) as u32)
+}
+
+// the config file's difficulty for the commands that weren't given one.
+fn default_difficulty(command: &mut Command, default: Difficulty) {
+    let difficulty = match command {
+        Command::Execute { input, .. }
+        | Command::Prove { input, .. }
+        | Command::Encode { input, .. }
+        | Command::Diff { input } => &mut input.difficulty,
+        Command::Verify { puzzle, .. }
+        | Command::VerifyBundle { puzzle, .. }
+        | Command::Badge { puzzle, .. } => &mut puzzle.difficulty,
+        #[cfg(feature = "tui")]
+        Command::Play(args) => &mut args.difficulty,
+        _ => return,
+    };
+    difficulty.get_or_insert(default);
+}
+
+// verify an encoded envelope, print what it proves and check it's for each expected puzzle.
+fn verify_proof(
+    bytes: &[u8],
+    config: &ProverConfig,
+    expected: &[(Board, PuzzleSource, Variant)],
+) -> Result<PublicValues, String> {
+    let verified = zk_sudoku_host::verify(bytes, config)?;
+    if verified.mock {
+        println!("MOCK PROOF, NOT VERIFIED ({:?})", verified.backend);
+    } else {
+        println!("proof verified ({:?})", verified.backend);
+    }
+    println!("proved on: {}", verified.proved_on.name());
+    print_program(&verified.program);
+    print_public_values(&verified.values);
+    for (puzzle, source, variant) in expected {
+        check_puzzle(&verified.values, puzzle, source, variant)?;
+        println!("puzzle matches");
+    }
+    Ok(verified.values)
+}
+
+// the salt is in there since it's random unless given, and needed to open the commitment later.
+fn encode(input: &ProofInput, puzzle_hash: &[u8; 32]) -> Result<serde_json::Value, String> {
+    let hex = |buffers: Vec<Vec<u8>>| {
+        buffers
+            .iter()
+            .map(|buffer| format!("0x{}", hex::encode(buffer)))
+            .collect::<Vec<_>>()
+    };
+    let mut encoded = serde_json::Map::new();
+    encoded.insert(
+        "puzzleHash".into(),
+        format!("0x{}", hex::encode(puzzle_hash)).into(),
+    );
+    encoded.insert(
+        "salt".into(),
+        format!("0x{}", hex::encode(input.salt)).into(),
+    );
+    encoded.insert("sp1".into(), hex(zk_sudoku_host::stdin::sp1(input)?).into());
+    #[cfg(feature = "openvm")]
+    encoded.insert(
+        "openvm".into(),
+        hex(zk_sudoku_host::stdin::openvm(input)?).into(),
+    );
+    Ok(encoded.into())
+}
+
+fn save_proof(envelope: &ProofEnvelope, output: &Path) -> Result<(), String> {
+    let values = PublicValues::abi_decode(&envelope.public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    check_logic_version(&values)?;
+    std::fs::write(output, envelope.encode())
+        .map_err(|e| format!("{}: {}", output.display(), e))?;
+    println!("proved on: {}", envelope.proved_on.name());
+    print_program(&Program {
+        vkey_hash: envelope.vkey_hash.clone(),
+        digest: envelope.program_digest.clone(),
+    });
+    print_public_values(&values);
+    if envelope.is_mock() {
+        println!(
+            "mock proof saved to {}, it proves nothing",
+            output.display()
+        );
+    } else {
+        println!("proof saved to {}", output.display());
+    }
+    Ok(())
+}
+
+fn print_program(program: &Program) {
+    println!("vkey hash: 0x{}", hex::encode(&program.vkey_hash));
+    println!("program digest: 0x{}", hex::encode(&program.digest));
+}
+
+// the same fields as print_public_values, byte strings as 0x-prefixed hex: the public values of
+// a proof document (sudoku_io::document).
+fn public_values_json(values: &PublicValues) -> serde_json::Value {
+    serde_json::to_value(PublicValuesDocument::from(values)).expect("public values serialize")
+}
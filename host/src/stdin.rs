@@ -0,0 +1,23 @@
+// the guest input as each zkVM's input stream carries it, one buffer per value the guest reads.
+// the backends build their stdin from these, and so do the scripts and the `encode` command, so
+// the two encodings of a ProofInput live here only.
+use sudoku_io::ProofInput;
+
+// what SP1Stdin::write pushes for each value, bincode. feed them to SP1Stdin::write_vec in
+// order.
+pub fn sp1(input: &ProofInput) -> Result<Vec<Vec<u8>>, String> {
+    let encode = |e: bincode::Error| format!("can't encode the sp1 input ({})", e);
+    Ok(vec![bincode::serialize(input).map_err(encode)?])
+}
+
+// what openvm's StdIn::write pushes, the openvm::serde words as little endian bytes, which the
+// hint stream carries one field element per byte. feed them to StdIn::write_bytes in order.
+#[cfg(feature = "openvm")]
+pub fn openvm(input: &ProofInput) -> Result<Vec<Vec<u8>>, String> {
+    let words = openvm::serde::to_vec(input)
+        .map_err(|e| format!("can't encode the openvm input ({:?})", e))?;
+    Ok(vec![words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect()])
+}
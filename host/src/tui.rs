@@ -0,0 +1,248 @@
+// playing a seeded puzzle full screen: a cursor to move around the board, pencil marks, a
+// timer, and once the board is solved, the choice to prove the solve. the proof is made after
+// the terminal is handed back, so the prover's output doesn't end up over the board.
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku_io::envelope::ProofEnvelope;
+use sudoku_io::PuzzleSource;
+use zk_sudoku_host::backend::prover;
+use zk_sudoku_host::config::ProverConfig;
+use zk_sudoku_host::input::{parse_difficulty, SubmissionArgs, DEFAULT_DIFFICULTY};
+use zk_sudoku_host::{check_input, check_proof};
+
+#[derive(clap::Args, Debug)]
+pub struct PlayArgs {
+    /// Seed of the puzzle, a random one when it isn't given.
+    #[clap(long)]
+    pub seed: Option<u32>,
+
+    /// Difficulty of the puzzle: easy, medium or hard. The config file's difficulty, or medium,
+    /// by default.
+    #[clap(long, value_parser = parse_difficulty)]
+    pub difficulty: Option<Difficulty>,
+
+    /// File the proof of the solve is saved to.
+    #[clap(long, default_value = "proof.bin")]
+    pub output: std::path::PathBuf,
+
+    #[clap(flatten)]
+    pub submission: SubmissionArgs,
+}
+
+struct Game {
+    puzzle: Board,
+    board: Board,
+    // bit v set when v is pencilled into the cell.
+    marks: [u16; 81],
+    cursor: usize,
+    pencil: bool,
+    started: Instant,
+    // how long the solve took, once it's solved.
+    solved: Option<Duration>,
+    message: String,
+}
+
+enum Outcome {
+    Quit,
+    Prove,
+}
+
+// play until the user quits, and prove the solve if they ask for it. the proof is returned
+// for the caller to save.
+pub fn run(args: &PlayArgs, config: &ProverConfig) -> Result<Option<ProofEnvelope>, String> {
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let difficulty = args.difficulty.unwrap_or(DEFAULT_DIFFICULTY);
+    let puzzle = Board::try_from_seed(seed, Some(difficulty))
+        .map_err(|e| format!("can't generate seed {} ({:?})", seed, e))?;
+    let mut game = Game {
+        board: puzzle.clone(),
+        puzzle,
+        marks: [0; 81],
+        cursor: 0,
+        pencil: false,
+        started: Instant::now(),
+        solved: None,
+        message: format!("seed {}, {:?}", seed, difficulty),
+    };
+
+    let mut terminal = ratatui::init();
+    let outcome = play(&mut terminal, &mut game);
+    ratatui::restore();
+    let (Outcome::Prove, Some(time)) = (outcome?, game.solved) else {
+        return Ok(None);
+    };
+
+    println!(
+        "solved seed {} in {}, proving it",
+        seed,
+        clock(time.as_secs())
+    );
+    // the guest regenerates the puzzle from its seed, so the proof commits it.
+    let source = PuzzleSource::Seed {
+        seed,
+        difficulty: difficulty as u8,
+    };
+    let input = args
+        .submission
+        .proof_input(source, &game.puzzle, &game.board)?;
+    println!("salt: 0x{}", hex::encode(input.salt));
+    let prover = prover(config.backend, config)?;
+    check_input(prover.as_ref(), &input, &game.puzzle)?;
+    let envelope = prover.prove(&input)?;
+    let proof = check_proof(envelope, &game.puzzle, &input)?;
+    Ok(Some(proof.envelope))
+}
+
+fn play(terminal: &mut DefaultTerminal, game: &mut Game) -> Result<Outcome, String> {
+    loop {
+        terminal
+            .draw(|frame| draw(frame, game))
+            .map_err(|e| e.to_string())?;
+        // redraw at least every second for the timer.
+        if !event::poll(Duration::from_secs(1)).map_err(|e| e.to_string())? {
+            continue;
+        }
+        let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        let (row, col) = (game.cursor / 9, game.cursor % 9);
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Outcome::Quit),
+            KeyCode::Enter if game.solved.is_some() => return Ok(Outcome::Prove),
+            KeyCode::Up | KeyCode::Char('k') => game.cursor = (row + 8) % 9 * 9 + col,
+            KeyCode::Down | KeyCode::Char('j') => game.cursor = (row + 1) % 9 * 9 + col,
+            KeyCode::Left | KeyCode::Char('h') => game.cursor = row * 9 + (col + 8) % 9,
+            KeyCode::Right | KeyCode::Char('l') => game.cursor = row * 9 + (col + 1) % 9,
+            KeyCode::Char('p') => {
+                game.pencil = !game.pencil;
+                game.message = match game.pencil {
+                    true => "pencil marks on".into(),
+                    false => "pencil marks off".into(),
+                };
+            }
+            _ if game.solved.is_some() => {}
+            KeyCode::Char(c @ '1'..='9') => enter(game, c as u8 - b'0'),
+            KeyCode::Char('0') | KeyCode::Backspace | KeyCode::Delete => {
+                if game.puzzle.cells[game.cursor] == 0 {
+                    game.board.cells[game.cursor] = 0;
+                    game.marks[game.cursor] = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// a value typed at the cursor: toggles the pencil mark in pencil mode, otherwise placed if
+// it doesn't repeat in the cell's row, column or box.
+fn enter(game: &mut Game, value: u8) {
+    let cell = game.cursor;
+    if game.puzzle.cells[cell] != 0 {
+        game.message = "that's a given".into();
+        return;
+    }
+    if game.pencil {
+        if game.board.cells[cell] == 0 {
+            game.marks[cell] ^= 1 << value;
+        }
+        return;
+    }
+    if !game.board.can_place(cell, value) {
+        game.message = format!("{} is already in this row, column or box", value);
+        return;
+    }
+    game.board.cells[cell] = value;
+    // the value is settled for the cell's peers, their marks of it are stale.
+    for peer in 0..81 {
+        let (row, col) = (peer / 9, peer % 9);
+        if row == cell / 9 || col == cell % 9 || (row / 3, col / 3) == (cell / 27, cell % 9 / 3) {
+            game.marks[peer] &= !(1 << value);
+        }
+    }
+    game.message.clear();
+    if game.board.validate() {
+        game.solved = Some(game.started.elapsed());
+        game.message = "solved! enter proves it, q quits".into();
+    }
+}
+
+fn clock(seconds: u64) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+// each cell is three lines of three characters: a value in the middle, or the pencil marks
+// as a 3x3 keypad.
+fn draw(frame: &mut Frame, game: &Game) {
+    let [board_area, status_area] =
+        Layout::vertical([Constraint::Length(31), Constraint::Length(3)]).areas(frame.area());
+    let border = Style::default().fg(Color::DarkGray);
+    let rule = |left: &str, middle: &str, right: &str| {
+        let segment = "─".repeat(11);
+        Line::styled(
+            [left, &segment, middle, &segment, middle, &segment, right].concat(),
+            border,
+        )
+    };
+
+    let mut lines = vec![rule("┌", "┬", "┐")];
+    for row in 0..9 {
+        for sub in 0..3 {
+            let mut spans = vec![Span::styled("│", border)];
+            for col in 0..9 {
+                let cell = row * 9 + col;
+                let mut style = match (game.puzzle.cells[cell], game.board.cells[cell]) {
+                    (0, 0) => Style::default().fg(Color::Gray),
+                    (0, _) => Style::default().fg(Color::Cyan),
+                    _ => Style::default().add_modifier(Modifier::BOLD),
+                };
+                if cell == game.cursor {
+                    style = style.bg(Color::DarkGray);
+                }
+                let text = match game.board.cells[cell] {
+                    0 => (1..=3)
+                        .map(|i| {
+                            let value = sub as u8 * 3 + i;
+                            match game.marks[cell] & (1 << value) != 0 {
+                                true => char::from(b'0' + value),
+                                false => ' ',
+                            }
+                        })
+                        .collect(),
+                    value if sub == 1 => format!(" {} ", value),
+                    _ => "   ".into(),
+                };
+                spans.push(Span::styled(text, style));
+                spans.push(match col % 3 {
+                    2 => Span::styled("│", border),
+                    _ => Span::raw(" "),
+                });
+            }
+            lines.push(Line::from(spans));
+        }
+        match row {
+            2 | 5 => lines.push(rule("├", "┼", "┤")),
+            8 => lines.push(rule("└", "┴", "┘")),
+            _ => {}
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), board_area);
+
+    let time = game.solved.unwrap_or_else(|| game.started.elapsed());
+    let mode = if game.pencil { "pencil" } else { "pen" };
+    let status = format!("{}  {}  {}", clock(time.as_secs()), mode, game.message);
+    let keys = "arrows/hjkl move  1-9 enter  0 erase  p pencil  q quit";
+    frame.render_widget(
+        Paragraph::new(status).block(Block::bordered().title(keys)),
+        status_area,
+    );
+}
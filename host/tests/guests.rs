@@ -0,0 +1,123 @@
+// runs the verifier guest of every backend this build has over the golden submissions in
+// golden.json, executing only, and checks the public values they commit byte for byte. the
+// expected values are the abi encoding, they only change along with LOGIC_VERSION.
+//
+//     cargo test --release --test guests
+//     cargo test --release --no-default-features --features openvm --test guests
+use serde_json::Value;
+use sudoku::core::board::Board;
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::variant::Variant;
+#[cfg(any(feature = "sp1", feature = "openvm"))]
+use sudoku_io::envelope::Backend;
+use sudoku_io::{
+    apply_policy, finish, puzzle_from_seed, verify_input, InputError, ProofInput, PublicValues,
+    PuzzleSource,
+};
+use zk_sudoku_host::check_logic_version;
+use zk_sudoku_host::input::{check_puzzle, parse_digits};
+
+struct Golden {
+    name: String,
+    input: ProofInput,
+    values: PublicValues,
+}
+
+fn golden() -> Vec<Golden> {
+    let text = include_str!("golden.json");
+    let entries: Vec<Value> = serde_json::from_str(text).expect("golden.json isn't JSON");
+    entries.iter().map(entry).collect()
+}
+
+fn entry(value: &Value) -> Golden {
+    let name = value["name"].as_str().unwrap().to_string();
+    let bytes = |key: &str| hex::decode(value[key].as_str().unwrap().trim_start_matches("0x"));
+    let source = match value["puzzle"].as_str() {
+        Some(puzzle) => {
+            let puzzle = parse_digits(puzzle).unwrap();
+            PuzzleSource::Puzzle {
+                cells: puzzle.cells.to_vec(),
+                expected_hash: puzzle_hash(&puzzle),
+            }
+        }
+        None => PuzzleSource::Seed {
+            seed: value["seed"].as_u64().unwrap() as u32,
+            difficulty: value["difficulty"].as_u64().unwrap() as u8,
+        },
+    };
+    let solution = parse_digits(value["solution"].as_str().unwrap()).unwrap();
+    let input = ProofInput {
+        salt: bytes("salt").unwrap().try_into().unwrap(),
+        reward_address: bytes("address").unwrap().try_into().unwrap(),
+        epoch: value["epoch"].as_u64().unwrap(),
+        nonce: bytes("nonce").unwrap().try_into().unwrap(),
+        ..ProofInput::new(source, solution.cells.to_vec())
+    };
+    let values = PublicValues::abi_decode(&bytes("publicValues").unwrap())
+        .unwrap_or_else(|e| panic!("{}: bad public values ({:?})", name, e));
+    Golden {
+        name,
+        input,
+        values,
+    }
+}
+
+// the golden values still are what the shared logic computes, so a guest that disagrees with
+// them disagrees with the host too. runs without any backend.
+#[test]
+fn golden_values_match_sudoku_io() {
+    for golden in golden() {
+        let values = apply_policy(
+            finish(verify_input(&golden.input, |_, _, _| false)),
+            &golden.input.policy,
+        );
+        // after a logic change, the entry's new publicValues.
+        assert!(
+            check_logic_version(&golden.values).is_ok() && values == golden.values,
+            "{}: golden.json is out of date, its publicValues are now 0x{}",
+            golden.name,
+            hex::encode(values.abi_encode())
+        );
+
+        // and what verify recomputes from the host's copy of the puzzle agrees with them.
+        let puzzle = match &golden.input.puzzle {
+            PuzzleSource::Seed { seed, difficulty } => puzzle_from_seed(*seed, *difficulty),
+            PuzzleSource::Puzzle { cells, .. } => Board::from_array(cells[..].try_into().unwrap())
+                .map_err(|_| InputError::InvalidPuzzle),
+        }
+        .unwrap();
+        check_puzzle(&values, &puzzle, &golden.input.puzzle, &Variant::Classic)
+            .unwrap_or_else(|e| panic!("{}: {}", golden.name, e));
+    }
+}
+
+#[cfg(any(feature = "sp1", feature = "openvm"))]
+fn check_guest(backend: Backend) {
+    let config = zk_sudoku_host::config::ProverConfig {
+        backend,
+        ..Default::default()
+    };
+    let prover = zk_sudoku_host::backend::prover(backend, &config).unwrap();
+    for golden in golden() {
+        let execution = prover
+            .execute(&golden.input)
+            .unwrap_or_else(|e| panic!("{}: {}", golden.name, e));
+        assert_eq!(
+            execution.values, golden.values,
+            "{}: the {:?} guest committed other values",
+            golden.name, backend
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "sp1")]
+fn sp1_guest_matches_golden() {
+    check_guest(Backend::Sp1);
+}
+
+#[test]
+#[cfg(feature = "openvm")]
+fn openvm_guest_matches_golden() {
+    check_guest(Backend::OpenVm);
+}
@@ -0,0 +1,222 @@
+//! Accepts SP1 Groth16 proofs of the zk-sudoku verifier program on Substrate chains with
+//! pallet-contracts, the ink! counterpart of `contracts/src/SudokuVerifier.sol`. The proof is
+//! checked with sp1-verifier's Groth16 verifier against the vkey hash the contract was
+//! instantiated with, then the public values (`sudoku_io::PublicValues`, ABI encoded as on the
+//! EVM) are decoded and rejected if they:
+//!
+//! - commit another logic version than the contract's,
+//! - aren't a valid solution,
+//! - reuse a nullifier, ie) the same puzzle was already submitted for that reward address.
+//!
+//! Like the solidity verifier it records, per reward address (the committed 20-byte address),
+//! the puzzles solved and how many, and emits `PuzzleCompleted`. `submit_solution`'s selector is
+//! pinned to `sudoku_io::scale::SUBMIT_SOLUTION_SELECTOR`, so hosts can build the call data
+//! without the contract's metadata.
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod sudoku_verifier {
+    use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use sudoku_io::PublicValues;
+
+    #[ink(storage)]
+    pub struct SudokuVerifier {
+        /// The vkey hash of the zk-sudoku verifier program, as the SP1 script's `vkey` command
+        /// prints it.
+        vkey_hash: [u8; 32],
+        /// The `sudoku_io::LOGIC_VERSION` proofs have to commit.
+        logic_version: u32,
+        /// Nullifiers (sha256(puzzle hash || reward address)) of the accepted proofs.
+        nullifier_used: Mapping<[u8; 32], ()>,
+        /// Number of puzzles each reward address has solved.
+        completions: Mapping<[u8; 20], u32>,
+        /// The puzzles each reward address has solved, by puzzle hash.
+        completed: Mapping<([u8; 20], [u8; 32]), ()>,
+    }
+
+    #[ink(event)]
+    pub struct PuzzleCompleted {
+        #[ink(topic)]
+        solver: [u8; 20],
+        #[ink(topic)]
+        puzzle_hash: [u8; 32],
+        epoch: u64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[ink::scale_derive(Encode, Decode, TypeInfo)]
+    pub enum Error {
+        InvalidProof,
+        MalformedPublicValues,
+        /// The logic version the proof committed.
+        LogicVersionMismatch(u32),
+        /// The status the proof committed.
+        InvalidSolution(u8),
+        NullifierUsed([u8; 32]),
+    }
+
+    impl SudokuVerifier {
+        #[ink(constructor)]
+        pub fn new(vkey_hash: [u8; 32], logic_version: u32) -> Self {
+            SudokuVerifier {
+                vkey_hash,
+                logic_version,
+                nullifier_used: Mapping::default(),
+                completions: Mapping::default(),
+                completed: Mapping::default(),
+            }
+        }
+
+        /// Verifies a Groth16 proof (`SP1ProofWithPublicValues::bytes`) of a solved puzzle and
+        /// records the completion for the reward address committed in `public_values`.
+        #[ink(message, selector = 0xde566a5d)]
+        pub fn submit_solution(
+            &mut self,
+            proof: Vec<u8>,
+            public_values: Vec<u8>,
+        ) -> Result<PublicValues, Error> {
+            sp1_verifier::Groth16Verifier::verify(
+                &proof,
+                &public_values,
+                &self.vkey_hash_hex(),
+                *sp1_verifier::GROTH16_VK_BYTES,
+            )
+            .map_err(|_| Error::InvalidProof)?;
+            let values = PublicValues::abi_decode(&public_values)
+                .map_err(|_| Error::MalformedPublicValues)?;
+            self.record(values)
+        }
+
+        #[ink(message)]
+        pub fn vkey_hash(&self) -> [u8; 32] {
+            self.vkey_hash
+        }
+
+        #[ink(message)]
+        pub fn logic_version(&self) -> u32 {
+            self.logic_version
+        }
+
+        #[ink(message)]
+        pub fn nullifier_used(&self, nullifier: [u8; 32]) -> bool {
+            self.nullifier_used.contains(nullifier)
+        }
+
+        #[ink(message)]
+        pub fn completions(&self, solver: [u8; 20]) -> u32 {
+            self.completions.get(solver).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn completed(&self, solver: [u8; 20], puzzle_hash: [u8; 32]) -> bool {
+            self.completed.contains((solver, puzzle_hash))
+        }
+
+        /// Everything after the proof: the checks on the values and the bookkeeping.
+        fn record(&mut self, values: PublicValues) -> Result<PublicValues, Error> {
+            if values.logic_version != self.logic_version {
+                return Err(Error::LogicVersionMismatch(values.logic_version));
+            }
+            if !values.valid {
+                return Err(Error::InvalidSolution(values.status));
+            }
+            if self.nullifier_used.contains(values.nullifier) {
+                return Err(Error::NullifierUsed(values.nullifier));
+            }
+
+            let solver = values.reward_address;
+            self.nullifier_used.insert(values.nullifier, &());
+            self.completed.insert((solver, values.puzzle_hash), &());
+            self.completions.insert(solver, &(self.completions(solver) + 1));
+            self.env().emit_event(PuzzleCompleted {
+                solver,
+                puzzle_hash: values.puzzle_hash,
+                epoch: values.epoch,
+            });
+            Ok(values)
+        }
+
+        /// The vkey hash the way sp1-verifier takes it, `0x` and lowercase hex.
+        fn vkey_hash_hex(&self) -> String {
+            const DIGITS: &[u8; 16] = b"0123456789abcdef";
+            let mut hex = String::from("0x");
+            for byte in self.vkey_hash {
+                hex.push(DIGITS[(byte >> 4) as usize] as char);
+                hex.push(DIGITS[(byte & 15) as usize] as char);
+            }
+            hex
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use sudoku_io::LOGIC_VERSION;
+
+        fn values() -> PublicValues {
+            PublicValues {
+                puzzle_hash: [1; 32],
+                valid: true,
+                nullifier: [2; 32],
+                epoch: 7,
+                reward_address: [3; 20],
+                logic_version: LOGIC_VERSION,
+                ..Default::default()
+            }
+        }
+
+        #[ink::test]
+        fn test_record() {
+            let mut contract = SudokuVerifier::new([0; 32], LOGIC_VERSION);
+            assert_eq!(contract.record(values()), Ok(values()));
+            assert!(contract.nullifier_used([2; 32]));
+            assert!(contract.completed([3; 20], [1; 32]));
+            assert_eq!(contract.completions([3; 20]), 1);
+            assert_eq!(ink::env::test::recorded_events().count(), 1);
+
+            // another puzzle for the same address counts too, the same one doesn't.
+            let another = PublicValues {
+                puzzle_hash: [4; 32],
+                nullifier: [5; 32],
+                ..values()
+            };
+            assert!(contract.record(another).is_ok());
+            assert_eq!(contract.completions([3; 20]), 2);
+            assert_eq!(contract.record(values()), Err(Error::NullifierUsed([2; 32])));
+        }
+
+        #[ink::test]
+        fn test_record_errors() {
+            let mut contract = SudokuVerifier::new([0; 32], LOGIC_VERSION);
+            let invalid = PublicValues {
+                valid: false,
+                status: 6,
+                ..values()
+            };
+            assert_eq!(contract.record(invalid), Err(Error::InvalidSolution(6)));
+            let outdated = PublicValues {
+                logic_version: LOGIC_VERSION - 1,
+                ..values()
+            };
+            assert_eq!(
+                contract.record(outdated),
+                Err(Error::LogicVersionMismatch(LOGIC_VERSION - 1))
+            );
+            assert!(!contract.nullifier_used([2; 32]));
+            assert_eq!(contract.completions([3; 20]), 0);
+        }
+
+        #[ink::test]
+        fn test_submit_solution() {
+            let mut contract = SudokuVerifier::new([0xab; 32], LOGIC_VERSION);
+            assert_eq!(contract.vkey_hash_hex(), ["0x", &"ab".repeat(32)].concat());
+            let public_values = values().abi_encode().to_vec();
+            assert_eq!(
+                contract.submit_solution(vec![0; 260], public_values),
+                Err(Error::InvalidProof)
+            );
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Prints what the ink! contract's `submit_solution` takes for a Groth16 proof saved by the SP1
+//! script: the two arguments as hex for `cargo contract call`, and the SCALE encoded call data
+//! for hosts that sign the `Contracts::call` extrinsic themselves.
+//! ```shell
+//! cd ../sp1_proof/script
+//! cargo run --release -- --prove --groth16 --output proof.bin
+//! cd ../../ink
+//! cargo run --release --bin call-data -- --proof ../sp1_proof/script/proof.bin
+//! ```
+//! Exits nonzero if the proof isn't an SP1 Groth16 proof.
+
+use clap::Parser;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use sudoku_io::scale::SubmitSolution;
+use sudoku_io::PublicValues;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The proof, as saved by the SP1 script's --prove --groth16 --output.
+    #[clap(long)]
+    proof: std::path::PathBuf,
+
+    /// Print only the call data, for piping into other tools.
+    #[clap(long)]
+    raw: bool,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if let Err(e) = print_call(&args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn print_call(args: &Args) -> Result<(), String> {
+    let bytes =
+        std::fs::read(&args.proof).map_err(|e| format!("{}: {}", args.proof.display(), e))?;
+    let envelope =
+        ProofEnvelope::decode(&bytes).map_err(|e| format!("not a saved proof ({:?})", e))?;
+    if envelope.backend != Backend::Sp1 {
+        return Err(format!(
+            "a {} proof, the contract takes SP1 proofs",
+            envelope.backend.name()
+        ));
+    }
+    let proof: SP1ProofWithPublicValues = bincode::deserialize(&envelope.proof)
+        .map_err(|e| format!("can't read the proof ({})", e))?;
+    if !matches!(proof.proof, SP1Proof::Groth16(_)) {
+        return Err("not a Groth16 proof, prove with --groth16".into());
+    }
+    let call = SubmitSolution {
+        proof: proof.bytes(),
+        public_values: proof.public_values.to_vec(),
+    };
+    if args.raw {
+        println!("0x{}", hex::encode(call.call_data()));
+        return Ok(());
+    }
+
+    let values = PublicValues::abi_decode(&call.public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    println!("vkey hash: 0x{}", hex::encode(&envelope.vkey_hash));
+    println!("puzzle hash: 0x{}", hex::encode(values.puzzle_hash));
+    println!("nullifier: 0x{}", hex::encode(values.nullifier));
+    println!(
+        "args: 0x{} 0x{}",
+        hex::encode(&call.proof),
+        hex::encode(&call.public_values)
+    );
+    println!("call data: 0x{}", hex::encode(call.call_data()));
+    Ok(())
+}
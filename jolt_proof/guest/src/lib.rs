@@ -0,0 +1,120 @@
+#![cfg_attr(feature = "guest", no_std)]
+// jolt guest for the sudoku verifier. jolt has no input stream, the inputs are the arguments
+// of the provable function and the public values are its return value, but the layout matches
+// the sp1 and openvm guests through sudoku_io.
+extern crate alloc;
+use alloc::vec::Vec;
+
+use sudoku_io::{
+    aggregate, apply_policy, check_progress, check_solvable, check_transcript, check_unique,
+    finish, verify_input, verify_solution, Move, ProofInput, PuzzleSource,
+};
+
+// `input` holds everything the sp1 and openvm verifiers read, see sudoku_io::ProofInput.
+// returns the public values abi encoded by sudoku_io::PublicValues::abi_encode, the same bytes
+// the sp1 guest commits.
+#[jolt::provable(max_input_size = 4096)]
+fn verify_sudoku(input: ProofInput) -> Vec<u8> {
+    // malformed input panics unless the panic-free feature is on, in which case its error code
+    // is returned as the status.
+    let values = finish(verify_input(&input, verify_ed25519));
+    apply_policy(values, &input.policy).abi_encode().to_vec()
+}
+
+// (puzzle hash, solvable, solution hash)
+pub type SolvabilityValues = ([u8; 32], bool, [u8; 32]);
+
+// runs the solver on the puzzle in the guest and only returns the hash of what it found,
+// salted with the private `salt`.
+#[jolt::provable(max_input_size = 4096)]
+fn prove_solvable(puzzle: PuzzleSource, salt: [u8; 32]) -> SolvabilityValues {
+    let values = finish(
+        puzzle
+            .load()
+            .map(|(board, _, _)| check_solvable(&board, &salt)),
+    );
+    (values.puzzle_hash, values.solvable, values.solution_hash)
+}
+
+// (puzzle hash, unique)
+pub type UniquenessValues = ([u8; 32], bool);
+
+// counts the puzzle's solutions in the guest, true when there is exactly one.
+#[jolt::provable(max_input_size = 4096)]
+fn prove_unique(puzzle: PuzzleSource) -> UniquenessValues {
+    let values = finish(puzzle.load().map(|(board, _, _)| check_unique(&board)));
+    (values.puzzle_hash, values.unique)
+}
+
+// (puzzle hash, consistent, clue count, filled, status)
+pub type ProgressValues = ([u8; 32], bool, u8, u8, u8);
+
+// checks a partially filled `user_input` (0 for empty cells) and returns how many cells were
+// filled in correctly, without the placements.
+#[jolt::provable(max_input_size = 4096)]
+fn prove_progress(puzzle: PuzzleSource, user_input: Vec<u8>) -> ProgressValues {
+    let values = finish(
+        puzzle
+            .load()
+            .and_then(|(board, _, _)| check_progress(board, &user_input)),
+    );
+    (
+        values.puzzle_hash,
+        values.consistent,
+        values.clue_count,
+        values.filled,
+        values.status,
+    )
+}
+
+// (puzzle hash, valid, move count, illegal move, transcript hash, status)
+pub type TranscriptValues = ([u8; 32], bool, u32, u32, [u8; 32], u8);
+
+// replays the session's `moves` (value 0 erases) against the puzzle and returns whether every
+// move was legal and the board ends up solved, without the moves themselves.
+#[jolt::provable(max_input_size = 4096)]
+fn prove_transcript(puzzle: PuzzleSource, moves: Vec<Move>) -> TranscriptValues {
+    let values = finish(
+        puzzle
+            .load()
+            .map(|(board, _, _)| check_transcript(board, &moves)),
+    );
+    (
+        values.puzzle_hash,
+        values.valid,
+        values.move_count,
+        values.illegal_move,
+        values.transcript_hash,
+        values.status,
+    )
+}
+
+// (count, valid count, results root)
+pub type BatchValues = (u32, u32, [u8; 32]);
+
+// one submission of a batch: the puzzle and the user's (raw or packed) board.
+pub type Submission = (PuzzleSource, Vec<u8>);
+
+// verifies every submission and returns the number of valid ones with the merkle root of
+// the per-submission results.
+#[jolt::provable(max_input_size = 65536)]
+fn verify_batch(submissions: Vec<Submission>) -> BatchValues {
+    let results: Vec<sudoku_io::PublicValues> = submissions
+        .iter()
+        .map(|(puzzle, user_input)| {
+            finish(puzzle.load().and_then(|(board, difficulty, _)| {
+                verify_solution(board, difficulty, user_input, &[0; 32])
+            }))
+        })
+        .collect();
+    let values = aggregate(&results);
+    (values.count, values.valid_count, values.results_root)
+}
+
+fn verify_ed25519(key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> bool {
+    ed25519_dalek::VerifyingKey::from_bytes(key)
+        .and_then(|key| {
+            key.verify_strict(message, &ed25519_dalek::Signature::from_bytes(signature))
+        })
+        .is_ok()
+}
@@ -0,0 +1,64 @@
+//! Proves the seed 666 demo puzzle with Jolt, to compare against the SP1 and OpenVM backends.
+//!
+//! ```shell
+//! cargo run --release
+//! ```
+use std::time::Instant;
+
+use sudoku::core::board::Difficulty;
+use sudoku_io::{ProofInput, PublicValues, PuzzleSource, LOGIC_VERSION};
+
+pub fn main() {
+    let (prove_verify_sudoku, verify_verify_sudoku) = guest::build_verify_sudoku();
+
+    let user_input: Vec<u8> = vec![
+        7, 5, 3, 8, 2, 1, 6, 9, 4, 1, 2, 4, 3, 6, 9, 5, 7, 8, 6, 8, 9, 4, 5, 7, 1, 2, 3, 2, 9, 1,
+        5, 7, 3, 8, 4, 6, 8, 4, 7, 2, 1, 6, 9, 3, 5, 5, 3, 6, 9, 4, 8, 2, 1, 7, 3, 7, 2, 1, 8, 5,
+        4, 6, 9, 4, 6, 5, 7, 9, 2, 3, 8, 1, 9, 1, 8, 6, 3, 4, 7, 5, 2,
+    ];
+
+    let start = Instant::now();
+    let (output, proof) = prove_verify_sudoku(ProofInput::new(
+        PuzzleSource::Seed {
+            seed: 666,
+            difficulty: Difficulty::Medium as u8,
+        },
+        user_input,
+    ));
+    println!("proved in {:?}", start.elapsed());
+
+    let values = PublicValues::abi_decode(&output).expect("guest returned malformed public values");
+    assert_eq!(
+        values.logic_version, LOGIC_VERSION,
+        "guest was built from different logic than this host"
+    );
+    println!("puzzle hash: 0x{}", hex(&values.puzzle_hash));
+    println!(
+        "solution commitment: 0x{}",
+        hex(&values.solution_commitment)
+    );
+    println!("valid: {}", values.valid);
+    println!(
+        "difficulty: {}, clues: {}",
+        values.difficulty, values.clue_count
+    );
+    println!("seed: {}", values.seed);
+    println!("nullifier: 0x{}", hex(&values.nullifier));
+    println!("epoch: {}", values.epoch);
+    println!("reward address: 0x{}", hex(&values.reward_address));
+    println!("status: {}", values.status);
+    println!("policy hash: 0x{}", hex(&values.policy_hash));
+    println!("organizer key: 0x{}", hex(&values.organizer_key));
+    println!("variant: {}", values.variant);
+    println!("logic version: {}", values.logic_version);
+    println!("cells root: 0x{}", hex(&values.cells_root));
+    println!("givens hash: 0x{}", hex(&values.givens_hash));
+    println!("nonce: 0x{}", hex(&values.nonce));
+
+    let is_valid = verify_verify_sudoku(proof);
+    println!("proof verified: {}", is_valid);
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -1,38 +1,45 @@
 #![cfg_attr(not(feature = "std"), no_main)]
 #![cfg_attr(not(feature = "std"), no_std)]
 // src/main.rs
-use openvm::io::{read_vec, reveal};
-use sudoku::core::board::{Board, Difficulty};
-use sudoku::core::solver::DancingLinks;
-extern crate alloc;
-use alloc::vec::Vec;
+use openvm::io::{read, reveal};
+use sudoku_io::{apply_policy, finish, phase, to_reveal_words, verify_input, ProofInput};
 
 openvm::entry!(main);
 
-fn main() {
-    // read_vec is a helper function that reads passed input from the hint stream.
-    let user_input: Vec<u8> = read_vec();
+// board commitments go through the sha256 intrinsic instead of the pure rust sha2 crate,
+// see sudoku::core::commitment::sha256. needs the sha256 extension enabled in openvm.toml.
+#[no_mangle]
+fn sudoku_sha256(input: &[u8]) -> [u8; 32] {
+    openvm_sha256_guest::sha256(input)
+}
 
-    // Generate a board from a seed.
-    let mut board = Board::from_seed(666, Some(Difficulty::Medium));
+#[cfg(feature = "log")]
+#[no_mangle]
+fn sudoku_log(line: &str) {
+    openvm::io::println(line);
+}
 
-    let mut dl = DancingLinks::new();
-    dl.init_header_row();
-    dl.init_constraint_matrix();
-    let sol = dl.solve_with_partial(&board).unwrap();
-    let solution_board = DancingLinks::to_sudoku_board(sol);
+fn verify_ed25519(key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> bool {
+    ed25519_dalek::VerifyingKey::from_bytes(key)
+        .and_then(|key| {
+            key.verify_strict(message, &ed25519_dalek::Signature::from_bytes(signature))
+        })
+        .is_ok()
+}
 
-    // unless you unwrap this, the execution doesn't panic.
-    board.apply_user_input_to_board(user_input);
-    
-    // #[cfg(not(feature = "std"))]
-    // println!("User playing board {}", board);
+fn main() {
+    // the whole input is one sudoku_io::ProofInput, deserialized from the hint stream. see its
+    // fields for what goes in, boards (givens and user input) are either 81 raw cells or 41
+    // packed bytes.
+    let input: ProofInput = phase("decode", read);
 
-    // let valid = board.validate();
-    let valid = false;
-    
-    // #[cfg(not(feature = "std"))]
-    // println!("user solution is {}", valid);
+    // no solver in here, verifying only needs the puzzle and validate() on the user's board.
+    // malformed input panics unless the panic-free feature is on, in which case its error code
+    // is revealed as the status.
+    let values = apply_policy(finish(verify_input(&input, verify_ed25519)), &input.policy);
 
-    reveal(1 as u32, 0);
+    // slot layout in sudoku_io::to_reveal_words, from_reveal_words reads it back on the host.
+    for (i, word) in to_reveal_words(&values).into_iter().enumerate() {
+        reveal(word, i);
+    }
 }
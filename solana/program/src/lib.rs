@@ -0,0 +1,178 @@
+//! Accepts SP1 Groth16 proofs of the zk-sudoku verifier program on Solana, the counterpart of
+//! `contracts/src/SudokuVerifier.sol`. The proof is checked with sp1-solana's Groth16 verifier
+//! against the program's vkey hash, then the public values (`sudoku_io::PublicValues`, ABI
+//! encoded as on the EVM) are decoded and rejected if they:
+//!
+//! - commit another logic version than the one this program was built with,
+//! - aren't a valid solution (the committed status is logged),
+//! - reuse a nullifier, ie) the same puzzle was already submitted for that reward address.
+//!
+//! Each accepted nullifier gets an empty account at the address derived from
+//! `[NULLIFIER_SEED, nullifier]`, which can only be created once. The vkey hash is pinned at
+//! build time:
+//! ```shell
+//! SUDOKU_VKEY_HASH=0x00a1... cargo build-sbf
+//! ```
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke_signed;
+use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::sysvar::Sysvar;
+use solana_program::{msg, system_instruction};
+use sudoku_io::{PublicValues, LOGIC_VERSION};
+
+/// The vkey hash of the zk-sudoku verifier program, as the SP1 script's `vkey` command prints it.
+pub const SUDOKU_VKEY_HASH: &str = env!("SUDOKU_VKEY_HASH");
+
+/// Seed of the nullifier accounts, followed by the nullifier itself.
+pub const NULLIFIER_SEED: &[u8] = b"nullifier";
+
+/// The instruction data: the Groth16 proof (`SP1ProofWithPublicValues::bytes`) and the public
+/// values it commits.
+///
+/// Accounts: the payer (signer, writable), the nullifier account (writable, see
+/// `nullifier_address`) and the system program.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SubmitSolution {
+    pub proof: Vec<u8>,
+    pub public_values: Vec<u8>,
+}
+
+/// Returned as `ProgramError::Custom` with the discriminant as its code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SudokuError {
+    InvalidProof = 0,
+    MalformedPublicValues = 1,
+    LogicVersionMismatch = 2,
+    InvalidSolution = 3,
+    NullifierUsed = 4,
+}
+
+impl From<SudokuError> for ProgramError {
+    fn from(e: SudokuError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+/// The account that marks `nullifier` as used, and its bump seed.
+pub fn nullifier_address(program_id: &Pubkey, nullifier: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier], program_id)
+}
+
+/// What the contract checks after the proof: the logic version and the solution's validity.
+pub fn check_values(values: &PublicValues) -> Result<(), SudokuError> {
+    if values.logic_version != LOGIC_VERSION {
+        msg!(
+            "logic version {} isn't {}",
+            values.logic_version,
+            LOGIC_VERSION
+        );
+        return Err(SudokuError::LogicVersionMismatch);
+    }
+    if !values.valid {
+        msg!("invalid solution, status {}", values.status);
+        return Err(SudokuError::InvalidSolution);
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = SubmitSolution::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    sp1_solana::verify_proof(
+        &instruction.proof,
+        &instruction.public_values,
+        SUDOKU_VKEY_HASH,
+        sp1_solana::GROTH16_VK_4_0_0_RC3_BYTES,
+    )
+    .map_err(|_| SudokuError::InvalidProof)?;
+    let values = PublicValues::abi_decode(&instruction.public_values)
+        .map_err(|_| SudokuError::MalformedPublicValues)?;
+    check_values(&values)?;
+
+    let accounts = &mut accounts.iter();
+    let payer = next_account_info(accounts)?;
+    let nullifier = next_account_info(accounts)?;
+    let system_program = next_account_info(accounts)?;
+    let (address, bump) = nullifier_address(program_id, &values.nullifier);
+    if *nullifier.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if nullifier.owner == program_id {
+        return Err(SudokuError::NullifierUsed.into());
+    }
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            &address,
+            Rent::get()?.minimum_balance(0),
+            0,
+            program_id,
+        ),
+        &[payer.clone(), nullifier.clone(), system_program.clone()],
+        &[&[NULLIFIER_SEED, &values.nullifier, &[bump]]],
+    )?;
+
+    msg!(
+        "puzzle 0x{} completed by 0x{}, epoch {}",
+        hex(&values.puzzle_hash),
+        hex(&values.reward_address),
+        values.epoch
+    );
+    Ok(())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_values() {
+        let values = PublicValues {
+            valid: true,
+            logic_version: LOGIC_VERSION,
+            ..Default::default()
+        };
+        assert_eq!(check_values(&values), Ok(()));
+        let invalid = PublicValues {
+            valid: false,
+            status: 6,
+            ..values.clone()
+        };
+        assert_eq!(check_values(&invalid), Err(SudokuError::InvalidSolution));
+        let outdated = PublicValues {
+            logic_version: LOGIC_VERSION - 1,
+            ..values
+        };
+        assert_eq!(
+            check_values(&outdated),
+            Err(SudokuError::LogicVersionMismatch)
+        );
+    }
+
+    #[test]
+    fn test_instruction_round_trip() {
+        let instruction = SubmitSolution {
+            proof: vec![1; 260],
+            public_values: PublicValues::default().abi_encode().to_vec(),
+        };
+        let data = borsh::to_vec(&instruction).unwrap();
+        assert_eq!(SubmitSolution::try_from_slice(&data).unwrap(), instruction);
+    }
+}
@@ -0,0 +1,118 @@
+//! Submits a Groth16 proof saved by the SP1 script to the Solana program, which verifies it and
+//! records its nullifier. The proof has to be wrapped in Groth16 when it's made:
+//! ```shell
+//! cd ../sp1_proof/script
+//! cargo run --release -- --prove --groth16 --output proof.bin
+//! cd ../../solana
+//! cargo run --release --bin submit -- --proof ../sp1_proof/script/proof.bin --program-id <ID>
+//! ```
+//! Exits nonzero if the proof isn't a Groth16 proof of the zk-sudoku verifier program or the
+//! transaction fails.
+
+use borsh::to_vec;
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::Transaction;
+use sp1_sdk::{SP1Proof, SP1ProofWithPublicValues};
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use sudoku_io::PublicValues;
+use sudoku_solana_program::{nullifier_address, SubmitSolution};
+
+/// Groth16 verification takes about 280k compute units, more than a transaction gets by default.
+const COMPUTE_UNITS: u32 = 500_000;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The proof, as saved by the SP1 script's --prove --groth16 --output.
+    #[clap(long)]
+    proof: std::path::PathBuf,
+
+    /// Address of the deployed sudoku-solana-program.
+    #[clap(long, env = "SUDOKU_PROGRAM_ID")]
+    program_id: Pubkey,
+
+    #[clap(long, env = "SOLANA_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    rpc_url: String,
+
+    /// Keypair that signs and pays for the transaction and the nullifier account.
+    #[clap(long, default_value = "~/.config/solana/id.json")]
+    keypair: String,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    if let Err(e) = submit(&args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn submit(args: &Args) -> Result<(), String> {
+    let bytes =
+        std::fs::read(&args.proof).map_err(|e| format!("{}: {}", args.proof.display(), e))?;
+    let envelope =
+        ProofEnvelope::decode(&bytes).map_err(|e| format!("not a saved proof ({:?})", e))?;
+    if envelope.backend != Backend::Sp1 {
+        return Err(format!(
+            "a {} proof, the program takes SP1 proofs",
+            envelope.backend.name()
+        ));
+    }
+    let proof: SP1ProofWithPublicValues = bincode::deserialize(&envelope.proof)
+        .map_err(|e| format!("can't read the proof ({})", e))?;
+    if !matches!(proof.proof, SP1Proof::Groth16(_)) {
+        return Err("not a Groth16 proof, prove with --groth16".into());
+    }
+    let values = PublicValues::abi_decode(proof.public_values.as_slice())
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    println!("vkey hash: 0x{}", hex::encode(&envelope.vkey_hash));
+    println!("puzzle hash: 0x{}", hex::encode(values.puzzle_hash));
+    println!("nullifier: 0x{}", hex::encode(values.nullifier));
+
+    let keypair_path = match args.keypair.strip_prefix("~/") {
+        Some(rest) => format!("{}/{}", std::env::var("HOME").unwrap_or_default(), rest),
+        None => args.keypair.clone(),
+    };
+    let payer = read_keypair_file(&keypair_path).map_err(|e| format!("{}: {}", keypair_path, e))?;
+    let (nullifier, _) = nullifier_address(&args.program_id, &values.nullifier);
+    let data = to_vec(&SubmitSolution {
+        proof: proof.bytes(),
+        public_values: proof.public_values.to_vec(),
+    })
+    .map_err(|e| e.to_string())?;
+    let instruction = Instruction::new_with_bytes(
+        args.program_id,
+        &data,
+        vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(nullifier, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    );
+
+    let client =
+        RpcClient::new_with_commitment(args.rpc_url.clone(), CommitmentConfig::confirmed());
+    let blockhash = client.get_latest_blockhash().map_err(|e| e.to_string())?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(COMPUTE_UNITS),
+            instruction,
+        ],
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+    let signature = client
+        .send_and_confirm_transaction(&transaction)
+        .map_err(|e| format!("the program refused the proof ({})", e))?;
+    println!("accepted in transaction {}", signature);
+    Ok(())
+}
@@ -1,20 +1,71 @@
-use alloy_sol_types::sol;
+use serde::{Deserialize, Serialize};
 
-sol! {
-    /// The public values encoded as a struct that can be easily deserialized inside Solidity.
-    struct PublicValuesStruct {
-        bool valid;
+// The digest of an SP1 verifying key as the checkpoint program commits it, its eight words
+// (`HashableKey::hash_u32` on the host) in big endian order.
+pub fn vkey_bytes(vkey: &[u32; 8]) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(vkey) {
+        chunk.copy_from_slice(&word.to_be_bytes());
     }
+    bytes
 }
 
-/// Compute the n'th fibonacci number (wrapping around on overflows), using normal Rust code.
-pub fn fibonacci(n: u32) -> (u32, u32) {
-    let mut a = 0u32;
-    let mut b = 1u32;
-    for _ in 0..n {
-        let c = a.wrapping_add(b);
-        a = b;
-        b = c;
+// The JSON fixture the contracts' Foundry tests read: every field of the verifier program's
+// public values next to their abi encoding, so a layout change on either side fails the tests,
+// plus the program's vkey and, for wrapped proofs, the proof bytes (empty otherwise). Byte
+// strings are 0x-prefixed hex.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicValuesFixture {
+    pub puzzle_hash: String,
+    pub solution_commitment: String,
+    pub valid: bool,
+    pub difficulty: u8,
+    pub seed: u32,
+    pub nullifier: String,
+    pub clue_count: u8,
+    pub epoch: u64,
+    pub reward_address: String,
+    pub status: u8,
+    pub policy_hash: String,
+    pub organizer_key: String,
+    pub variant: u8,
+    pub logic_version: u32,
+    pub cells_root: String,
+    pub givens_hash: String,
+    pub nonce: String,
+    pub vkey: String,
+    pub public_values: String,
+    pub proof: String,
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+impl PublicValuesFixture {
+    pub fn new(values: &sudoku_io::PublicValues, vkey: String, proof: &[u8]) -> Self {
+        PublicValuesFixture {
+            puzzle_hash: hex_string(&values.puzzle_hash),
+            solution_commitment: hex_string(&values.solution_commitment),
+            valid: values.valid,
+            difficulty: values.difficulty,
+            seed: values.seed,
+            nullifier: hex_string(&values.nullifier),
+            clue_count: values.clue_count,
+            epoch: values.epoch,
+            reward_address: hex_string(&values.reward_address),
+            status: values.status,
+            policy_hash: hex_string(&values.policy_hash),
+            organizer_key: hex_string(&values.organizer_key),
+            variant: values.variant,
+            logic_version: values.logic_version,
+            cells_root: hex_string(&values.cells_root),
+            givens_hash: hex_string(&values.givens_hash),
+            nonce: hex_string(&values.nonce),
+            vkey,
+            public_values: hex_string(&values.abi_encode()),
+            proof: hex_string(proof),
+        }
     }
-    (a, b)
 }
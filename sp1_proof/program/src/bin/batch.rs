@@ -0,0 +1,37 @@
+//! Verifies a batch of (puzzle, solution) submissions in one proof and commits the number of
+//! valid ones along with a merkle root of the per-submission results, so a service checking
+//! many players at once pays for one proof instead of one each.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+extern crate alloc;
+use alloc::vec::Vec;
+use sudoku_io::{aggregate, finish, verify_solution, BatchInput, PublicValues};
+
+pub fn main() {
+    // every submission as one sudoku_io::BatchInput, a puzzle source and the user's (raw or
+    // packed) board each.
+    let input = sp1_zkvm::io::read::<BatchInput>();
+
+    let results: Vec<PublicValues> = input
+        .submissions
+        .iter()
+        .map(|submission| {
+            // with panic-free a malformed submission only puts its error code in its own
+            // leaf, otherwise it aborts the whole batch. the leaves don't include the
+            // solution commitment, so there is no salt to read.
+            finish(submission.puzzle.load().and_then(|(board, difficulty, _)| {
+                verify_solution(board, difficulty, &submission.solution, &[0; 32])
+            }))
+        })
+        .collect();
+
+    let values = aggregate(&results);
+    println!(
+        "{} of {} submissions valid",
+        values.valid_count, values.count
+    );
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
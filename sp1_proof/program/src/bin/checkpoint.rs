@@ -0,0 +1,45 @@
+//! Proves a long solving session in links: each run replays the next stretch of moves on top of
+//! the board the previous link committed to, after verifying the previous link's proof in the
+//! guest, so a session is proven incrementally instead of re-proving it from the first move.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use fibonacci_lib::vkey_bytes;
+use sudoku::core::commitment::sha256;
+use sudoku_io::{extend_checkpoint, finish, CheckpointInput, CheckpointValues};
+
+pub fn main() {
+    // See sudoku_io::CheckpointInput. The previous link's proof itself comes through the proof
+    // stream the host fills with SP1Stdin::write_proof.
+    let input = sp1_zkvm::io::read::<CheckpointInput>();
+
+    let values = finish(input.puzzle.load().and_then(|(board, _, _)| {
+        match &input.previous {
+            Some(previous) => {
+                // Panics (and so fails to prove) unless the next proof in the stream was made
+                // by the program with this key and committed exactly these public values.
+                sp1_zkvm::lib::verify::verify_sp1_proof(
+                    &previous.vkey,
+                    &sha256(&previous.public_values),
+                );
+                let decoded = CheckpointValues::abi_decode(&previous.public_values)?;
+                extend_checkpoint(
+                    board,
+                    Some((&decoded, &previous.board)),
+                    vkey_bytes(&previous.vkey),
+                    &input.moves,
+                    &input.salt,
+                )
+            }
+            None => extend_checkpoint(board, None, [0; 32], &input.moves, &input.salt),
+        }
+    }));
+
+    println!(
+        "{} moves so far, legal: {}, solved: {}",
+        values.move_count, values.legal, values.solved
+    );
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
@@ -0,0 +1,29 @@
+//! Proves partial progress on a puzzle: the user's board may still have empty cells, and the
+//! program commits how many of them were filled in correctly without revealing which ones or
+//! with what.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sudoku_io::{check_progress, finish, ProgressInput};
+
+pub fn main() {
+    // the puzzle source as in the verifier program and the user's raw or packed board, 0 for
+    // the cells the user hasn't filled yet, see sudoku_io::ProgressInput.
+    let input = sp1_zkvm::io::read::<ProgressInput>();
+
+    let values = finish(
+        input
+            .puzzle
+            .load()
+            .and_then(|(board, _, _)| check_progress(board, &input.solution)),
+    );
+
+    println!(
+        "{} of {} empty cells filled",
+        values.filled,
+        81 - values.clue_count
+    );
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
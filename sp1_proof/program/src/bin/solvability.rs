@@ -0,0 +1,27 @@
+//! Proves a puzzle has a solution by running the DLX solver on it inside the zkVM. Only the
+//! salted hash of the solution is committed, so the proof doesn't give the solution away.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sudoku_io::{check_solvable, finish, SolvabilityInput};
+
+pub fn main() {
+    // the puzzle source as in the verifier program and the random salt chosen by the host and
+    // folded into the solution hash, see sudoku_io::SolvabilityInput. the salt stays private,
+    // the prover only hands it out to open the commitment.
+    let input = sp1_zkvm::io::read::<SolvabilityInput>();
+
+    // malformed input aborts the guest, unless the panic-free feature is on. there is no
+    // status to commit here, so the zero values (not solvable) go out instead.
+    let values = finish(
+        input
+            .puzzle
+            .load()
+            .map(|(board, _, _)| check_solvable(&board, &input.salt)),
+    );
+
+    println!("puzzle is solvable: {}", values.solvable);
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
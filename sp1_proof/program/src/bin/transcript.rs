@@ -0,0 +1,28 @@
+//! Proves how a puzzle was solved rather than just the end state: replays the user's moves
+//! against the puzzle, checking each one is legal, and commits whether the session ends with
+//! the board solved, without revealing the moves.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sudoku_io::{check_transcript, finish, TranscriptInput};
+
+pub fn main() {
+    // the puzzle source as in the verifier program and the session's moves in the order they
+    // were made, see sudoku_io::TranscriptInput.
+    let input = sp1_zkvm::io::read::<TranscriptInput>();
+
+    let values = finish(
+        input
+            .puzzle
+            .load()
+            .map(|(board, _, _)| check_transcript(board, &input.moves)),
+    );
+
+    println!(
+        "{} moves, solved: {}, first illegal move: {}",
+        values.move_count, values.valid, values.illegal_move
+    );
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
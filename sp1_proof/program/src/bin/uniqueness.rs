@@ -0,0 +1,24 @@
+//! Proves a puzzle is well-posed: the solver counts its solutions inside the zkVM (stopping at
+//! two) and the program commits whether there was exactly one. Lets a tournament show the
+//! puzzles it publishes can't be solved two ways.
+
+#![no_main]
+sp1_zkvm::entrypoint!(main);
+
+use sudoku_io::{check_unique, finish, UniquenessInput};
+
+pub fn main() {
+    // the puzzle source as in the verifier program, see sudoku_io::UniquenessInput.
+    let input = sp1_zkvm::io::read::<UniquenessInput>();
+
+    let values = finish(
+        input
+            .puzzle
+            .load()
+            .map(|(board, _, _)| check_unique(&board)),
+    );
+
+    println!("puzzle has a unique solution: {}", values.unique);
+    let bytes = values.abi_encode();
+    sp1_zkvm::io::commit_slice(&bytes);
+}
@@ -0,0 +1,31 @@
+// input reading shared by the guest programs in this crate.
+
+// sink for the sudoku crate's log! macro, written to the zkVM's stdout.
+#[cfg(feature = "log")]
+#[no_mangle]
+fn sudoku_log(line: &str) {
+    println!("{}", line);
+}
+
+// How far the heap has grown, see sudoku_io::heap. SP1's default bump allocator never frees,
+// so the next address it hands out minus the end of the program image is the heap in use.
+#[cfg(feature = "heap-stats")]
+#[no_mangle]
+fn sudoku_heap_used() -> usize {
+    extern "C" {
+        static _end: u8;
+    }
+    unsafe {
+        sp1_zkvm::syscalls::sys_alloc_aligned(0, 1) as usize - core::ptr::addr_of!(_end) as usize
+    }
+}
+
+// Checks an organizer's signature over the puzzle hash, see sudoku_io::check_organizer. The
+// curve arithmetic goes through SP1's ed25519 precompile via the patched curve25519-dalek.
+pub fn verify_ed25519(key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> bool {
+    ed25519_dalek::VerifyingKey::from_bytes(key)
+        .and_then(|key| {
+            key.verify_strict(message, &ed25519_dalek::Signature::from_bytes(signature))
+        })
+        .is_ok()
+}
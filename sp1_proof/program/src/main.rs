@@ -1,5 +1,5 @@
-//! A simple program that takes a number `n` as input, and writes the `n-1`th and `n`th fibonacci
-//! number as an output.
+//! Verifies a submitted sudoku board against its puzzle and commits the abi encoded
+//! sudoku_io::PublicValues.
 
 // These two lines are necessary for the program to properly compile.
 //
@@ -8,41 +8,24 @@
 #![no_main]
 sp1_zkvm::entrypoint!(main);
 
-use sudoku::core::board::{Board, Difficulty};
-use sudoku::core::solver::DancingLinks;
-extern crate alloc;
-use alloc::vec::Vec;
-use fibonacci_lib::{PublicValuesStruct};
-use alloy_sol_types::SolType;
-pub fn main() {
-    // Read an input to the program.
-    //
-    // Behind the scenes, this compiles down to a custom system call which handles reading inputs
-    // from the prover.
-    let n = sp1_zkvm::io::read::<u32>();
-    let user_input =  sp1_zkvm::io::read::<Vec<u8>>();
-
-    println!("{:?}", user_input);
-
-    let mut board = Board::from_seed(666, Some(Difficulty::Medium));
-
-    #[cfg(not(feature = "std"))]
-    println!("Board generated! {}", board);
+use sudoku_io::{apply_policy, finish, phase, verify_input, ProofInput};
 
-    let mut dl = DancingLinks::new();
-    dl.init_header_row();
-    dl.init_constraint_matrix();
-    let sol = dl.solve_with_partial(&board).unwrap();
-    let solution_board = DancingLinks::to_sudoku_board(sol);
-
-    // unless you unwrap this, the execution doesn't panic.
-    board.apply_user_input_to_board(user_input);
-    #[cfg(not(feature = "std"))]
-    println!("User playing board {}", board);
-
-    let valid = board.validate();
-    #[cfg(not(feature = "std"))]
-    println!("user solution is {}", valid);
-        let bytes = PublicValuesStruct::abi_encode(&PublicValuesStruct {valid });
-    sp1_zkvm::io::commit_slice(&bytes);
+pub fn main() {
+    // Everything comes in as one sudoku_io::ProofInput: the puzzle source, the user's
+    // board, the private salt for the solution commitment, the reward address and epoch the
+    // proof is bound to, the verifier's policy, the optional organizer signature and the
+    // variant. See its fields for what each one does.
+    let input = phase("decode", sp1_zkvm::io::read::<ProofInput>);
+
+    // No solver in here, verifying only needs the puzzle and validate() on the user's board.
+    // Malformed input panics unless the panic-free feature is on, in which case its error code
+    // is committed as the status.
+    let values = finish(verify_input(&input, fibonacci_program::verify_ed25519));
+    let values = apply_policy(values, &input.policy);
+
+    sudoku::log!("user solution is {}, status {}", values.valid, values.status);
+    sp1_zkvm::io::commit_slice(&values.abi_encode());
+
+    #[cfg(feature = "heap-stats")]
+    sudoku_io::heap::report();
 }
@@ -1,5 +1,22 @@
-use sp1_build::build_program_with_args;
+use sp1_build::{build_program_with_args, BuildArgs};
 
 fn main() {
-    build_program_with_args("../program", Default::default())
+    // ZK_SUDOKU_BENCH=1 builds the guests with cycle-tracker markers for the bench binary.
+    // Instead of 1 it can list more guest features to build with, e.g.
+    // ZK_SUDOKU_BENCH=heap-stats,fixed-buffers.
+    println!("cargo:rerun-if-env-changed=ZK_SUDOKU_BENCH");
+    let features = match std::env::var("ZK_SUDOKU_BENCH") {
+        Ok(extra) => std::iter::once("bench")
+            .chain(extra.split(',').filter(|f| !f.is_empty() && *f != "1"))
+            .map(String::from)
+            .collect(),
+        Err(_) => vec![],
+    };
+    build_program_with_args(
+        "../program",
+        BuildArgs {
+            features,
+            ..Default::default()
+        },
+    )
 }
@@ -0,0 +1,110 @@
+//! Measures the cycles each phase of the guests takes (generation, hashing, applying the input,
+//! validation, solving), per guest variant and difficulty, using SP1's execution report.
+//!
+//! The phases are only tracked when the guests are built with the `bench` feature:
+//! ```shell
+//! ZK_SUDOKU_BENCH=1 cargo run --release --bin bench
+//! ```
+//! To compare allocation modes, list the guest features instead of 1:
+//! `ZK_SUDOKU_BENCH=heap-stats,fixed-buffers` also logs the heap in use after every phase and
+//! keeps the allocator out of the verification path.
+//! OpenVM has no equivalent per-phase report, compare whole runs with `cargo openvm run` there.
+
+use clap::Parser;
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::solver::DancingLinks;
+use sudoku_io::{ProgressInput, ProofInput, PuzzleSource, SolvabilityInput, UniquenessInput};
+
+pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+pub const SOLVABILITY_ELF: &[u8] = include_elf!("solvability");
+pub const UNIQUENESS_ELF: &[u8] = include_elf!("uniqueness");
+pub const PROGRESS_ELF: &[u8] = include_elf!("progress");
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Seed of the generated puzzle every variant runs on.
+    #[clap(long, default_value = "666")]
+    seed: u32,
+}
+
+// a guest's whole input, the one value it reads.
+fn stdin<T: serde::Serialize>(input: &T) -> SP1Stdin {
+    let mut stdin = SP1Stdin::new();
+    stdin.write(input);
+    stdin
+}
+
+fn seed_source(seed: u32, difficulty: Difficulty) -> PuzzleSource {
+    PuzzleSource::Seed {
+        seed,
+        difficulty: difficulty as u8,
+    }
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    let args = Args::parse();
+    let client = ProverClient::from_env();
+
+    println!(
+        "{:<12} {:<8} {:<10} {:>12}",
+        "variant", "tier", "phase", "cycles"
+    );
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let puzzle = Board::from_seed(args.seed, Some(difficulty));
+        let solution = DancingLinks::solve_board(&puzzle).expect("generated puzzle is solvable");
+
+        // half of the empty cells filled in, for the progress variant.
+        let mut partial = puzzle.clone();
+        let empty: Vec<usize> = (0..81).filter(|&i| puzzle.cells[i] == 0).collect();
+        for &i in &empty[..empty.len() / 2] {
+            partial.cells[i] = solution.cells[i];
+        }
+
+        let verify = stdin(&ProofInput::new(
+            seed_source(args.seed, difficulty),
+            solution.cells.to_vec(),
+        ));
+        let solvability = stdin(&SolvabilityInput {
+            puzzle: seed_source(args.seed, difficulty),
+            salt: [0; 32],
+        });
+        let uniqueness = stdin(&UniquenessInput {
+            puzzle: seed_source(args.seed, difficulty),
+        });
+        let progress = stdin(&ProgressInput {
+            puzzle: seed_source(args.seed, difficulty),
+            solution: partial.cells.to_vec(),
+        });
+
+        for (variant, elf, stdin) in [
+            ("verify", FIBONACCI_ELF, verify),
+            ("solvability", SOLVABILITY_ELF, solvability),
+            ("uniqueness", UNIQUENESS_ELF, uniqueness),
+            ("progress", PROGRESS_ELF, progress),
+        ] {
+            let (_, report) = client.execute(elf, &stdin).run().unwrap();
+
+            let mut phases: Vec<(&String, &u64)> = report.cycle_tracker.iter().collect();
+            phases.sort();
+            for (phase, cycles) in phases {
+                println!(
+                    "{:<12} {:<8} {:<10} {:>12}",
+                    variant,
+                    format!("{:?}", difficulty),
+                    phase,
+                    cycles
+                );
+            }
+            println!(
+                "{:<12} {:<8} {:<10} {:>12}",
+                variant,
+                format!("{:?}", difficulty),
+                "total",
+                report.total_instruction_count()
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Generates an EVM-compatible (Groth16 or PLONK) proof of the verifier program for a seeded
+//! puzzle and exports what a Solidity contract needs to check it: the SP1 verifier interface,
+//! the public values struct and a JSON fixture with the program's vkey, the public values and
+//! the proof.
+//!
+//! You can run this script using the following command:
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --system groth16
+//! ```
+
+use clap::{Parser, ValueEnum};
+use fibonacci_lib::PublicValuesFixture;
+use sp1_sdk::{include_elf, HashableKey, ProverClient, SP1Stdin};
+use std::path::PathBuf;
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::solver::DancingLinks;
+use sudoku_io::{ProofInput, PublicValues, PuzzleSource, LOGIC_VERSION};
+
+/// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
+pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+
+/// The interface of SP1's deployed verifier gateways, as published in sp1-contracts.
+const SP1_VERIFIER_INTERFACE: &str = r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @title SP1 Verifier Interface
+/// @author Succinct Labs
+/// @notice This contract is the interface for the SP1 Verifier.
+interface ISP1Verifier {
+    /// @notice Verifies a proof with given public values and vkey.
+    /// @dev It is expected that the first 4 bytes of proofBytes must match the first 4 bytes of
+    /// target verifier's VERIFIER_HASH.
+    /// @param programVKey The verification key for the RISC-V program.
+    /// @param publicValues The public values encoded as bytes.
+    /// @param proofBytes The proof of the program execution the SP1 zkVM encoded as bytes.
+    function verifyProof(
+        bytes32 programVKey,
+        bytes calldata publicValues,
+        bytes calldata proofBytes
+    ) external view;
+}
+"#;
+
+/// The arguments for the EVM command.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct EvmArgs {
+    #[clap(long, value_enum, default_value = "groth16")]
+    system: ProofSystem,
+
+    /// Seed of the puzzle the fixture proves a solution for.
+    #[clap(long, default_value = "666")]
+    seed: u32,
+
+    /// EVM address (0x-prefixed hex) committed as the reward recipient.
+    #[clap(long, default_value = "0x0000000000000000000000000000000000000000")]
+    address: String,
+
+    /// Directory the fixture and the Solidity files are written to.
+    #[clap(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/../../contracts/src/fixtures"))]
+    out: PathBuf,
+}
+
+/// Enum representing the available proof systems
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+enum ProofSystem {
+    Plonk,
+    Groth16,
+}
+
+fn main() {
+    // Setup the logger.
+    sp1_sdk::utils::setup_logger();
+    dotenv::dotenv().ok();
+
+    // Parse the command line arguments.
+    let args = EvmArgs::parse();
+
+    let address: [u8; 20] = hex::decode(args.address.trim_start_matches("0x"))
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .expect("expected a 20 byte 0x-prefixed hex address");
+
+    // Setup the prover client.
+    let client = ProverClient::from_env();
+
+    // Setup the program.
+    let (pk, vk) = client.setup(FIBONACCI_ELF);
+
+    // Setup the inputs, submitting the solver's solution of the seeded puzzle.
+    let puzzle = Board::from_seed(args.seed, Some(Difficulty::Medium));
+    let solution = DancingLinks::solve_board(&puzzle).expect("generated puzzle is solvable");
+    let input = ProofInput {
+        salt: rand::random(),
+        reward_address: address,
+        ..ProofInput::new(
+            PuzzleSource::Seed {
+                seed: args.seed,
+                difficulty: Difficulty::Medium as u8,
+            },
+            solution.cells.to_vec(),
+        )
+    };
+    let mut stdin = SP1Stdin::new();
+    for buffer in zk_sudoku_host::stdin::sp1(&input).unwrap() {
+        stdin.write_vec(buffer);
+    }
+
+    println!("Proof System: {:?}", args.system);
+
+    // Generate the proof based on the selected proof system.
+    let proof = match args.system {
+        ProofSystem::Plonk => client.prove(&pk, &stdin).plonk().run(),
+        ProofSystem::Groth16 => client.prove(&pk, &stdin).groth16().run(),
+    }
+    .expect("failed to generate proof");
+
+    create_proof_fixture(&args, &proof, &vk);
+}
+
+/// Create the fixture and the Solidity files a contract is tested against.
+fn create_proof_fixture(
+    args: &EvmArgs,
+    proof: &sp1_sdk::SP1ProofWithPublicValues,
+    vk: &sp1_sdk::SP1VerifyingKey,
+) {
+    // Deserialize the public values.
+    let bytes = proof.public_values.as_slice();
+    let values = PublicValues::abi_decode(bytes).expect("malformed public values");
+    assert_eq!(
+        values.logic_version, LOGIC_VERSION,
+        "guest elf was built from different logic, rebuild it"
+    );
+
+    // Create the testing fixture so we can test things end-to-end.
+    let fixture = PublicValuesFixture::new(&values, vk.bytes32(), &proof.bytes());
+
+    // The verification key is used to verify that the proof corresponds to the execution of the
+    // program on the given input.
+    println!("Verification Key: {}", fixture.vkey);
+
+    // The public values are the values which are publicly committed to by the zkVM.
+    println!("Public Values: {}", fixture.public_values);
+
+    // The proof proves to the verifier that the program was executed with some inputs that led
+    // to the given public values.
+    println!("Proof Bytes: {}", fixture.proof);
+
+    // Save the fixture and the Solidity files next to it.
+    std::fs::create_dir_all(&args.out).expect("failed to create fixture path");
+    let system = format!("{:?}", args.system).to_lowercase();
+    let fixture_path = args.out.join(format!("{}-fixture.json", system));
+    std::fs::write(
+        &fixture_path,
+        serde_json::to_string_pretty(&fixture).unwrap(),
+    )
+    .expect("failed to write fixture");
+    std::fs::write(args.out.join("ISP1Verifier.sol"), SP1_VERIFIER_INTERFACE)
+        .expect("failed to write verifier interface");
+    std::fs::write(
+        args.out.join("PublicValuesStruct.sol"),
+        sudoku_io::sol::PUBLIC_VALUES_STRUCT,
+    )
+    .expect("failed to write public values struct");
+    println!("fixture written to {}", fixture_path.display());
+}
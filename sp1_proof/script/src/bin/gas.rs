@@ -0,0 +1,156 @@
+//! Estimates the gas of verifying the Groth16 and PLONK proofs in the fixtures written by the
+//! `evm` command, to help pick a wrapping. Each proof is checked with `verifyProof` on SP1's
+//! verifier gateway through Foundry's `cast estimate`, against an RPC endpoint that has the
+//! gateway deployed, usually an anvil fork of the target chain (`--fork-url` starts one).
+//! ```shell
+//! RUST_LOG=info cargo run --release --bin evm -- --system groth16
+//! RUST_LOG=info cargo run --release --bin evm -- --system plonk
+//! cargo run --release --bin gas -- --verifier <gateway address> --fork-url <rpc url>
+//! ```
+//! A proof that doesn't verify makes the estimate revert, which is reported as an error.
+
+use clap::Parser;
+use fibonacci_lib::PublicValuesFixture;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Fixtures to estimate, as written by the evm command. Defaults to the groth16 and plonk
+    /// fixtures in contracts/src/fixtures that exist.
+    #[clap(long)]
+    fixture: Vec<PathBuf>,
+
+    /// Address of the SP1 verifier gateway (or a verifier for the proofs' SP1 version).
+    #[clap(long)]
+    verifier: String,
+
+    /// RPC endpoint to estimate against. Ignored with --fork-url.
+    #[clap(long, default_value = "http://127.0.0.1:8545")]
+    rpc_url: String,
+
+    /// Start an anvil fork of this chain and estimate against it instead.
+    #[clap(long)]
+    fork_url: Option<String>,
+
+    /// Port of the anvil fork.
+    #[clap(long, default_value = "8546")]
+    port: u16,
+}
+
+/// The anvil fork started for --fork-url, stopped when dropped.
+struct Anvil(Child);
+
+impl Drop for Anvil {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if let Err(e) = run(&args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let fixtures = if args.fixture.is_empty() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../contracts/src/fixtures");
+        ["groth16", "plonk"]
+            .iter()
+            .map(|system| dir.join(format!("{}-fixture.json", system)))
+            .filter(|path| path.exists())
+            .collect()
+    } else {
+        args.fixture.clone()
+    };
+    if fixtures.is_empty() {
+        return Err("no fixtures, generate them with the evm command or pass --fixture".into());
+    }
+
+    let (_anvil, rpc_url) = match &args.fork_url {
+        Some(fork_url) => {
+            let rpc_url = format!("http://127.0.0.1:{}", args.port);
+            (Some(start_anvil(fork_url, args.port, &rpc_url)?), rpc_url)
+        }
+        None => (None, args.rpc_url.clone()),
+    };
+
+    let mut estimates = Vec::new();
+    for path in &fixtures {
+        let text =
+            std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let fixture: PublicValuesFixture = serde_json::from_str(&text)
+            .map_err(|e| format!("{} is not a fixture ({})", path.display(), e))?;
+        if fixture.proof == "0x" {
+            return Err(format!("{} has no proof to verify", path.display()));
+        }
+        let gas = estimate(&args.verifier, &rpc_url, &fixture)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        // 0x and two hex digits per byte.
+        let proof_len = (fixture.proof.len() - 2) / 2;
+        estimates.push((path.display().to_string(), proof_len, gas));
+    }
+
+    println!("{:<50} {:>12} {:>10}", "fixture", "proof bytes", "gas");
+    for (fixture, proof_len, gas) in &estimates {
+        println!("{:<50} {:>12} {:>10}", fixture, proof_len, gas);
+    }
+    Ok(())
+}
+
+/// The gas `eth_estimateGas` reports for the verifyProof call, calldata included.
+fn estimate(verifier: &str, rpc_url: &str, fixture: &PublicValuesFixture) -> Result<u64, String> {
+    let output = Command::new("cast")
+        .args([
+            "estimate",
+            verifier,
+            "verifyProof(bytes32,bytes,bytes)",
+            fixture.vkey.as_str(),
+            fixture.public_values.as_str(),
+            fixture.proof.as_str(),
+            "--rpc-url",
+            rpc_url,
+        ])
+        .output()
+        .map_err(|e| format!("can't run cast, is Foundry installed? ({})", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "the estimate failed, does the proof verify? ({})",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    let gas = String::from_utf8_lossy(&output.stdout);
+    gas.trim()
+        .parse()
+        .map_err(|_| format!("unexpected cast output '{}'", gas.trim()))
+}
+
+/// Starts anvil forking `fork_url` and waits until it answers.
+fn start_anvil(fork_url: &str, port: u16, rpc_url: &str) -> Result<Anvil, String> {
+    let port = port.to_string();
+    let child = Command::new("anvil")
+        .args(["--fork-url", fork_url, "--port", port.as_str(), "--silent"])
+        .spawn()
+        .map_err(|e| format!("can't run anvil, is Foundry installed? ({})", e))?;
+    let anvil = Anvil(child);
+
+    let start = Instant::now();
+    while start.elapsed() < Duration::from_secs(60) {
+        let ready = Command::new("cast")
+            .args(["chain-id", "--rpc-url", rpc_url])
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+        if ready {
+            return Ok(anvil);
+        }
+        std::thread::sleep(Duration::from_millis(500));
+    }
+    Err(format!("anvil didn't come up on {}", rpc_url))
+}
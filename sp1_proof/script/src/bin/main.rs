@@ -13,15 +13,56 @@
 use alloy_sol_types::SolType;
 use clap::Parser;
 use hex;
-use fibonacci_lib::PublicValuesStruct;
-use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use fibonacci_lib::{vkey_bytes, PublicValuesFixture};
+use sp1_sdk::{
+    include_elf, HashableKey, ProverClient, SP1Proof, SP1ProofWithPublicValues, SP1Stdin,
+};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::commitment::{puzzle_hash, sha256};
+use sudoku::core::solver::DancingLinks;
+use sudoku::core::variant::Variant;
+use sudoku_io::envelope::{Backend, ProofEnvelope, ProvedOn};
+use sudoku_io::sol::{
+    BatchPublicValuesStruct, CheckpointPublicValuesStruct, ProgressPublicValuesStruct,
+    SolvabilityPublicValuesStruct, TranscriptPublicValuesStruct, UniquenessPublicValuesStruct,
+};
+use sudoku_io::{
+    aggregate, givens_hash, replay_moves, verify_solution, BatchInput, BatchSubmission,
+    CheckpointInput, Move, Policy, PreviousCheckpoint, ProgressInput, ProofInput, PublicValues,
+    PuzzleSource, SolvabilityInput, TranscriptInput, UniquenessInput, DIFFICULTY_UNRATED,
+};
+// the host's parsers and checks, so the script and the zk-sudoku binary read the same inputs.
+use zk_sudoku_host::input::{
+    check_inputs, encode_board, parse_address, parse_difficulty, parse_hex, parse_key,
+    parse_nonce, parse_salt, read_board,
+};
+use zk_sudoku_host::print_public_values;
 
 extern crate alloc;
 use alloc::vec::Vec;
+use std::time::{Duration, Instant};
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
 
+/// The ELF of the program that solves the puzzle in the zkVM instead of checking a user's board.
+pub const SOLVABILITY_ELF: &[u8] = include_elf!("solvability");
+
+/// The ELF of the program that proves the puzzle has exactly one solution.
+pub const UNIQUENESS_ELF: &[u8] = include_elf!("uniqueness");
+
+/// The ELF of the program that proves how much of a partially filled board is correct.
+pub const PROGRESS_ELF: &[u8] = include_elf!("progress");
+
+/// The ELF of the program that replays a solving session move by move.
+pub const TRANSCRIPT_ELF: &[u8] = include_elf!("transcript");
+
+/// The ELF of the program that proves a session in links, each one extending the last.
+pub const CHECKPOINT_ELF: &[u8] = include_elf!("checkpoint");
+
+/// The ELF of the program that verifies many submissions in one proof.
+pub const BATCH_ELF: &[u8] = include_elf!("batch");
+
 /// The arguments for the command.
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -32,16 +73,156 @@ struct Args {
     #[clap(long)]
     prove: bool,
 
-    #[clap(long, default_value = "20")]
-    n: u32,
+    /// Wrap the proof in Groth16 so it can be verified on the EVM (needs a lot of memory).
+    #[clap(long, requires = "prove", conflicts_with = "plonk")]
+    groth16: bool,
+
+    /// Wrap the proof in PLONK so it can be verified on the EVM (needs a lot of memory).
+    #[clap(long, requires = "prove")]
+    plonk: bool,
+
+    /// Write the verifier program's public values, field by field and abi encoded, with its
+    /// vkey (and the proof for --groth16/--plonk) to this JSON file for the contracts' Foundry
+    /// tests. The salt defaults to zero instead of a random one, so the fixture is
+    /// deterministic.
+    #[clap(long)]
+    export_fixture: Option<std::path::PathBuf>,
+
+    /// Save the verifier program's proof to this file, along with the backend, the program's
+    /// vkey hash and the public values (sudoku_io::envelope), to verify it later with --proof.
+    #[clap(long, requires = "prove")]
+    output: Option<std::path::PathBuf>,
+
+    /// Verify a proof saved with --output against the verifier program instead of running it,
+    /// and print its public values.
+    #[clap(long, conflicts_with_all = ["execute", "prove"])]
+    proof: Option<std::path::PathBuf>,
+
+    /// Seed the guest regenerates the puzzle from.
+    #[clap(long, default_value = "666")]
+    seed: u32,
+
+    /// Difficulty the guest generates the seeded puzzle with: easy, medium or hard.
+    #[clap(long, default_value = "medium", value_parser = parse_difficulty)]
+    difficulty: Difficulty,
+
+    /// File with the puzzle to use instead of a seeded one: 81 digits (0 or . for empty cells,
+    /// whitespace ignored), a JSON array of 81 cells (or of 9 rows of 9), the 41 byte packed
+    /// encoding or a puzzle id.
+    #[clap(long, value_parser = |arg: &str| read_board(arg.as_ref()))]
+    puzzle: Option<Board>,
+
+    /// Send boards to the guest in the 41 byte packed encoding instead of 81 raw cells.
+    #[clap(long)]
+    packed: bool,
+
+    /// Lowest difficulty tier (easy, medium or hard) the verifier accepts. Supplied puzzles are
+    /// unrated and only pass without one.
+    #[clap(long, value_parser = parse_difficulty)]
+    min_difficulty: Option<Difficulty>,
+
+    /// Most clues the verifier accepts in the puzzle.
+    #[clap(long, default_value = "81")]
+    max_clues: u8,
+
+    /// Prove that the puzzle is solvable (committing the hash of its solution) instead of
+    /// verifying the demo solution.
+    #[clap(long)]
+    solvability: bool,
+
+    /// Salt (32 bytes, 0x-prefixed hex) for the committed solution hash, of the solver's
+    /// solution for --solvability and of the user's board otherwise. A random one is picked and
+    /// printed when it isn't given; keep it to open the commitment later.
+    #[clap(long, value_parser = parse_salt)]
+    salt: Option<[u8; 32]>,
+
+    /// Rules the verifier program checks the board against: classic, x (both diagonals hold
+    /// 1-9 as well), or the 0x-prefixed hex of a killer variant's encoding
+    /// (sudoku::core::variant::Variant::to_bytes).
+    #[clap(long, default_value = "classic", value_parser = parse_variant)]
+    variant: Variant,
+
+    /// Prove that the puzzle has exactly one solution instead of verifying the demo solution.
+    #[clap(long, conflicts_with = "solvability")]
+    uniqueness: bool,
+
+    /// Prove partial progress on the puzzle with the board given by --solution, committing the
+    /// number of correctly filled cells.
+    #[clap(long, conflicts_with_all = ["solvability", "uniqueness"])]
+    progress: bool,
+
+    /// Replay a solving session against the puzzle and prove every move was legal and the
+    /// board ends up solved. Moves are comma separated cell=value pairs, cells numbered 0-80
+    /// row by row and value 0 erasing the cell, e.g. 2=4,3=7,3=0,3=1.
+    #[clap(long, conflicts_with_all = ["solvability", "uniqueness", "progress"], value_parser = parse_moves)]
+    transcript: Option<Vec<Move>>,
+
+    /// Prove the --transcript in links of this many moves instead of at once, each link
+    /// verifying the previous link's proof in the guest. With --execute only the first link
+    /// runs, the later ones need a real proof of the link before them.
+    #[clap(long, requires = "transcript")]
+    checkpoint: Option<usize>,
+
+    /// Verify a batch of this many seeded puzzles (--seed, --seed + 1, ...) in one proof,
+    /// submitting the solver's solution for each.
+    #[clap(
+        long,
+        conflicts_with_all = ["solvability", "uniqueness", "progress", "transcript", "puzzle", "solution"]
+    )]
+    batch: Option<u32>,
+
+    /// File with the user's board, in any of the --puzzle formats. It may have empty cells with
+    /// --progress only. Defaults to the solver's solution of the puzzle.
+    #[clap(long, value_parser = |arg: &str| read_board(arg.as_ref()))]
+    solution: Option<Board>,
+
+    /// Epoch (e.g. tournament round or date) the guest echoes into the public values.
+    #[clap(long, default_value = "0")]
+    epoch: u64,
+
+    /// Nonce (32 bytes, 0x-prefixed hex) the guest echoes into the public values, to bind the
+    /// proof to the request it answers. All zeros when it isn't given.
+    #[clap(long, value_parser = parse_nonce)]
+    nonce: Option<[u8; 32]>,
+
+    /// EVM address (0x-prefixed hex) committed in the proof as the reward recipient.
+    #[clap(long, default_value = "0x0000000000000000000000000000000000000000", value_parser = parse_address)]
+    address: [u8; 20],
+
+    /// Ed25519 key (32 bytes, 0x-prefixed hex) of the organizer that issued the puzzle. The
+    /// guest checks --signature against it and commits the key.
+    #[clap(long, requires = "signature", value_parser = parse_key)]
+    organizer_key: Option<[u8; 32]>,
+
+    /// The organizer's ed25519 signature (64 bytes, 0x-prefixed hex) over the puzzle hash.
+    #[clap(long, requires = "organizer_key", value_parser = parse_hex)]
+    signature: Option<Vec<u8>>,
 
     // #[clap(value_parser = parse_hex)]
     // hex_input: Vec<u8>,
 }
-fn parse_hex(arg: &str) -> Result<Vec<u8>, hex::FromHexError> {
-    let cleaned = arg.trim_start_matches("0x")  // Remove optional 0x prefix
-        .replace(" ", "");                      // Remove any whitespace
-    hex::decode(cleaned)
+fn parse_variant(arg: &str) -> Result<Variant, String> {
+    match arg {
+        "classic" => Ok(Variant::Classic),
+        "x" => Ok(Variant::X),
+        _ => {
+            let bytes = parse_hex(arg)?;
+            Variant::from_bytes(&bytes).map_err(|e| e.to_string())
+        }
+    }
+}
+fn parse_moves(arg: &str) -> Result<Vec<Move>, String> {
+    arg.split(',')
+        .map(|m| {
+            let (cell, value) = m
+                .split_once('=')
+                .ok_or_else(|| format!("expected cell=value, got '{}'", m))?;
+            Ok(Move {
+                cell: cell.trim().parse().map_err(|_| format!("bad cell in '{}'", m))?,
+                value: value.trim().parse().map_err(|_| format!("bad value in '{}'", m))?,
+            })
+        })
+        .collect()
 }
 
 fn main() {
@@ -52,6 +233,11 @@ fn main() {
     // Parse the command line arguments.
     let args = Args::parse();
 
+    if let Some(path) = &args.proof {
+        verify_saved_proof(path);
+        return;
+    }
+
     if args.execute == args.prove {
         eprintln!("Error: You must specify either --execute or --prove");
         std::process::exit(1);
@@ -60,19 +246,132 @@ fn main() {
     // Setup the prover client.
     let client = ProverClient::from_env();
 
+    if let Some(count) = args.batch {
+        run_batch(&client, &args, count);
+        return;
+    }
+
     // Setup the inputs.
+    let source = match &args.puzzle {
+        Some(puzzle) => PuzzleSource::Puzzle {
+            cells: encode_board(puzzle, args.packed),
+            expected_hash: puzzle_hash(puzzle),
+        },
+        None => PuzzleSource::Seed {
+            seed: args.seed,
+            difficulty: args.difficulty as u8,
+        },
+    };
     let mut stdin = SP1Stdin::new();
-    stdin.write(&args.n);
 
-    let user_input: Vec<u8> = vec![
-        7, 5, 3, 8, 2, 1, 6, 9, 4, 1, 2, 4, 3, 6, 9, 5, 7, 8, 6, 8, 9, 4, 5, 7, 1, 2, 3, 2, 9, 1,
-        5, 7, 3, 8, 4, 6, 8, 4, 7, 2, 1, 6, 9, 3, 5, 5, 3, 6, 9, 4, 8, 2, 1, 7, 3, 7, 2, 1, 8, 5,
-        4, 6, 9, 4, 6, 5, 7, 9, 2, 3, 8, 1, 9, 1, 8, 6, 3, 4, 7, 5, 2,
-    ];
+    if args.solvability {
+        let salt = args.salt.unwrap_or_else(rand::random);
+        println!("salt: 0x{}", hex::encode(salt));
+        stdin.write(&SolvabilityInput {
+            puzzle: source,
+            salt,
+        });
+        run_puzzle_program(&client, SOLVABILITY_ELF, &stdin, args.execute, |output| {
+            let decoded = SolvabilityPublicValuesStruct::abi_decode(output, true).unwrap();
+            println!("puzzle hash: {}", decoded.puzzleHash);
+            println!("solvable: {}", decoded.solvable);
+            println!("solution hash: {}", decoded.solutionHash);
+        });
+        return;
+    }
+    if args.uniqueness {
+        stdin.write(&UniquenessInput { puzzle: source });
+        run_puzzle_program(&client, UNIQUENESS_ELF, &stdin, args.execute, |output| {
+            let decoded = UniquenessPublicValuesStruct::abi_decode(output, true).unwrap();
+            println!("puzzle hash: {}", decoded.puzzleHash);
+            println!("unique: {}", decoded.unique);
+        });
+        return;
+    }
+
+    if let (Some(moves), Some(size)) = (&args.transcript, args.checkpoint) {
+        run_checkpoints(&client, &args, source, moves, size);
+        return;
+    }
+    if let Some(moves) = &args.transcript {
+        stdin.write(&TranscriptInput {
+            puzzle: source,
+            moves: moves.clone(),
+        });
+        run_puzzle_program(&client, TRANSCRIPT_ELF, &stdin, args.execute, |output| {
+            let decoded = TranscriptPublicValuesStruct::abi_decode(output, true).unwrap();
+            println!("puzzle hash: {}", decoded.puzzleHash);
+            println!("solved with legal moves: {}", decoded.valid);
+            println!("moves: {}", decoded.moveCount);
+            if decoded.illegalMove < decoded.moveCount {
+                println!("first illegal move: {}", decoded.illegalMove);
+            }
+            println!("transcript hash: {}", decoded.transcriptHash);
+            println!("status: {}", decoded.status);
+        });
+        return;
+    }
+
+    let puzzle = args
+        .puzzle
+        .clone()
+        .unwrap_or_else(|| Board::from_seed(args.seed, Some(args.difficulty)));
+    let user_input = match &args.solution {
+        Some(board) => board.clone(),
+        None => DancingLinks::solve_board(&puzzle).unwrap_or_else(|e| {
+            eprintln!("Error: can't solve the puzzle ({}), pass a board with --solution", e);
+            std::process::exit(1);
+        }),
+    };
+    if let Err(e) = check_inputs(&puzzle, &user_input, args.progress) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
 
-    stdin.write(&user_input);
+    if args.progress {
+        stdin.write(&ProgressInput {
+            puzzle: source,
+            solution: encode_board(&user_input, args.packed),
+        });
+        run_puzzle_program(&client, PROGRESS_ELF, &stdin, args.execute, |output| {
+            let decoded = ProgressPublicValuesStruct::abi_decode(output, true).unwrap();
+            println!("puzzle hash: {}", decoded.puzzleHash);
+            println!("consistent: {}", decoded.consistent);
+            println!(
+                "filled: {} of {} empty cells",
+                decoded.filled,
+                81 - decoded.clueCount
+            );
+            println!("status: {}", decoded.status);
+        });
+        return;
+    }
 
-    println!("n: {}", args.n);
+    let policy = Policy {
+        min_difficulty: args.min_difficulty.map_or(DIFFICULTY_UNRATED, |d| d as u8),
+        max_clues: args.max_clues,
+    };
+    let salt = match args.salt {
+        Some(salt) => salt,
+        None if args.export_fixture.is_some() => [0; 32],
+        None => rand::random(),
+    };
+    println!("salt: 0x{}", hex::encode(salt));
+    let input = ProofInput {
+        puzzle: source,
+        solution: encode_board(&user_input, args.packed),
+        salt,
+        reward_address: args.address,
+        epoch: args.epoch,
+        policy,
+        organizer: args.organizer_key.zip(args.signature),
+        variant: args.variant.to_bytes(),
+        nonce: args.nonce.unwrap_or_default(),
+    };
+    // the same encoding the host and the evm command send, see zk_sudoku_host::stdin.
+    for buffer in zk_sudoku_host::stdin::sp1(&input).unwrap() {
+        stdin.write_vec(buffer);
+    }
 
     if args.execute {
         // Execute the program
@@ -80,26 +379,347 @@ fn main() {
         println!("Program executed successfully.");
 
         // Read the output.
-        let decoded = PublicValuesStruct::abi_decode(output.as_slice(), true).unwrap();
-        let PublicValuesStruct { valid } = decoded;
-        println!("valid: {}", valid);
+        let values = PublicValues::abi_decode(output.as_slice()).unwrap();
+        check_logic_version(&values);
+        print_public_values(&values);
+        assert_eq!(values.policy_hash, policy.hash(), "guest committed a different policy");
+        assert_eq!(
+            values.givens_hash,
+            givens_hash(&puzzle),
+            "guest checked the board against other givens"
+        );
+
+        if let Some(path) = &args.export_fixture {
+            let (_, vk) = client.setup(FIBONACCI_ELF);
+            export_fixture(path, &values, &vk, &[]);
+        }
 
         // Record the number of cycles executed.
         println!("Number of cycles: {}", report.total_instruction_count());
     } else {
+        let mut timings = Timings::default();
+
+        // Refuse an ELF built from different logic before spending time on a proof for it.
+        let (output, report) = timings.time("execute", || {
+            client.execute(FIBONACCI_ELF, &stdin).run().unwrap()
+        });
+        check_logic_version(&PublicValues::abi_decode(output.as_slice()).unwrap());
+        println!("Number of cycles: {}", report.total_instruction_count());
+
         // Setup the program for proving.
-        let (pk, vk) = client.setup(FIBONACCI_ELF);
+        let (pk, vk) = timings.time("setup", || client.setup(FIBONACCI_ELF));
 
-        // Generate the proof
-        let proof = client
-            .prove(&pk, &stdin)
-            .run()
-            .expect("failed to generate proof");
+        // Generate the proof, wrapped for the EVM if asked to.
+        let builder = client.prove(&pk, &stdin);
+        let builder = if args.groth16 {
+            builder.groth16()
+        } else if args.plonk {
+            builder.plonk()
+        } else {
+            builder
+        };
+        let proof = timings.time("prove", || builder.run().expect("failed to generate proof"));
 
         println!("Successfully generated proof!");
+        print_shards(&proof.proof);
 
         // Verify the proof.
-        client.verify(&proof, &vk).expect("failed to verify proof");
+        timings.time("verify", || {
+            client.verify(&proof, &vk).expect("failed to verify proof")
+        });
+        println!("Successfully verified proof!");
+        timings.report();
+
+        // What an on-chain verifier needs: the program's vkey hash, the committed public values
+        // and the wrapped proof bytes.
+        if args.groth16 || args.plonk {
+            println!("vkey hash: {}", vk.bytes32());
+            println!("public values: 0x{}", hex::encode(proof.public_values.as_slice()));
+            println!("proof: 0x{}", hex::encode(proof.bytes()));
+        }
+        if let Some(path) = &args.export_fixture {
+            let values = PublicValues::abi_decode(proof.public_values.as_slice()).unwrap();
+            let proof_bytes = if args.groth16 || args.plonk { proof.bytes() } else { Vec::new() };
+            export_fixture(path, &values, &vk, &proof_bytes);
+        }
+        if let Some(path) = &args.output {
+            let envelope = ProofEnvelope {
+                backend: Backend::Sp1,
+                proved_on: proved_on(),
+                vkey_hash: vkey_hash(&vk),
+                program_digest: sha256(FIBONACCI_ELF).to_vec(),
+                public_values: proof.public_values.to_vec(),
+                proof: bincode::serialize(&proof).expect("failed to serialize proof"),
+            };
+            std::fs::write(path, envelope.encode()).expect("failed to save proof");
+            println!("vkey hash: {}", vk.bytes32());
+            println!("program digest: 0x{}", hex::encode(&envelope.program_digest));
+            println!("proof saved to {}", path.display());
+        }
+    }
+}
+
+/// Where `ProverClient::from_env` proves, which `SP1_PROVER` picks.
+fn proved_on() -> ProvedOn {
+    match std::env::var("SP1_PROVER").as_deref() {
+        Ok("cuda") => ProvedOn::Cuda,
+        Ok("network") => ProvedOn::Network,
+        _ => ProvedOn::Cpu,
+    }
+}
+
+// the 32 bytes of the program's vkey hash, as a contract gets it from `HashableKey::bytes32`.
+fn vkey_hash(vk: &sp1_sdk::SP1VerifyingKey) -> Vec<u8> {
+    hex::decode(vk.bytes32().trim_start_matches("0x")).unwrap()
+}
+
+// load a proof saved with --output and verify it against the verifier program built into this
+// script. a proof of another program, or of this one built from other logic, is refused before
+// it's verified.
+fn verify_saved_proof(path: &std::path::Path) {
+    let bytes = std::fs::read(path).expect("failed to read proof");
+    let envelope = ProofEnvelope::decode(&bytes).unwrap_or_else(|e| {
+        eprintln!("Error: {} is not a saved proof ({:?})", path.display(), e);
+        std::process::exit(1);
+    });
+    if envelope.backend != Backend::Sp1 {
+        eprintln!("Error: the proof was made with {:?}, not SP1", envelope.backend);
+        std::process::exit(1);
+    }
+
+    if envelope.program_digest != sha256(FIBONACCI_ELF) {
+        eprintln!(
+            "Error: the proof is for program digest 0x{}, this script runs 0x{}",
+            hex::encode(&envelope.program_digest),
+            hex::encode(sha256(FIBONACCI_ELF))
+        );
+        std::process::exit(1);
+    }
+
+    let client = ProverClient::from_env();
+    let (_, vk) = client.setup(FIBONACCI_ELF);
+    if envelope.vkey_hash != vkey_hash(&vk) {
+        eprintln!(
+            "Error: the proof is for program 0x{}, this script verifies {}",
+            hex::encode(&envelope.vkey_hash),
+            vk.bytes32()
+        );
+        std::process::exit(1);
+    }
+
+    let proof: SP1ProofWithPublicValues =
+        bincode::deserialize(&envelope.proof).expect("malformed proof");
+    if proof.public_values.as_slice() != envelope.public_values {
+        eprintln!("Error: the saved public values don't match the proof's");
+        std::process::exit(1);
+    }
+    client.verify(&proof, &vk).expect("failed to verify proof");
+    println!("Successfully verified proof!");
+
+    let values =
+        PublicValues::abi_decode(&envelope.public_values).expect("malformed public values");
+    check_logic_version(&values);
+    print_public_values(&values);
+}
+
+// The verifier program commits the sudoku_io::LOGIC_VERSION it was built with. A proof from an
+// ELF built from other generation or validation logic doesn't prove what this script checks
+// for, so it stops there and asks for a rebuild.
+fn check_logic_version(values: &PublicValues) {
+    if let Err(e) = zk_sudoku_host::check_logic_version(values) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+// write the fixture the contracts' tests decode the public values against.
+fn export_fixture(
+    path: &std::path::Path,
+    values: &PublicValues,
+    vk: &sp1_sdk::SP1VerifyingKey,
+    proof: &[u8],
+) {
+    let fixture = PublicValuesFixture::new(values, vk.bytes32(), proof);
+    let json = serde_json::to_string_pretty(&fixture).unwrap();
+    std::fs::write(path, json).expect("failed to write fixture");
+    println!("fixture written to {}", path.display());
+}
+
+// the solvability, uniqueness, progress and batch programs only read puzzles (and boards, for
+// progress and batch), the rest of the inputs don't apply. `print_output` decodes the committed public
+// values.
+fn run_puzzle_program(
+    client: &sp1_sdk::EnvProver,
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    execute: bool,
+    print_output: impl Fn(&[u8]),
+) {
+    if execute {
+        let (output, report) = client.execute(elf, stdin).run().unwrap();
+        println!("Program executed successfully.");
+
+        print_output(output.as_slice());
+        println!("Number of cycles: {}", report.total_instruction_count());
+    } else {
+        let mut timings = Timings::default();
+        let (pk, vk) = timings.time("setup", || client.setup(elf));
+        let proof = timings.time("prove", || {
+            client
+                .prove(&pk, stdin)
+                .run()
+                .expect("failed to generate proof")
+        });
+        println!("Successfully generated proof!");
+        print_shards(&proof.proof);
+
+        timings.time("verify", || {
+            client.verify(&proof, &vk).expect("failed to verify proof")
+        });
         println!("Successfully verified proof!");
+        timings.report();
+    }
+}
+
+// wall clock time of each stage of a run, printed as the stages finish and summed up at the
+// end, to see where a long proving run spends its time. SP1 generates the traces shard by shard
+// while proving, so that time is part of "prove", set SHARD_SIZE (cycles per shard) to trade
+// shard count against memory.
+#[derive(Default)]
+struct Timings(Vec<(&'static str, Duration)>);
+
+impl Timings {
+    fn time<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        println!("{}...", stage);
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        println!("{} took {:.2?}", stage, elapsed);
+        self.0.push((stage, elapsed));
+        result
+    }
+
+    fn report(&self) {
+        for (stage, elapsed) in &self.0 {
+            println!("{:<10} {:>12.2?}", stage, elapsed);
+        }
+        let total: Duration = self.0.iter().map(|(_, elapsed)| *elapsed).sum();
+        println!("{:<10} {:>12.2?}", "total", total);
+    }
+}
+
+// core proofs have one proof per shard, the compressed and wrapped ones fold them into one.
+fn print_shards(proof: &SP1Proof) {
+    match proof {
+        SP1Proof::Core(shards) => println!("shards: {}", shards.len()),
+        SP1Proof::Compressed(_) => println!("compressed proof"),
+        SP1Proof::Plonk(_) => println!("plonk proof"),
+        SP1Proof::Groth16(_) => println!("groth16 proof"),
+    }
+}
+
+// prove the session in links of `size` moves. every link after the first gets the previous
+// link's compressed proof, its public values and the (private) board it committed to, which the
+// host tracks by replaying the moves the same way the guest does.
+fn run_checkpoints(
+    client: &sp1_sdk::EnvProver,
+    args: &Args,
+    source: PuzzleSource,
+    moves: &[Move],
+    size: usize,
+) {
+    let (puzzle, _, _) = source.load().expect("invalid puzzle");
+    let salt = args.salt.unwrap_or_else(rand::random);
+    println!("salt: 0x{}", hex::encode(salt));
+
+    let (pk, vk) = client.setup(CHECKPOINT_ELF);
+    let vkey = vk.hash_u32();
+    let mut board = puzzle.clone();
+    let mut legal = true;
+    let mut previous: Option<(Vec<u8>, Vec<u8>, SP1Proof)> = None;
+
+    for (link, chunk) in moves.chunks(size.max(1)).enumerate() {
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&CheckpointInput {
+            puzzle: source.clone(),
+            salt,
+            moves: chunk.to_vec(),
+            previous: previous
+                .as_ref()
+                .map(|(public_values, board, _)| PreviousCheckpoint {
+                    vkey,
+                    public_values: public_values.clone(),
+                    board: board.clone(),
+                }),
+        });
+        if let Some((_, _, SP1Proof::Compressed(proof))) = &previous {
+            stdin.write_proof(*proof.clone(), vk.vk.clone());
+        }
+        // the guest stops replaying at the first illegal move, and so does the board it commits.
+        legal = legal && replay_moves(&puzzle, &mut board, chunk).is_none();
+
+        let public_values = if args.execute {
+            let (output, report) = client.execute(CHECKPOINT_ELF, &stdin).run().unwrap();
+            println!("Number of cycles: {}", report.total_instruction_count());
+            output.to_vec()
+        } else {
+            let proof = client
+                .prove(&pk, &stdin)
+                .compressed()
+                .run()
+                .expect("failed to generate proof");
+            client.verify(&proof, &vk).expect("failed to verify proof");
+            let public_values = proof.public_values.to_vec();
+            let committed_board = encode_board(&board, args.packed);
+            previous = Some((public_values.clone(), committed_board, proof.proof));
+            public_values
+        };
+
+        let decoded = CheckpointPublicValuesStruct::abi_decode(&public_values, true).unwrap();
+        println!(
+            "link {}: {} moves, legal: {}, solved: {}, status: {}",
+            link, decoded.moveCount, decoded.legal, decoded.solved, decoded.status
+        );
+        assert_eq!(decoded.legal, legal, "guest and host replayed the moves differently");
+        if link > 0 {
+            assert_eq!(decoded.previousVkey, vkey_bytes(&vkey), "chain verified another program");
+        }
+        if args.execute {
+            println!("Stopping after the first link, the next one needs its proof (--prove).");
+            break;
+        }
     }
 }
+
+// write a batch of seeded puzzles with their solutions, and check the committed results root
+// against the one computed from the same submissions on the host.
+fn run_batch(client: &sp1_sdk::EnvProver, args: &Args, count: u32) {
+    let mut submissions = Vec::new();
+    let mut expected = Vec::new();
+    for seed in args.seed..args.seed + count {
+        let puzzle = Board::from_seed(seed, Some(args.difficulty));
+        let solution = DancingLinks::solve_board(&puzzle).expect("generated puzzle is solvable");
+        let user_input = encode_board(&solution, args.packed);
+
+        expected.push(
+            verify_solution(puzzle, args.difficulty as u8, &user_input, &[0; 32]).unwrap(),
+        );
+        submissions.push(BatchSubmission {
+            puzzle: PuzzleSource::Seed {
+                seed,
+                difficulty: args.difficulty as u8,
+            },
+            solution: user_input,
+        });
+    }
+    let mut stdin = SP1Stdin::new();
+    stdin.write(&BatchInput { submissions });
+    let expected_root = aggregate(&expected).results_root;
+
+    run_puzzle_program(client, BATCH_ELF, &stdin, args.execute, |output| {
+        let decoded = BatchPublicValuesStruct::abi_decode(output, true).unwrap();
+        println!("{} of {} submissions valid", decoded.validCount, decoded.count);
+        println!("results root: {}", decoded.resultsRoot);
+        assert_eq!(decoded.resultsRoot, expected_root, "results root mismatch");
+    });
+}
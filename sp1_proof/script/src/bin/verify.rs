@@ -0,0 +1,118 @@
+//! Verifies a proof saved with `--output` against a verifying key saved with `vkey --output`,
+//! and prints the public values it commits. Needs neither the program nor its inputs, so anyone
+//! handed the two files can check the proof:
+//! ```shell
+//! cargo run --release --bin vkey -- --output vk.bin
+//! cargo run --release --bin verify -- --proof proof.bin --vk vk.bin
+//! ```
+//! Exits nonzero if the proof doesn't verify.
+
+use clap::Parser;
+use sp1_sdk::{HashableKey, ProverClient, SP1ProofWithPublicValues, SP1VerifyingKey};
+use sudoku_io::envelope::{Backend, ProofEnvelope};
+use sudoku_io::{PublicValues, LOGIC_VERSION};
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// The proof, as saved by the main script's --output.
+    #[clap(long)]
+    proof: std::path::PathBuf,
+
+    /// The verifier program's verifying key, as saved by the vkey command's --output.
+    #[clap(long)]
+    vk: std::path::PathBuf,
+
+    /// Only accept proofs of the program with this digest (0x-prefixed hex, printed by the vkey
+    /// command). The verifying key already pins the program, this also pins its ELF.
+    #[clap(long)]
+    program_digest: Option<String>,
+}
+
+fn main() {
+    sp1_sdk::utils::setup_logger();
+    let args = Args::parse();
+
+    if let Err(e) = verify(&args) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn verify(args: &Args) -> Result<(), String> {
+    let read = |path: &std::path::Path| {
+        std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))
+    };
+    let envelope = ProofEnvelope::decode(&read(&args.proof)?)
+        .map_err(|e| format!("{} is not a saved proof ({:?})", args.proof.display(), e))?;
+    if envelope.backend != Backend::Sp1 {
+        return Err(format!(
+            "the proof was made with {:?}, not SP1",
+            envelope.backend
+        ));
+    }
+    let program_digest = format!("0x{}", hex::encode(&envelope.program_digest));
+    if let Some(expected) = &args.program_digest {
+        if !expected.eq_ignore_ascii_case(&program_digest) {
+            return Err(format!(
+                "the proof is for program digest {}, expected {}",
+                program_digest, expected
+            ));
+        }
+    }
+    let vk: SP1VerifyingKey = bincode::deserialize(&read(&args.vk)?)
+        .map_err(|e| format!("{} is not a verifying key ({})", args.vk.display(), e))?;
+
+    let vkey_hash = vk.bytes32();
+    if hex::encode(&envelope.vkey_hash) != vkey_hash.trim_start_matches("0x") {
+        return Err(format!(
+            "the proof is for program 0x{}, the key is {}",
+            hex::encode(&envelope.vkey_hash),
+            vkey_hash
+        ));
+    }
+
+    let proof: SP1ProofWithPublicValues =
+        bincode::deserialize(&envelope.proof).map_err(|e| format!("malformed proof ({})", e))?;
+    if proof.public_values.as_slice() != envelope.public_values {
+        return Err("the saved public values don't match the proof's".into());
+    }
+    let client = ProverClient::from_env();
+    client
+        .verify(&proof, &vk)
+        .map_err(|e| format!("the proof doesn't verify ({})", e))?;
+    println!("Successfully verified proof for program {}!", vkey_hash);
+    println!("program digest: {}", program_digest);
+
+    let values = PublicValues::abi_decode(&envelope.public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    if values.logic_version != LOGIC_VERSION {
+        println!(
+            "warning: the program was built with logic version {}, this build has {}",
+            values.logic_version, LOGIC_VERSION
+        );
+    }
+    println!("puzzle hash: 0x{}", hex::encode(values.puzzle_hash));
+    println!(
+        "solution commitment: 0x{}",
+        hex::encode(values.solution_commitment)
+    );
+    println!("valid: {}", values.valid);
+    println!(
+        "difficulty: {}, clues: {}",
+        values.difficulty, values.clue_count
+    );
+    println!("seed: {}", values.seed);
+    println!("nullifier: 0x{}", hex::encode(values.nullifier));
+    println!("epoch: {}", values.epoch);
+    println!("reward address: 0x{}", hex::encode(values.reward_address));
+    println!("status: {}", values.status);
+    println!("policy hash: 0x{}", hex::encode(values.policy_hash));
+    println!("organizer key: 0x{}", hex::encode(values.organizer_key));
+    println!("variant: {}", values.variant);
+    println!("logic version: {}", values.logic_version);
+    println!("cells root: 0x{}", hex::encode(values.cells_root));
+    println!("givens hash: 0x{}", hex::encode(values.givens_hash));
+    println!("nonce: 0x{}", hex::encode(values.nonce));
+    Ok(())
+}
@@ -1,10 +1,28 @@
+use clap::Parser;
 use sp1_sdk::{include_elf, HashableKey, Prover, ProverClient};
+use sudoku::core::commitment::sha256;
 
 /// The ELF (executable and linkable format) file for the Succinct RISC-V zkVM.
 pub const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
 
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Also save the verifying key to this file, for the verify command.
+    #[clap(long)]
+    output: Option<std::path::PathBuf>,
+}
+
 fn main() {
+    let args = Args::parse();
     let prover = ProverClient::builder().cpu().build();
     let (_, vk) = prover.setup(FIBONACCI_ELF);
-    println!("{}", vk.bytes32());
-}
\ No newline at end of file
+    // the two values a verifier pins the program with, see sudoku_io::envelope.
+    println!("vkey hash: {}", vk.bytes32());
+    println!("program digest: 0x{}", hex::encode(sha256(FIBONACCI_ELF)));
+
+    if let Some(path) = &args.output {
+        let bytes = bincode::serialize(&vk).expect("failed to serialize verifying key");
+        std::fs::write(path, bytes).expect("failed to save verifying key");
+    }
+}
@@ -0,0 +1,59 @@
+//! Host/guest parity: the host and the guest have to generate the same puzzle from a seed,
+//! otherwise the puzzle hash a proof commits won't match the one the host (or a contract)
+//! expects. Executes the verifier program, so it needs the SP1 toolchain:
+//! ```shell
+//! cargo test --release --test parity
+//! ```
+
+use sp1_sdk::{include_elf, ProverClient, SP1Stdin};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::solver::DancingLinks;
+use sudoku_io::{ProofInput, PublicValues, PuzzleSource, LOGIC_VERSION, STATUS_OK};
+
+const FIBONACCI_ELF: &[u8] = include_elf!("fibonacci-program");
+
+#[test]
+fn test_guest_regenerates_host_puzzle() {
+    let client = ProverClient::from_env();
+
+    for (seed, difficulty) in [
+        (666, Difficulty::Medium),
+        (2200, Difficulty::Easy),
+        (42, Difficulty::Hard),
+        (u32::MAX, Difficulty::Medium),
+    ] {
+        let puzzle = Board::from_seed(seed, Some(difficulty));
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+
+        let input = ProofInput::new(
+            PuzzleSource::Seed {
+                seed,
+                difficulty: difficulty as u8,
+            },
+            solution.cells.to_vec(),
+        );
+        let mut stdin = SP1Stdin::new();
+        for buffer in zk_sudoku_host::stdin::sp1(&input).unwrap() {
+            stdin.write_vec(buffer);
+        }
+
+        let (output, _) = client.execute(FIBONACCI_ELF, &stdin).run().unwrap();
+        let values = PublicValues::abi_decode(output.as_slice()).unwrap();
+
+        assert_eq!(
+            values.logic_version, LOGIC_VERSION,
+            "guest elf was built from different logic, rebuild it"
+        );
+        assert_eq!(
+            values.puzzle_hash,
+            puzzle_hash(&puzzle),
+            "seed {} generated a different puzzle in the guest",
+            seed
+        );
+        assert_eq!(values.clue_count, puzzle.clue_count());
+        assert_eq!(values.seed, seed);
+        assert_eq!(values.status, STATUS_OK);
+        assert!(values.valid);
+    }
+}
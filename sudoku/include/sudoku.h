@@ -0,0 +1,41 @@
+/* the C interface of the sudoku crate's `ffi` feature, see src/ffi.rs.
+ *
+ * boards are 81 bytes, row-wise, 0 for empty. difficulties are 1 (easy), 2 (medium) or 3 (hard).
+ * the functions return SUDOKU_OK or a negative code. */
+#ifndef SUDOKU_H
+#define SUDOKU_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define SUDOKU_OK 0
+#define SUDOKU_NULL -1
+#define SUDOKU_BAD_DIFFICULTY -2
+#define SUDOKU_BAD_BOARD -3
+#define SUDOKU_UNSOLVABLE -4
+#define SUDOKU_BAD_MOVES -5
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* the puzzle the guests regenerate from the seed, into out[81]. */
+int32_t sudoku_generate(uint32_t seed, uint8_t difficulty, uint8_t *out);
+
+/* the dancing links solution of puzzle[81], into out[81]. */
+int32_t sudoku_solve(const uint8_t *puzzle, uint8_t *out);
+
+/* 1 when board[81] is complete and follows the rules, 0 when it isn't. */
+int32_t sudoku_validate(const uint8_t *board);
+
+/* plays count moves, two bytes each (cell 0-80, value 0-9, 0 erases), on board[81] up to the
+ * first illegal one: a given of puzzle[81] changed, or a value repeated in its row, column or
+ * box. returns how many were played. */
+int32_t sudoku_apply_moves(const uint8_t *puzzle, uint8_t *board, const uint8_t *moves,
+                           size_t count);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
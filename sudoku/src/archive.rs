@@ -0,0 +1,182 @@
+// rkyv archives of generated puzzles, for datasets of millions that are read far more often
+// than they're written. an archive is used in place: memory-map the file (or read it into an
+// aligned buffer), `PuzzleArchive::access` checks it once, and lookups read straight from the
+// bytes without deserializing anything.
+//
+//     let archive = PuzzleArchive::new(records);
+//     std::fs::write("puzzles.rkyv", archive.to_bytes())?;
+//     ...
+//     let mmap = unsafe { memmap2::Mmap::map(&file)? };
+//     let archived = PuzzleArchive::access(&mmap)?;
+//     let puzzle = archived.find(42, Difficulty::Hard).map(|record| record.puzzle());
+//
+// boards are stored packed (`Board::to_packed`), records are sorted by seed and difficulty so
+// `find` is a binary search. `sudoku generate --archive FILE` writes one.
+extern crate alloc;
+use alloc::vec::Vec;
+
+use rkyv::rancor;
+use rkyv::util::AlignedVec;
+use rkyv::{Archive, Deserialize, Serialize};
+
+use crate::core::board::{Board, Difficulty, PACKED_LEN};
+
+// bumped when a record changes, archives of another version are refused.
+pub const ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    // not an archive, or a corrupted one: rkyv's check of the bytes failed.
+    Invalid(rancor::Error),
+    UnsupportedVersion(u32),
+}
+
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleRecord {
+    pub seed: u32,
+    // the `Difficulty` discriminant.
+    pub difficulty: u8,
+    pub clue_count: u8,
+    pub puzzle: [u8; PACKED_LEN],
+    pub solution: [u8; PACKED_LEN],
+}
+
+#[derive(Archive, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleArchive {
+    pub version: u32,
+    pub puzzles: Vec<PuzzleRecord>,
+}
+
+impl PuzzleRecord {
+    pub fn new(seed: u32, difficulty: Difficulty, puzzle: &Board, solution: &Board) -> Self {
+        PuzzleRecord {
+            seed,
+            difficulty: difficulty as u8,
+            clue_count: puzzle.clue_count(),
+            puzzle: puzzle.to_packed(),
+            solution: solution.to_packed(),
+        }
+    }
+}
+
+impl ArchivedPuzzleRecord {
+    // access has checked the archive, but not that its boards unpack, a record from anything
+    // but PuzzleRecord::new may not.
+    pub fn puzzle(&self) -> Option<Board> {
+        Board::from_packed(&self.puzzle).ok()
+    }
+
+    pub fn solution(&self) -> Option<Board> {
+        Board::from_packed(&self.solution).ok()
+    }
+
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        Difficulty::from_u8(self.difficulty)
+    }
+}
+
+impl PuzzleArchive {
+    pub fn new(mut puzzles: Vec<PuzzleRecord>) -> Self {
+        puzzles.sort_by_key(|record| (record.seed, record.difficulty));
+        PuzzleArchive {
+            version: ARCHIVE_VERSION,
+            puzzles,
+        }
+    }
+
+    pub fn to_bytes(&self) -> AlignedVec {
+        // serializing into memory only fails on allocation.
+        rkyv::to_bytes::<rancor::Error>(self).unwrap()
+    }
+
+    // the archive in `bytes`, which have to be 16-byte aligned like a memory map or an
+    // AlignedVec.
+    pub fn access(bytes: &[u8]) -> Result<&ArchivedPuzzleArchive, ArchiveError> {
+        let archive = rkyv::access::<ArchivedPuzzleArchive, rancor::Error>(bytes)
+            .map_err(ArchiveError::Invalid)?;
+        match archive.version.to_native() {
+            ARCHIVE_VERSION => Ok(archive),
+            version => Err(ArchiveError::UnsupportedVersion(version)),
+        }
+    }
+}
+
+impl ArchivedPuzzleArchive {
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+
+    pub fn records(&self) -> &[ArchivedPuzzleRecord] {
+        &self.puzzles
+    }
+
+    pub fn find(&self, seed: u32, difficulty: Difficulty) -> Option<&ArchivedPuzzleRecord> {
+        let key = (seed, difficulty as u8);
+        self.puzzles
+            .binary_search_by_key(&key, |record| (record.seed.to_native(), record.difficulty))
+            .ok()
+            .map(|index| &self.puzzles[index])
+    }
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::*;
+    use crate::core::solver::DancingLinks;
+
+    fn records() -> Vec<PuzzleRecord> {
+        let mut records = Vec::new();
+        for seed in [666, 42, 2200] {
+            for difficulty in [Difficulty::Hard, Difficulty::Easy] {
+                let puzzle = Board::from_seed(seed, Some(difficulty));
+                let solution = DancingLinks::solve_board(&puzzle).unwrap();
+                records.push(PuzzleRecord::new(seed, difficulty, &puzzle, &solution));
+            }
+        }
+        records
+    }
+
+    #[test]
+    fn test_access() {
+        let bytes = PuzzleArchive::new(records()).to_bytes();
+        let archived = PuzzleArchive::access(&bytes).unwrap();
+        assert_eq!(archived.len(), 6);
+        assert_eq!(archived.records()[0].seed, 42);
+
+        let record = archived.find(666, Difficulty::Hard).unwrap();
+        let puzzle = record.puzzle().unwrap();
+        assert_eq!(puzzle, Board::from_seed(666, Some(Difficulty::Hard)));
+        assert_eq!(record.clue_count, puzzle.clue_count());
+        assert_eq!(record.difficulty(), Some(Difficulty::Hard));
+        assert!(record.solution().unwrap().validate());
+        assert!(archived.find(666, Difficulty::Medium).is_none());
+        assert!(archived.find(7, Difficulty::Hard).is_none());
+
+        let deserialized = rkyv::deserialize::<PuzzleArchive, rancor::Error>(archived).unwrap();
+        assert_eq!(deserialized, PuzzleArchive::new(records()));
+    }
+
+    #[test]
+    fn test_access_errors() {
+        let bytes = PuzzleArchive::new(records()).to_bytes();
+        let mut corrupted = AlignedVec::<16>::new();
+        corrupted.extend_from_slice(&bytes[..bytes.len() - 4]);
+        assert!(matches!(
+            PuzzleArchive::access(&corrupted),
+            Err(ArchiveError::Invalid(_))
+        ));
+
+        let newer = PuzzleArchive {
+            version: ARCHIVE_VERSION + 1,
+            puzzles: Vec::new(),
+        };
+        assert!(matches!(
+            PuzzleArchive::access(&newer.to_bytes()),
+            Err(ArchiveError::UnsupportedVersion(2))
+        ));
+    }
+}
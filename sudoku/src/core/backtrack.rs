@@ -0,0 +1,206 @@
+// a plain backtracking solver, independent of the dancing links one (core::solver) so the two
+// can check each other. the values used in each row, column and box are kept as bitmasks, and
+// the search always fills the empty cell with the fewest candidates next.
+use crate::core::board::Board;
+
+// bits 1 to 9.
+const ALL: u16 = 0b11_1111_1110;
+
+pub struct Backtracker {
+    cells: [u8; 81],
+    rows: [u16; 9],
+    cols: [u16; 9],
+    boxes: [u16; 9],
+    nodes: usize,
+}
+
+fn box_of(cell: usize) -> usize {
+    cell / 27 * 3 + cell % 9 / 3
+}
+
+impl Backtracker {
+    pub fn new(board: &Board) -> Result<Self, &'static str> {
+        let mut solver = Backtracker {
+            cells: [0; 81],
+            rows: [0; 9],
+            cols: [0; 9],
+            boxes: [0; 9],
+            nodes: 0,
+        };
+        for (cell, &value) in board.cells.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            if value > 9 {
+                return Err("invalid value");
+            }
+            if solver.candidates(cell) & (1 << value) == 0 {
+                return Err("givens conflict");
+            }
+            solver.place(cell, value);
+        }
+        Ok(solver)
+    }
+
+    // same as DancingLinks::solve_board.
+    pub fn solve_board(board: &Board) -> Result<Board, &'static str> {
+        Backtracker::new(board)?.solve().ok_or("No solution found")
+    }
+
+    // same as DancingLinks::count_solutions, conflicting givens have no solution.
+    pub fn count_solutions(board: &Board, limit: usize) -> Result<usize, &'static str> {
+        match Backtracker::new(board) {
+            Ok(mut solver) => Ok(solver.count(limit)),
+            Err("givens conflict") => Ok(0),
+            Err(e) => Err(e),
+        }
+    }
+
+    // how many nodes of the search tree solve and count have visited.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    // the first solution found, the board is left as it was given.
+    pub fn solve(&mut self) -> Option<Board> {
+        self.nodes += 1;
+        let Some((cell, candidates)) = self.next_cell() else {
+            return Some(Board { cells: self.cells });
+        };
+        for value in 1..=9 {
+            if candidates & (1 << value) == 0 {
+                continue;
+            }
+            self.place(cell, value);
+            let solution = self.solve();
+            self.remove(cell);
+            if solution.is_some() {
+                return solution;
+            }
+        }
+        None
+    }
+
+    // the solutions, counting stops at limit.
+    pub fn count(&mut self, limit: usize) -> usize {
+        self.nodes += 1;
+        let Some((cell, candidates)) = self.next_cell() else {
+            return 1;
+        };
+        let mut found = 0;
+        for value in 1..=9 {
+            if found >= limit {
+                break;
+            }
+            if candidates & (1 << value) == 0 {
+                continue;
+            }
+            self.place(cell, value);
+            found += self.count(limit - found);
+            self.remove(cell);
+        }
+        found
+    }
+
+    fn candidates(&self, cell: usize) -> u16 {
+        !(self.rows[cell / 9] | self.cols[cell % 9] | self.boxes[box_of(cell)]) & ALL
+    }
+
+    fn place(&mut self, cell: usize, value: u8) {
+        self.cells[cell] = value;
+        self.rows[cell / 9] |= 1 << value;
+        self.cols[cell % 9] |= 1 << value;
+        self.boxes[box_of(cell)] |= 1 << value;
+    }
+
+    fn remove(&mut self, cell: usize) {
+        let value = self.cells[cell];
+        self.cells[cell] = 0;
+        self.rows[cell / 9] &= !(1 << value);
+        self.cols[cell % 9] &= !(1 << value);
+        self.boxes[box_of(cell)] &= !(1 << value);
+    }
+
+    // the empty cell with the fewest candidates and its candidates, none once the board is
+    // full. a cell without candidates comes first, it ends the branch.
+    fn next_cell(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+        for cell in 0..81 {
+            if self.cells[cell] != 0 {
+                continue;
+            }
+            let candidates = self.candidates(cell);
+            let fewer = match best {
+                Some((_, fewest)) => candidates.count_ones() < fewest.count_ones(),
+                None => true,
+            };
+            if fewer {
+                best = Some((cell, candidates));
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod backtrack_tests {
+    extern crate alloc;
+    use super::*;
+    use crate::core::board::Difficulty;
+    use crate::core::solver::DancingLinks;
+    use alloc::vec;
+
+    fn seventeen_clues() -> Board {
+        let mut cells = [0; 81];
+        for (i, c) in
+            "000000010400000000020000000000050407008000300001090000300400200050100000000806000"
+                .chars()
+                .enumerate()
+        {
+            cells[i] = c.to_digit(10).unwrap() as u8;
+        }
+        Board { cells }
+    }
+
+    #[test]
+    fn test_agrees_with_dancing_links() {
+        let mut puzzles = vec![seventeen_clues()];
+        for seed in [42, 666, 2200] {
+            for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+                puzzles.push(Board::from_seed(seed, Some(difficulty)));
+            }
+        }
+        for puzzle in puzzles {
+            let solution = Backtracker::solve_board(&puzzle).unwrap();
+            assert!(solution.validate());
+            assert_eq!(solution, DancingLinks::solve_board(&puzzle).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        assert_eq!(Backtracker::count_solutions(&seventeen_clues(), 2), Ok(1));
+        let seeded = Board::from_seed(2200, None);
+        assert_eq!(Backtracker::count_solutions(&seeded, 2), Ok(2));
+        assert_eq!(Backtracker::count_solutions(&seeded, 1), Ok(1));
+
+        let mut cells = [0; 81];
+        cells[0] = 1;
+        cells[1] = 1;
+        assert_eq!(Backtracker::count_solutions(&Board { cells }, 2), Ok(0));
+        assert!(Backtracker::solve_board(&Board { cells }).is_err());
+    }
+
+    #[test]
+    fn test_leaves_the_board() {
+        let puzzle = seventeen_clues();
+        let mut solver = Backtracker::new(&puzzle).unwrap();
+        let solution = solver.solve().unwrap();
+        assert!(solver.nodes() > 81 - 17);
+        assert_eq!(solver.cells, puzzle.cells);
+        assert_eq!(solver.solve(), Some(solution));
+    }
+}
@@ -19,21 +19,89 @@ use alloc::vec::Vec;
 use super::solver;
 use crate::core::solver::DancingLinks;
 
+// discriminants are what gets committed as the difficulty tier in the proofs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Difficulty {
-    Easy,
-    Medium,
-    Hard,
+    Easy = 1,
+    Medium = 2,
+    Hard = 3,
 }
 
+impl Difficulty {
+    pub fn from_u8(value: u8) -> Option<Difficulty> {
+        match value {
+            1 => Some(Difficulty::Easy),
+            2 => Some(Difficulty::Medium),
+            3 => Some(Difficulty::Hard),
+            _ => None,
+        }
+    }
+}
+
+// length of the packed 4-bit board encoding, see `Board::to_packed`.
+pub const PACKED_LEN: usize = 41;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
     // row-wise indexing, ie) index i maps to cell (i // 9, i % 9)
+    #[cfg_attr(feature = "serde", serde(with = "cells"))]
     pub cells: [u8; 81],
 }
 
+// 81 cells as a plain sequence, serde only derives arrays of up to 32. deserializing refuses
+// any other length and values above 9.
+#[cfg(feature = "serde")]
+pub(crate) mod cells {
+    use super::{format, Vec};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(cells: &[u8; 81], serializer: S) -> Result<S::Ok, S::Error> {
+        cells.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 81], D::Error> {
+        let cells = Vec::<u8>::deserialize(deserializer)?;
+        if let Some(value) = cells.iter().find(|&&value| value > 9) {
+            return Err(D::Error::custom(format!("cell value {} above 9", value)));
+        }
+        cells
+            .try_into()
+            .map_err(|cells: Vec<u8>| D::Error::invalid_length(cells.len(), &"81 cells"))
+    }
+}
+
+// cells of 0-9 like every board from_array accepts, with about half of them empty. fuzzers
+// reach malformed boards through the byte encodings instead.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Board {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut cells = [0; 81];
+        for cell in cells.iter_mut() {
+            *cell = match u.int_in_range(0..=17)? {
+                value @ 1..=9 => value,
+                _ => 0,
+            };
+        }
+        Ok(Board { cells })
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (81, Some(81))
+    }
+}
+
 impl Board {
     //generate random bytes and Create a sudoku board based on difficulty
     pub fn from_seed(seed: u32, difficulty: Option<Difficulty>) -> Self {
+        Board::try_from_seed(seed, difficulty).unwrap()
+    }
+
+    // same as from_seed, but solver failures are returned instead of panicking.
+    // the guests' panic-free profile relies on this.
+    pub fn try_from_seed(seed: u32, difficulty: Option<Difficulty>) -> Result<Self, SudokuError> {
         let difficulty = difficulty.unwrap_or(Difficulty::Easy);
 
         let mut rng = SimpleRng::new(seed);
@@ -45,8 +113,11 @@ impl Board {
 
         let mut dl = DancingLinks::new();
         dl.init_header_row();
-        dl.init_constraint_matrix();
-        let sol = dl.solve_with_partial(&temp).unwrap();
+        dl.init_constraint_matrix()
+            .map_err(|_| SudokuError::Unsolvable)?;
+        let sol = dl
+            .solve_with_partial(&temp)
+            .map_err(|_| SudokuError::Unsolvable)?;
         let mut board = DancingLinks::to_sudoku_board(sol);
 
         // now remove elements randomly
@@ -61,7 +132,7 @@ impl Board {
         for &idx in random_indices.iter().take(count) {
             board.cells[idx as usize] = 0; // Assuming 0 represents an empty cell
         }
-        board
+        Ok(board)
     }
 
     pub fn from_array(data: [u8; 81]) -> Result<Board, SudokuError> {
@@ -73,8 +144,40 @@ impl Board {
         Ok(Board { cells: data })
     }
 
+    // packed encoding, 2 cells per byte. cell 2i goes in the low nibble of byte i and cell 2i + 1
+    // in the high nibble, the high nibble of the last byte is always 0. 41 bytes instead of 81.
+    pub fn to_packed(&self) -> [u8; PACKED_LEN] {
+        let mut packed = [0; PACKED_LEN];
+        for (i, &cell) in self.cells.iter().enumerate() {
+            packed[i / 2] |= cell << (4 * (i % 2));
+        }
+        packed
+    }
+
+    pub fn from_packed(packed: &[u8]) -> Result<Board, SudokuError> {
+        if packed.len() != PACKED_LEN || packed[PACKED_LEN - 1] >> 4 != 0 {
+            return Err(SudokuError::InvalidLength);
+        }
+
+        let mut cells = [0; 81];
+        for (i, cell) in cells.iter_mut().enumerate() {
+            *cell = (packed[i / 2] >> (4 * (i % 2))) & 0x0f;
+        }
+        Board::from_array(cells)
+    }
+
+    // number of givens, ie) the non-empty cells.
+    pub fn clue_count(&self) -> u8 {
+        self.cells.iter().filter(|&&x| x != 0).count() as u8
+    }
+
     pub fn apply_user_input_to_board(&mut self, user_input: Vec<u8>) -> Result<bool, &'static str> {
-        for (cell, &input) in self.cells.iter_mut().zip(&user_input) {
+        self.apply_user_input(&user_input)
+    }
+
+    // same as apply_user_input_to_board, without taking ownership of a vec.
+    pub fn apply_user_input(&mut self, user_input: &[u8]) -> Result<bool, &'static str> {
+        for (cell, &input) in self.cells.iter_mut().zip(user_input) {
             // println!("existing node: {}, user inputting: {}", *cell, input);
             if *cell != 0 && input != *cell {
                 return Err("user input is replacing a pre-defined hint.");
@@ -88,8 +191,6 @@ impl Board {
 
     // naive sudoku board validator. todo: experiment with making this faster for the zkVM.
     pub fn validate(&self) -> bool {
-        const CORRECT_SORTED_ROW: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
-
         // check rows
         for row_idx in 0..=8 {
             // need to check slices [0 - 8], [9 - 17], ..., [62, 80]
@@ -98,55 +199,214 @@ impl Board {
             // println!("{:?}", &self.cells[start_idx..end_idx]);
             let row = &self.cells[start_idx..end_idx];
 
-            // check that elements [1,9] appear exactly once.
-            // sort by ascending values
-            let mut sorted = row.to_vec();
-            sorted.sort_unstable();
-
-            // check if sorted is element-wise equal to [1,2,3,..,9].
-            if sorted != CORRECT_SORTED_ROW {
+            if !is_complete(row.iter().copied()) {
                 return false;
             };
         }
 
         // check columns
-        for _ in 0..=8 {
+        for col_idx in 0..=8 {
             // columns are indexed as [0, 9, 18, ..., 72], [1, 10, 19, .., 73]
-            let col = (0..self.cells.len())
+            let col = (col_idx..self.cells.len())
                 .step_by(9)
-                .map(|i| self.cells[i])
-                .collect::<Vec<u8>>();
-            // check that elements [1,9] appear exactly once.
-            // sort by ascending values
-            let mut sorted = col.to_vec();
-            sorted.sort_unstable();
-
-            // check if sorted is element-wise equal to [1,2,3,..,9].
-            if sorted != CORRECT_SORTED_ROW {
+                .map(|i| self.cells[i]);
+            if !is_complete(col) {
                 return false;
             };
         }
 
         // check all (9) cells
-        // todo: move checking logic into a function since it's all the same.
         // indexing for this is [0, 1, 2, 9, 11, 12, 18, 19, 20], [3, 4, 5, 12, 13, 14, 21, 22, 23]
         let box_start_idxs: [usize; 9] = [0, 3, 6, 27, 30, 33, 54, 57, 60];
         let valid = box_start_idxs.iter().all(|start| {
+            #[cfg(not(feature = "fixed-buffers"))]
             let cell_indices = get_cell_indices(start);
-            let mut cell_values: Vec<u8> = cell_indices
-                .iter()
-                .map(|element| self.cells[*element])
-                .collect();
-            cell_values.sort_unstable();
-
-            cell_values == CORRECT_SORTED_ROW
+            #[cfg(feature = "fixed-buffers")]
+            let cell_indices = box_cell_indices(*start);
+            is_complete(cell_indices.iter().map(|element| self.cells[*element]))
         });
 
         valid
     }
+
+    // like validate, but for a board that isn't finished yet: no value is repeated in a row,
+    // column or box. empty cells are ignored.
+    pub fn is_conflict_free(&self) -> bool {
+        let mut rows = [0u16; 9];
+        let mut cols = [0u16; 9];
+        let mut boxes = [0u16; 9];
+
+        for (i, &value) in self.cells.iter().enumerate() {
+            if value == 0 {
+                continue;
+            }
+            let bit = 1 << value;
+            let (row, col) = (i / 9, i % 9);
+            let square = (row / 3) * 3 + col / 3;
+            if rows[row] & bit != 0 || cols[col] & bit != 0 || boxes[square] & bit != 0 {
+                return false;
+            }
+            rows[row] |= bit;
+            cols[col] |= bit;
+            boxes[square] |= bit;
+        }
+        true
+    }
+
+    // everything wrong with a (possibly unfinished) board: each value repeated in a row,
+    // column or box, and each empty cell that no value can go into anymore. empty for a board
+    // that can still be completed as far as its peers go, a full one without violations is
+    // solved.
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for unit in [Unit::Row, Unit::Column, Unit::Box] {
+            for index in 0..9 {
+                let cells = unit.cells(index);
+                for value in 1..=9 {
+                    let repeats: Vec<usize> = cells
+                        .iter()
+                        .copied()
+                        .filter(|&cell| self.cells[cell] == value)
+                        .collect();
+                    if repeats.len() > 1 {
+                        violations.push(Violation::Repeated {
+                            unit,
+                            index: index as u8,
+                            value,
+                            cells: repeats,
+                        });
+                    }
+                }
+            }
+        }
+        for cell in 0..81 {
+            if self.cells[cell] == 0 && !(1..=9).any(|value| self.can_place(cell, value)) {
+                violations.push(Violation::NoCandidates { cell });
+            }
+        }
+        violations
+    }
+
+    // whether `value` can go into `cell` without repeating in its row, column or box. the
+    // cell's own current value doesn't count, so this also covers overwriting it.
+    pub fn can_place(&self, cell: usize, value: u8) -> bool {
+        let (row, col) = (cell / 9, cell % 9);
+        let box_start = (row / 3) * 27 + (col / 3) * 3;
+        (0..9).all(|i| {
+            let peers = [row * 9 + i, i * 9 + col, box_start + (i / 3) * 9 + i % 3];
+            peers
+                .iter()
+                .all(|&peer| peer == cell || self.cells[peer] != value)
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Unit {
+    Row,
+    Column,
+    Box,
 }
+
+impl Unit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Unit::Row => "row",
+            Unit::Column => "column",
+            Unit::Box => "box",
+        }
+    }
+
+    // the row-wise indices of the cells of row, column or box `index` (0-8, boxes numbered
+    // row-wise from the top left).
+    pub fn cells(&self, index: usize) -> [usize; 9] {
+        core::array::from_fn(|i| match self {
+            Unit::Row => index * 9 + i,
+            Unit::Column => i * 9 + index,
+            Unit::Box => (index / 3) * 27 + (index % 3) * 3 + (i / 3) * 9 + i % 3,
+        })
+    }
+}
+
+// one entry of `Board::violations`. cells are row-wise indices, units numbered 0-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Violation {
+    // `value` is in `cells` of the same unit.
+    Repeated {
+        unit: Unit,
+        index: u8,
+        value: u8,
+        cells: Vec<usize>,
+    },
+    // an empty cell whose row, column and box already hold every value.
+    NoCandidates {
+        cell: usize,
+    },
+}
+
+// "row 3 repeats 5 at r3c1, r3c7", "r5c5 is empty but every value is already in its row,
+// column or box". rows, columns and boxes are numbered from 1 here.
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Repeated {
+                unit,
+                index,
+                value,
+                cells,
+            } => {
+                write!(f, "{} {} repeats {} at ", unit.name(), index + 1, value)?;
+                for (i, cell) in cells.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "r{}c{}", cell / 9 + 1, cell % 9 + 1)?;
+                }
+                Ok(())
+            }
+            Violation::NoCandidates { cell } => write!(
+                f,
+                "r{}c{} is empty but every value is already in its row, column or box",
+                cell / 9 + 1,
+                cell % 9 + 1
+            ),
+        }
+    }
+}
+
+// check that elements [1,9] appear exactly once: sort by ascending values and compare
+// element-wise to [1,2,3,..,9]. sorts in a vec, or in a 9 byte stack buffer with the
+// fixed-buffers feature so validate never touches the guest's allocator.
+fn is_complete(values: impl Iterator<Item = u8>) -> bool {
+    const CORRECT_SORTED_ROW: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+    #[cfg(not(feature = "fixed-buffers"))]
+    let mut sorted: Vec<u8> = values.collect();
+    #[cfg(feature = "fixed-buffers")]
+    let mut sorted = {
+        // every row, column and box has exactly 9 cells.
+        let mut buffer = [0u8; 9];
+        for (slot, value) in buffer.iter_mut().zip(values) {
+            *slot = value;
+        }
+        buffer
+    };
+    sorted.sort_unstable();
+
+    sorted == CORRECT_SORTED_ROW
+}
+
+// get_cell_indices without the vec.
+#[cfg(feature = "fixed-buffers")]
+fn box_cell_indices(start_idx: usize) -> [usize; 9] {
+    core::array::from_fn(|i| start_idx + (i / 3) * 9 + i % 3)
+}
+
 // get the cell indices and return them as a vector for a given starting index.
 // in classic 9x9, that would be 0, 3, 6, 27, 30, 33, 54, 57, and 60.
+#[cfg(not(feature = "fixed-buffers"))]
 fn get_cell_indices(start_idx: &usize) -> Vec<usize> {
     // flat_map takes the 3 vectors inside and flattens them into one vector.
     // 3 rows of cells (3x3 cells for a 9x9 grid).
@@ -191,6 +451,105 @@ mod board_tests {
         // println!("{}", solved_board);
     }
 
+    #[test]
+    fn test_packed_round_trip() {
+        let board = Board::from_seed(666, Some(Difficulty::Medium));
+        let packed = board.to_packed();
+        assert_eq!(packed[0], 0x57);
+        assert_eq!(Board::from_packed(&packed).unwrap(), board);
+    }
+
+    #[test]
+    fn test_from_packed_rejects_bad_input() {
+        assert!(Board::from_packed(&[0; 40]).is_err());
+        // trailing nibble has to be empty
+        let mut packed = [0; PACKED_LEN];
+        packed[PACKED_LEN - 1] = 0x10;
+        assert!(Board::from_packed(&packed).is_err());
+        // cell values above 9
+        packed[PACKED_LEN - 1] = 0;
+        packed[3] = 0xa0;
+        assert!(Board::from_packed(&packed).is_err());
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+        let bytes: Vec<u8> = (0..=255).collect();
+        let board = Board::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+        assert!(Board::from_array(board.cells).is_ok());
+        assert!(board.clue_count() > 0 && board.clue_count() < 81);
+        // out of data, every cell is empty.
+        let board = Board::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert_eq!(board.clue_count(), 0);
+    }
+
+    #[test]
+    fn test_clue_count() {
+        let mut board = Board { cells: [0; 81] };
+        assert_eq!(board.clue_count(), 0);
+        board.cells[0] = 5;
+        board.cells[80] = 9;
+        assert_eq!(board.clue_count(), 2);
+    }
+
+    #[test]
+    fn test_can_place() {
+        let board = Board::from_seed(666, Some(Difficulty::Medium));
+        let solution = DancingLinks::solve_board(&board).unwrap();
+        let empty = (0..81).find(|&i| board.cells[i] == 0).unwrap();
+        assert!(board.can_place(empty, solution.cells[empty]));
+
+        // a value already given somewhere in the cell's row.
+        let row = empty / 9;
+        let given = (row * 9..row * 9 + 9)
+            .find(|&i| board.cells[i] != 0)
+            .unwrap();
+        assert!(!board.can_place(empty, board.cells[given]));
+        // a given can always keep its own value.
+        assert!(board.can_place(given, board.cells[given]));
+    }
+
+    #[test]
+    fn test_is_conflict_free() {
+        let mut board = Board::from_seed(666, Some(Difficulty::Medium));
+        assert!(board.is_conflict_free());
+
+        // repeat the first given further along its row, column and box.
+        let first = board.cells[0];
+        let (row, col) = (
+            board.cells[1..9].iter().position(|&x| x == 0).unwrap() + 1,
+            0,
+        );
+        board.cells[row] = first;
+        assert!(!board.is_conflict_free());
+        board.cells[row] = 0;
+
+        let below = (1..9)
+            .map(|r| r * 9 + col)
+            .find(|&i| board.cells[i] == 0)
+            .unwrap();
+        board.cells[below] = first;
+        assert!(!board.is_conflict_free());
+        board.cells[below] = 0;
+
+        let in_box = [10, 11, 19, 20]
+            .into_iter()
+            .find(|&i| board.cells[i] == 0)
+            .unwrap();
+        board.cells[in_box] = first;
+        assert!(!board.is_conflict_free());
+    }
+
+    #[test]
+    fn test_difficulty_from_u8() {
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            assert_eq!(Difficulty::from_u8(difficulty as u8), Some(difficulty));
+        }
+        assert_eq!(Difficulty::from_u8(0), None);
+    }
+
     #[test]
     fn test_apply_user_input_to_board() {
         let mut board = Board::from_seed(2200, None);
@@ -240,6 +599,13 @@ mod board_tests {
         let valid = board.validate();
         assert_eq!(valid, true, "Validation was incorrect");
     }
+    #[test]
+    #[cfg(feature = "fixed-buffers")]
+    fn test_box_cell_indices() {
+        assert_eq!(box_cell_indices(0), [0, 1, 2, 9, 10, 11, 18, 19, 20]);
+        assert_eq!(box_cell_indices(60), [60, 61, 62, 69, 70, 71, 78, 79, 80]);
+    }
+
     #[test]
     fn test_validate_empty_board() {
         let invalid_cells: [u8; 81] = [0; 81];
@@ -264,4 +630,103 @@ mod board_tests {
         let valid = board.validate();
         assert_eq!(valid, false, "Validator incorrect result");
     }
+
+    #[test]
+    fn test_validate_checks_every_column() {
+        // swapping two cells of a row inside one box keeps the row and box complete, and used
+        // to pass because only the first column was checked.
+        let mut cells: [u8; 81] = [
+            7, 9, 6, 5, 8, 1, 4, 2, 3, 2, 4, 1, 9, 3, 7, 5, 6, 8, 8, 3, 5, 6, 2, 4, 9, 1, 7, 6, 8,
+            7, 3, 5, 2, 1, 4, 9, 4, 1, 9, 8, 7, 6, 3, 5, 2, 3, 5, 2, 4, 1, 9, 7, 8, 6, 1, 7, 8, 2,
+            4, 3, 6, 9, 5, 5, 6, 3, 1, 9, 8, 2, 7, 4, 9, 2, 4, 7, 6, 5, 8, 3, 1,
+        ];
+        cells.swap(1, 2);
+        let board = Board { cells };
+        assert!(!board.validate());
+        assert_eq!(
+            board.violations(),
+            vec![
+                Violation::Repeated {
+                    unit: Unit::Column,
+                    index: 1,
+                    value: 6,
+                    cells: vec![1, 64],
+                },
+                Violation::Repeated {
+                    unit: Unit::Column,
+                    index: 2,
+                    value: 9,
+                    cells: vec![2, 38],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_violations() {
+        let puzzle = Board::from_seed(666, Some(Difficulty::Medium));
+        assert!(puzzle.violations().is_empty());
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        assert!(solution.violations().is_empty());
+
+        // the first given again further along its row.
+        let mut board = puzzle.clone();
+        let empty = (1..9).find(|&i| board.cells[i] == 0).unwrap();
+        board.cells[empty] = board.cells[0];
+        let violations = board.violations();
+        assert!(violations.contains(&Violation::Repeated {
+            unit: Unit::Row,
+            index: 0,
+            value: board.cells[0],
+            cells: vec![0, empty],
+        }));
+        assert_eq!(
+            format!("{}", violations[0]),
+            format!("row 1 repeats {} at r1c1, r1c{}", board.cells[0], empty + 1)
+        );
+
+        // 1-8 around an empty corner and 9 below it.
+        let mut cells = [0; 81];
+        cells[1..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        cells[27] = 9;
+        let board = Board { cells };
+        assert_eq!(
+            board.violations(),
+            vec![Violation::NoCandidates { cell: 0 }]
+        );
+        assert_eq!(
+            format!("{}", board.violations()[0]),
+            "r1c1 is empty but every value is already in its row, column or box"
+        );
+    }
+
+    #[test]
+    fn test_unit_cells() {
+        assert_eq!(Unit::Row.cells(1), [9, 10, 11, 12, 13, 14, 15, 16, 17]);
+        assert_eq!(Unit::Column.cells(8), [8, 17, 26, 35, 44, 53, 62, 71, 80]);
+        assert_eq!(Unit::Box.cells(8), [60, 61, 62, 69, 70, 71, 78, 79, 80]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let board = Board::from_seed(666, Some(Difficulty::Medium));
+        let json = serde_json::to_string(&board).unwrap();
+        assert!(json.starts_with("{\"cells\":["));
+        assert_eq!(serde_json::from_str::<Board>(&json).unwrap(), board);
+
+        let short = json.replacen("[", "[1,", 1).replacen(",0", "", 2);
+        assert!(serde_json::from_str::<Board>(&short).is_err());
+        let above_9 = json.replacen("[", "[10,", 1).replacen(",0", "", 1);
+        assert!(serde_json::from_str::<Board>(&above_9).is_err());
+
+        let violation = Violation::Repeated {
+            unit: Unit::Box,
+            index: 4,
+            value: 7,
+            cells: vec![30, 40],
+        };
+        let json = serde_json::to_string(&violation).unwrap();
+        assert_eq!(serde_json::from_str::<Violation>(&json).unwrap(), violation);
+    }
 }
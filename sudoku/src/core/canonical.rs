@@ -0,0 +1,136 @@
+// one representative per class of equivalent puzzles, for dedup and registries. relabeling
+// the digits, permuting the rows within a band, the bands, the columns within a stack, the
+// stacks, and transposing all give a puzzle that's the same to solve. the canonical form is
+// the smallest of all of them, compared cell by cell in row-wise order with 0 for empty
+// cells, so equivalent puzzles share it and its hash.
+use crate::core::board::Board;
+use crate::core::commitment::puzzle_hash;
+extern crate alloc;
+use alloc::vec::Vec;
+
+// the 1296 orders of the nine rows (or columns) that keep bands (stacks) together.
+fn line_orders() -> Vec<[usize; 9]> {
+    const PERMUTATIONS: [[usize; 3]; 6] = [
+        [0, 1, 2],
+        [0, 2, 1],
+        [1, 0, 2],
+        [1, 2, 0],
+        [2, 0, 1],
+        [2, 1, 0],
+    ];
+    let mut orders = Vec::with_capacity(1296);
+    for bands in PERMUTATIONS {
+        for first in PERMUTATIONS {
+            for second in PERMUTATIONS {
+                for third in PERMUTATIONS {
+                    let within = [first, second, third];
+                    orders.push(core::array::from_fn(|i| {
+                        bands[i / 3] * 3 + within[i / 3][i % 3]
+                    }));
+                }
+            }
+        }
+    }
+    orders
+}
+
+pub fn canonical_form(board: &Board) -> Board {
+    let orders = line_orders();
+    let transposed: [u8; 81] = core::array::from_fn(|i| board.cells[(i % 9) * 9 + i / 9]);
+    let mut best = [u8::MAX; 81];
+    for grid in [&board.cells, &transposed] {
+        for rows in &orders {
+            for cols in &orders {
+                // digits are relabeled in the order they first show up, which is the
+                // smallest labeling for this arrangement. the arrangement is given up at the
+                // first cell that makes it larger than the best so far.
+                let mut labels = [0u8; 10];
+                let mut next = 1;
+                let mut smaller = false;
+                let mut candidate = [0u8; 81];
+                for i in 0..81 {
+                    let value = grid[rows[i / 9] * 9 + cols[i % 9]];
+                    let label = match value {
+                        0 => 0,
+                        _ if labels[value as usize] != 0 => labels[value as usize],
+                        _ => {
+                            labels[value as usize] = next;
+                            next += 1;
+                            next - 1
+                        }
+                    };
+                    if !smaller {
+                        if label > best[i] {
+                            break;
+                        }
+                        smaller = label < best[i];
+                    }
+                    candidate[i] = label;
+                }
+                if smaller {
+                    best = candidate;
+                }
+            }
+        }
+    }
+    Board { cells: best }
+}
+
+// the puzzle hash of the canonical form, the same for every equivalent puzzle.
+pub fn canonical_hash(board: &Board) -> [u8; 32] {
+    puzzle_hash(&canonical_form(board))
+}
+
+#[cfg(test)]
+mod canonical_tests {
+    use super::*;
+    use crate::core::board::Difficulty;
+
+    // relabel 1-9 as 9-1, swap the first two bands, the last two columns and transpose.
+    fn disguise(board: &Board) -> Board {
+        let relabeled = board
+            .cells
+            .map(|value| if value == 0 { 0 } else { 10 - value });
+        let rows = [3, 4, 5, 0, 1, 2, 6, 7, 8];
+        let cols = [0, 1, 2, 3, 4, 5, 6, 8, 7];
+        Board {
+            cells: core::array::from_fn(|i| relabeled[rows[i % 9] * 9 + cols[i / 9]]),
+        }
+    }
+
+    #[test]
+    fn test_line_orders() {
+        let orders = line_orders();
+        assert_eq!(orders.len(), 1296);
+        assert_eq!(orders[0], [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        let mut unique = orders.clone();
+        unique.sort_unstable();
+        unique.dedup();
+        assert_eq!(unique.len(), 1296);
+    }
+
+    #[test]
+    fn test_equivalent_puzzles_share_the_form() {
+        let puzzle = Board::from_seed(666, Some(Difficulty::Medium));
+        let disguised = disguise(&puzzle);
+        assert_ne!(disguised, puzzle);
+        let canonical = canonical_form(&puzzle);
+        assert_eq!(canonical_form(&disguised), canonical);
+        assert_eq!(canonical_hash(&disguised), canonical_hash(&puzzle));
+        assert_eq!(canonical_form(&canonical), canonical);
+        assert_eq!(canonical.clue_count(), puzzle.clue_count());
+    }
+
+    #[test]
+    fn test_different_puzzles_differ() {
+        let first = Board::from_seed(1, Some(Difficulty::Medium));
+        let second = Board::from_seed(2, Some(Difficulty::Medium));
+        assert_ne!(canonical_hash(&first), canonical_hash(&second));
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let empty = Board { cells: [0; 81] };
+        assert_eq!(canonical_form(&empty), empty);
+    }
+}
@@ -0,0 +1,109 @@
+// commitments to boards that get exposed as public values by the guests.
+// the hash has to be reproducible outside of this crate (host, solidity), so
+// it is just sha256 over the 81 cell bytes in row-wise order, 0 for empty cells.
+use crate::core::board::Board;
+#[cfg(not(feature = "custom-sha256"))]
+use sha2::{Digest, Sha256};
+
+// hashing is most of the cost of a commitment in a zkVM, so guests swap in their sha256
+// precompile. sp1 patches the sha2 crate, which needs nothing here. for openvm the
+// custom-sha256 feature calls a function the guest defines instead:
+//
+//     #[no_mangle]
+//     fn sudoku_sha256(input: &[u8]) -> [u8; 32] { openvm_sha256_guest::sha256(input) }
+#[cfg(feature = "custom-sha256")]
+extern "Rust" {
+    fn sudoku_sha256(input: &[u8]) -> [u8; 32];
+}
+
+pub fn sha256(input: &[u8]) -> [u8; 32] {
+    // the guest has to define sudoku_sha256, otherwise this fails to link.
+    #[cfg(feature = "custom-sha256")]
+    unsafe {
+        sudoku_sha256(input)
+    }
+    #[cfg(not(feature = "custom-sha256"))]
+    Sha256::digest(input).into()
+}
+
+pub fn puzzle_hash(board: &Board) -> [u8; 32] {
+    sha256(&board.cells)
+}
+
+// sha256(salt || 81 cells). lets a prover show it knows a solution without putting the
+// solution in the public values. the salt is a random value the host picks and keeps, without
+// it a published commitment of a puzzle with few solutions could be brute forced.
+pub fn solution_hash(solution: &Board, salt: &[u8; 32]) -> [u8; 32] {
+    let mut input = [0; 32 + 81];
+    input[..32].copy_from_slice(salt);
+    input[32..].copy_from_slice(&solution.cells);
+    sha256(&input)
+}
+
+#[cfg(test)]
+mod commitment_tests {
+    use super::*;
+
+    #[test]
+    fn test_puzzle_hash_is_deterministic() {
+        let board = Board::from_seed(666, None);
+        assert_eq!(puzzle_hash(&board), puzzle_hash(&board.clone()));
+    }
+
+    #[test]
+    fn test_puzzle_hash_changes_with_givens() {
+        let board = Board::from_seed(666, None);
+        let mut other = board.clone();
+        other.cells[80] = (other.cells[80] + 1) % 10;
+        assert_ne!(puzzle_hash(&board), puzzle_hash(&other));
+    }
+
+    #[test]
+    fn test_solution_hash_is_salted() {
+        let board = Board::from_seed(666, None);
+        assert_ne!(
+            solution_hash(&board, &[0; 32]),
+            solution_hash(&board, &[1; 32])
+        );
+        assert_ne!(solution_hash(&board, &[0; 32]), puzzle_hash(&board));
+    }
+
+    #[test]
+    fn test_seeded_puzzle_hashes() {
+        // the guests regenerate seeded puzzles and commit their hash, which the host recomputes.
+        // any change to the rng or the generator's solver changes these and breaks every proof
+        // made against a published seed, so treat a failure here as a breaking change.
+        use crate::core::board::Difficulty;
+        let cases = [
+            (
+                666,
+                Difficulty::Medium,
+                [0x7b, 0xf9, 0xa8, 0x4c, 0x39, 0x10, 0xbf, 0x36],
+            ),
+            (
+                2200,
+                Difficulty::Easy,
+                [0xb1, 0xa4, 0xde, 0x11, 0xa2, 0x44, 0x52, 0xff],
+            ),
+            (
+                42,
+                Difficulty::Hard,
+                [0xc1, 0xba, 0xb6, 0xbb, 0x53, 0x30, 0xaf, 0xaa],
+            ),
+        ];
+        for (seed, difficulty, prefix) in cases {
+            let board = Board::from_seed(seed, Some(difficulty));
+            assert_eq!(puzzle_hash(&board)[..8], prefix, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn test_empty_board_hash() {
+        // sha256 of 81 zero bytes, so other implementations can check against it.
+        let board = Board { cells: [0; 81] };
+        assert_eq!(
+            puzzle_hash(&board)[..8],
+            [0x67, 0x78, 0xc7, 0xc7, 0xb6, 0xb6, 0xc1, 0xc2]
+        );
+    }
+}
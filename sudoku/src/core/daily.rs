@@ -0,0 +1,157 @@
+// the puzzle of the day. its seed is derived from the date, so everyone running the same
+// version gets the same board on the same day without asking a server for it:
+//
+//     seed = first 4 bytes, big endian, of sha256(DAILY_TAG || "YYYY-MM-DD")
+//
+// the tag keeps daily seeds apart from any other seed derived from a hash. it's versioned
+// along with the generator: a change to either is a new daily series.
+use crate::core::board::{Board, Difficulty};
+use crate::core::commitment::sha256;
+use crate::core::error::SudokuError;
+extern crate alloc;
+use alloc::vec::Vec;
+use core::fmt::{self, Display};
+
+pub const DAILY_TAG: &[u8] = b"zk-sudoku/daily/v1";
+
+// the same difficulty every day, it's the date that picks the puzzle.
+pub const DAILY_DIFFICULTY: Difficulty = Difficulty::Medium;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    // none for a day the month doesn't have, or a year without four digits.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        // every fourth year, except centuries not divisible by 400.
+        let leap = matches!((year % 4, year % 100, year % 400), (0, 1.., _) | (_, _, 0));
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        ((1000..=9999).contains(&year) && (1..=days).contains(&day)).then_some(Date {
+            year,
+            month,
+            day,
+        })
+    }
+
+    // exactly YYYY-MM-DD.
+    pub fn parse(text: &str) -> Option<Date> {
+        let bytes = text.as_bytes();
+        if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            return None;
+        }
+        let number = |range: core::ops::Range<usize>| {
+            bytes[range.clone()]
+                .iter()
+                .all(u8::is_ascii_digit)
+                .then(|| text[range].parse::<u16>().ok())
+                .flatten()
+        };
+        Date::new(number(0..4)?, number(5..7)? as u8, number(8..10)? as u8)
+    }
+
+    // the date the given number of days after 1970-01-01 (the unix epoch).
+    pub fn from_days(days: u32) -> Date {
+        // howard hinnant's civil_from_days, in 400 year eras starting on march 1st.
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = era * 400 + year_of_era + (month <= 2) as u32;
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub fn daily_seed(date: &Date) -> u32 {
+    let mut input = Vec::from(DAILY_TAG);
+    input.extend_from_slice(alloc::format!("{}", date).as_bytes());
+    let hash = sha256(&input);
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+pub fn daily_puzzle(date: &Date) -> Result<Board, SudokuError> {
+    Board::try_from_seed(daily_seed(date), Some(DAILY_DIFFICULTY))
+}
+
+#[cfg(test)]
+mod daily_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let date = Date::parse("2024-02-29").unwrap();
+        assert_eq!(
+            date,
+            Date {
+                year: 2024,
+                month: 2,
+                day: 29
+            }
+        );
+        assert_eq!(alloc::format!("{}", date), "2024-02-29");
+        assert_eq!(Date::parse("2023-02-29"), None);
+        assert_eq!(Date::parse("2100-02-29"), None);
+        assert!(Date::parse("2000-02-29").is_some());
+        assert_eq!(Date::parse("2024-13-01"), None);
+        assert_eq!(Date::parse("2024-04-31"), None);
+        assert_eq!(Date::parse("2024-4-01"), None);
+        assert_eq!(Date::parse("2024-+4-01"), None);
+        assert_eq!(Date::parse("2024/04/01"), None);
+    }
+
+    #[test]
+    fn test_from_days() {
+        assert_eq!(Date::from_days(0), Date::parse("1970-01-01").unwrap());
+        assert_eq!(Date::from_days(59), Date::parse("1970-03-01").unwrap());
+        assert_eq!(Date::from_days(11_016), Date::parse("2000-02-29").unwrap());
+        assert_eq!(Date::from_days(19_782), Date::parse("2024-02-29").unwrap());
+        assert_eq!(Date::from_days(20_454), Date::parse("2026-01-01").unwrap());
+    }
+
+    #[test]
+    fn test_daily_seed() {
+        let date = Date::parse("2024-01-01").unwrap();
+        let mut input = Vec::from(DAILY_TAG);
+        input.extend_from_slice(b"2024-01-01");
+        let hash = sha256(&input);
+        assert_eq!(
+            daily_seed(&date),
+            u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+        );
+        let next = Date::parse("2024-01-02").unwrap();
+        assert_ne!(daily_seed(&date), daily_seed(&next));
+        assert_eq!(
+            daily_puzzle(&date).unwrap(),
+            Board::from_seed(daily_seed(&date), Some(Difficulty::Medium))
+        );
+    }
+}
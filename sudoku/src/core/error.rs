@@ -2,4 +2,6 @@
 pub enum SudokuError {
     OutOfBounds,
     InvalidValue,
+    InvalidLength,
+    Unsolvable,
 }
@@ -0,0 +1,494 @@
+// solving the way a person does: candidates per cell and a fixed set of techniques, tried from
+// the simplest up, each step recorded. unlike the dancing links solver it never guesses, so the
+// hardest technique a puzzle needs says how hard it is to solve by hand (see `grade`), and the
+// first step of the trace is a hint.
+use crate::core::board::{Board, Difficulty, Unit};
+use core::fmt;
+extern crate alloc;
+use alloc::vec::Vec;
+
+// ordered from the easiest, the order they're tried in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Technique {
+    // a cell with one candidate left.
+    NakedSingle,
+    // a value with one place left in a row, column or box.
+    HiddenSingle,
+    // two cells of a unit with the same two candidates, which go nowhere else in the unit.
+    NakedPair,
+    // a value confined to one line of a box (or one box of a line), so it goes nowhere else
+    // on that line (or in that box).
+    LockedCandidates,
+    // two values with the same two places in a unit, so those cells hold nothing else.
+    HiddenPair,
+    // three cells of a unit whose candidates are three values between them.
+    NakedTriple,
+    // a value with the same two places in two rows (or columns), which clears the columns
+    // (or rows) of it.
+    XWing,
+}
+
+impl Technique {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle => "naked single",
+            Technique::HiddenSingle => "hidden single",
+            Technique::NakedPair => "naked pair",
+            Technique::LockedCandidates => "locked candidates",
+            Technique::HiddenPair => "hidden pair",
+            Technique::NakedTriple => "naked triple",
+            Technique::XWing => "x-wing",
+        }
+    }
+
+    // the difficulty tier a puzzle needing this technique is graded as.
+    pub fn difficulty(&self) -> Difficulty {
+        match self {
+            Technique::NakedSingle | Technique::HiddenSingle => Difficulty::Easy,
+            Technique::NakedPair | Technique::LockedCandidates | Technique::HiddenPair => {
+                Difficulty::Medium
+            }
+            Technique::NakedTriple | Technique::XWing => Difficulty::Hard,
+        }
+    }
+}
+
+// one move: either a value placed, or candidates (cell, value) ruled out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    pub technique: Technique,
+    pub placement: Option<(usize, u8)>,
+    pub eliminations: Vec<(usize, u8)>,
+}
+
+// "hidden single: r4c7=6", "naked pair: r1c2<>3, r1c9<>3".
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.technique.name())?;
+        if let Some((cell, value)) = self.placement {
+            return write!(f, "r{}c{}={}", cell / 9 + 1, cell % 9 + 1, value);
+        }
+        for (i, (cell, value)) in self.eliminations.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "r{}c{}<>{}", cell / 9 + 1, cell % 9 + 1, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Trace {
+    pub steps: Vec<Step>,
+    // the board as far as the techniques got.
+    pub board: Board,
+    pub solved: bool,
+}
+
+impl Trace {
+    pub fn hardest(&self) -> Option<Technique> {
+        self.steps.iter().map(|step| step.technique).max()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Grade {
+    // None when the techniques don't solve the puzzle, it then needs guessing (or has no
+    // unique solution).
+    pub difficulty: Option<Difficulty>,
+    pub hardest: Option<Technique>,
+    pub clue_count: u8,
+}
+
+// the rows, then the columns, then the boxes, as row-wise cell indices.
+fn units() -> [[usize; 9]; 27] {
+    core::array::from_fn(|unit| [Unit::Row, Unit::Column, Unit::Box][unit / 9].cells(unit % 9))
+}
+
+fn values(mask: u16) -> impl Iterator<Item = u8> {
+    (1..=9).filter(move |value| mask & (1 << value) != 0)
+}
+
+pub struct LogicSolver {
+    cells: [u8; 81],
+    // bit v set when v can still go into the (empty) cell.
+    candidates: [u16; 81],
+    units: [[usize; 9]; 27],
+}
+
+impl LogicSolver {
+    // fails for a board that already repeats a value in a row, column or box.
+    pub fn new(board: &Board) -> Result<Self, &'static str> {
+        if !board.is_conflict_free() {
+            return Err("the board repeats a value in a row, column or box");
+        }
+        let mut solver = LogicSolver {
+            cells: board.cells,
+            candidates: [0; 81],
+            units: units(),
+        };
+        for cell in 0..81 {
+            if solver.cells[cell] == 0 {
+                solver.candidates[cell] = (1..=9)
+                    .filter(|&value| board.can_place(cell, value))
+                    .fold(0, |mask, value| mask | 1 << value);
+            }
+        }
+        Ok(solver)
+    }
+
+    pub fn board(&self) -> Board {
+        Board { cells: self.cells }
+    }
+
+    // the first step the simplest technique that applies finds, None when none does (or the
+    // board is full, or an empty cell has no candidates left).
+    pub fn next_step(&self) -> Option<Step> {
+        if (0..81).any(|cell| self.cells[cell] == 0 && self.candidates[cell] == 0) {
+            return None;
+        }
+        self.naked_single()
+            .or_else(|| self.hidden_single())
+            .or_else(|| self.naked_pair())
+            .or_else(|| self.locked_candidates())
+            .or_else(|| self.hidden_pair())
+            .or_else(|| self.naked_triple())
+            .or_else(|| self.x_wing())
+    }
+
+    pub fn apply(&mut self, step: &Step) {
+        if let Some((cell, value)) = step.placement {
+            self.cells[cell] = value;
+            self.candidates[cell] = 0;
+            for unit in self.units.iter().filter(|unit| unit.contains(&cell)) {
+                for &peer in unit {
+                    self.candidates[peer] &= !(1 << value);
+                }
+            }
+        }
+        for &(cell, value) in &step.eliminations {
+            self.candidates[cell] &= !(1 << value);
+        }
+    }
+
+    fn empty_in(&self, unit: &[usize; 9]) -> Vec<usize> {
+        unit.iter()
+            .copied()
+            .filter(|&cell| self.cells[cell] == 0)
+            .collect()
+    }
+
+    // the cells of `unit` where `value` is still a candidate.
+    fn places(&self, unit: &[usize; 9], value: u8) -> Vec<usize> {
+        unit.iter()
+            .copied()
+            .filter(|&cell| self.candidates[cell] & (1 << value) != 0)
+            .collect()
+    }
+
+    // a step eliminating every candidate in `mask` from `cells`, None if there's none left to
+    // eliminate.
+    fn eliminate(
+        &self,
+        technique: Technique,
+        cells: impl Iterator<Item = usize>,
+        mask: u16,
+    ) -> Option<Step> {
+        let mut eliminations = Vec::new();
+        for cell in cells {
+            for value in values(self.candidates[cell] & mask) {
+                eliminations.push((cell, value));
+            }
+        }
+        eliminations.sort_unstable();
+        eliminations.dedup();
+        (!eliminations.is_empty()).then_some(Step {
+            technique,
+            placement: None,
+            eliminations,
+        })
+    }
+
+    fn naked_single(&self) -> Option<Step> {
+        (0..81)
+            .find(|&cell| self.cells[cell] == 0 && self.candidates[cell].count_ones() == 1)
+            .map(|cell| Step {
+                technique: Technique::NakedSingle,
+                placement: Some((cell, self.candidates[cell].trailing_zeros() as u8)),
+                eliminations: Vec::new(),
+            })
+    }
+
+    fn hidden_single(&self) -> Option<Step> {
+        for unit in &self.units {
+            for value in 1..=9 {
+                if let [cell] = self.places(unit, value)[..] {
+                    return Some(Step {
+                        technique: Technique::HiddenSingle,
+                        placement: Some((cell, value)),
+                        eliminations: Vec::new(),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn naked_pair(&self) -> Option<Step> {
+        for unit in &self.units {
+            let empty = self.empty_in(unit);
+            for (i, &a) in empty.iter().enumerate() {
+                let mask = self.candidates[a];
+                if mask.count_ones() != 2 {
+                    continue;
+                }
+                for &b in &empty[i + 1..] {
+                    if self.candidates[b] != mask {
+                        continue;
+                    }
+                    let others = empty.iter().copied().filter(|&c| c != a && c != b);
+                    if let Some(step) = self.eliminate(Technique::NakedPair, others, mask) {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // pointing (a box's places on one line) and claiming (a line's places in one box).
+    fn locked_candidates(&self) -> Option<Step> {
+        let (lines, boxes) = self.units.split_at(18);
+        for value in 1..=9 {
+            for (from, to) in [(boxes, lines), (lines, boxes)] {
+                for unit in from {
+                    let places = self.places(unit, value);
+                    if places.len() < 2 {
+                        continue;
+                    }
+                    let Some(target) = to
+                        .iter()
+                        .find(|other| places.iter().all(|cell| other.contains(cell)))
+                    else {
+                        continue;
+                    };
+                    let outside = target.iter().copied().filter(|cell| !unit.contains(cell));
+                    if let Some(step) =
+                        self.eliminate(Technique::LockedCandidates, outside, 1 << value)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn hidden_pair(&self) -> Option<Step> {
+        for unit in &self.units {
+            for first in 1..=9 {
+                let places = self.places(unit, first);
+                if places.len() != 2 {
+                    continue;
+                }
+                for second in first + 1..=9 {
+                    if self.places(unit, second) != places {
+                        continue;
+                    }
+                    let others = !(1 << first | 1 << second);
+                    if let Some(step) =
+                        self.eliminate(Technique::HiddenPair, places.iter().copied(), others)
+                    {
+                        return Some(step);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn naked_triple(&self) -> Option<Step> {
+        for unit in &self.units {
+            let empty = self.empty_in(unit);
+            let n = empty.len();
+            for i in 0..n {
+                for j in i + 1..n {
+                    for k in j + 1..n {
+                        let triple = [empty[i], empty[j], empty[k]];
+                        let mask = triple
+                            .iter()
+                            .fold(0, |mask, &cell| mask | self.candidates[cell]);
+                        if mask.count_ones() != 3 {
+                            continue;
+                        }
+                        let others = empty.iter().copied().filter(|c| !triple.contains(c));
+                        if let Some(step) = self.eliminate(Technique::NakedTriple, others, mask) {
+                            return Some(step);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    // rows as the base, then columns.
+    fn x_wing(&self) -> Option<Step> {
+        for value in 1..=9 {
+            for (base, cover) in [(0, 9), (9, 0)] {
+                for a in 0..9 {
+                    let first = self.places(&self.units[base + a], value);
+                    if first.len() != 2 {
+                        continue;
+                    }
+                    // position of each place along the base line.
+                    let across = |cell: usize| if base == 0 { cell % 9 } else { cell / 9 };
+                    for b in a + 1..9 {
+                        let second = self.places(&self.units[base + b], value);
+                        if second.len() != 2
+                            || across(second[0]) != across(first[0])
+                            || across(second[1]) != across(first[1])
+                        {
+                            continue;
+                        }
+                        let corners = [first[0], first[1], second[0], second[1]];
+                        let cleared = [across(first[0]), across(first[1])]
+                            .into_iter()
+                            .flat_map(|line| self.units[cover + line])
+                            .filter(|cell| !corners.contains(cell));
+                        if let Some(step) = self.eliminate(Technique::XWing, cleared, 1 << value) {
+                            return Some(step);
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+// every step the techniques find, until the board is full or none applies.
+pub fn solve_logically(board: &Board) -> Result<Trace, &'static str> {
+    let mut solver = LogicSolver::new(board)?;
+    let mut steps = Vec::new();
+    while let Some(step) = solver.next_step() {
+        solver.apply(&step);
+        steps.push(step);
+    }
+    let board = solver.board();
+    Ok(Trace {
+        solved: board.validate(),
+        steps,
+        board,
+    })
+}
+
+// how hard the puzzle is to solve by hand: the tier of the hardest technique it needs.
+pub fn grade(board: &Board) -> Result<Grade, &'static str> {
+    let trace = solve_logically(board)?;
+    let hardest = trace.hardest();
+    Ok(Grade {
+        difficulty: match trace.solved {
+            true => Some(hardest.map_or(Difficulty::Easy, |technique| technique.difficulty())),
+            false => None,
+        },
+        hardest,
+        clue_count: board.clue_count(),
+    })
+}
+
+#[cfg(test)]
+mod logic_tests {
+    use super::*;
+    use crate::core::solver::DancingLinks;
+
+    fn board(digits: &str) -> Board {
+        let cells: Vec<u8> = digits
+            .bytes()
+            .map(|b| if b == b'.' { 0 } else { b - b'0' })
+            .collect();
+        Board::from_array(cells.try_into().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_units() {
+        let units = units();
+        assert_eq!(units[0], [0, 1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(units[9 + 2], [2, 11, 20, 29, 38, 47, 56, 65, 74]);
+        assert_eq!(units[18 + 4], [30, 31, 32, 39, 40, 41, 48, 49, 50]);
+        for cell in 0..81 {
+            assert_eq!(units.iter().filter(|unit| unit.contains(&cell)).count(), 3);
+        }
+    }
+
+    #[test]
+    fn test_singles_puzzle_is_easy() {
+        let puzzle = board(
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79",
+        );
+        let trace = solve_logically(&puzzle).unwrap();
+        assert!(trace.solved);
+        assert_eq!(trace.board, DancingLinks::solve_board(&puzzle).unwrap());
+        let grade = grade(&puzzle).unwrap();
+        assert_eq!(grade.difficulty, Some(Difficulty::Easy));
+        assert!(grade.hardest <= Some(Technique::HiddenSingle));
+        assert_eq!(grade.clue_count, 30);
+    }
+
+    #[test]
+    fn test_seeded_puzzles_agree_with_the_solver() {
+        for seed in [1, 42, 666] {
+            for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+                let puzzle = Board::from_seed(seed, Some(difficulty));
+                let trace = solve_logically(&puzzle).unwrap();
+                let solution = DancingLinks::solve_board(&puzzle).unwrap();
+                // whatever gets placed is right, solved or not.
+                for (placed, solved) in trace.board.cells.iter().zip(solution.cells) {
+                    assert!(*placed == 0 || *placed == solved);
+                }
+                assert_eq!(trace.solved, trace.board == solution);
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_display() {
+        let step = Step {
+            technique: Technique::HiddenSingle,
+            placement: Some((33, 6)),
+            eliminations: Vec::new(),
+        };
+        assert_eq!(alloc::format!("{}", step), "hidden single: r4c7=6");
+        let step = Step {
+            technique: Technique::NakedPair,
+            placement: None,
+            eliminations: alloc::vec![(1, 3), (8, 3)],
+        };
+        assert_eq!(alloc::format!("{}", step), "naked pair: r1c2<>3, r1c9<>3");
+    }
+
+    #[test]
+    fn test_x_wing() {
+        // a classic x-wing on 1 in columns 5 and 8 (rows 2 and 8 after the singles run out).
+        let puzzle = board(
+            "1.....569492.561.8.561.924...964.8.1.64.1....218.356.4.4.5...169.5.614.2621.....5",
+        );
+        let trace = solve_logically(&puzzle).unwrap();
+        assert!(trace.solved);
+        assert_eq!(trace.hardest(), Some(Technique::XWing));
+        assert_eq!(grade(&puzzle).unwrap().difficulty, Some(Difficulty::Hard));
+    }
+
+    #[test]
+    fn test_conflicting_board() {
+        let mut cells = [0; 81];
+        cells[0] = 5;
+        cells[1] = 5;
+        assert!(solve_logically(&Board::from_array(cells).unwrap()).is_err());
+    }
+}
@@ -1,4 +1,12 @@
+pub mod backtrack;
 pub mod board;
+pub mod canonical;
+pub mod commitment;
+pub mod daily;
 mod error;
+pub mod logic;
+pub mod puzzle_id;
 mod random;
+pub mod share_code;
 pub mod solver;
+pub mod variant;
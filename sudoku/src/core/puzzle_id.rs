@@ -0,0 +1,260 @@
+// compact ids for generated puzzles, short enough to paste in a chat or a url. an id names the
+// seed, difficulty and variant the puzzle is generated from, and carries a checksum so a typo
+// is caught instead of naming another puzzle:
+//
+//     "zk" version "-" base32(seed u32 | difficulty u8 | variant u8 | checksum 2 bytes)
+//
+// the seed is big endian, the checksum the first two bytes of sha256 over the version and the
+// six bytes before it, and base32 is the lowercase rfc 4648 alphabet without padding. ids are
+// read case-insensitively. only variants without extra data (classic, x) have ids, killer
+// cages don't come from a seed.
+use crate::core::board::{Board, Difficulty};
+use crate::core::commitment::sha256;
+use crate::core::error::SudokuError;
+use crate::core::variant::{Variant, VARIANT_CLASSIC, VARIANT_X};
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const PUZZLE_ID_PREFIX: &str = "zk";
+
+// bumped whenever the layout changes, older ids are refused rather than misread.
+pub const PUZZLE_ID_VERSION: u8 = 1;
+
+const ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+// seed, difficulty, variant and checksum.
+const PAYLOAD_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleIdError {
+    // not "zk<version>-<base32>", or the base32 is the wrong length.
+    InvalidEncoding,
+    UnsupportedVersion(u8),
+    BadChecksum,
+    InvalidDifficulty,
+    // a variant kind that needs more than its kind to be checked.
+    UnsupportedVariant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PuzzleId {
+    pub seed: u32,
+    pub difficulty: Difficulty,
+    // `Variant::kind`, VARIANT_CLASSIC or VARIANT_X.
+    pub variant: u8,
+}
+
+impl PuzzleId {
+    pub fn new(
+        seed: u32,
+        difficulty: Difficulty,
+        variant: &Variant,
+    ) -> Result<Self, PuzzleIdError> {
+        match variant {
+            Variant::Classic | Variant::X => Ok(PuzzleId {
+                seed,
+                difficulty,
+                variant: variant.kind(),
+            }),
+            Variant::Killer(_) => Err(PuzzleIdError::UnsupportedVariant),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut payload = [0; PAYLOAD_LEN];
+        payload[..4].copy_from_slice(&self.seed.to_be_bytes());
+        payload[4] = self.difficulty as u8;
+        payload[5] = self.variant;
+        let checksum = checksum(PUZZLE_ID_VERSION, &payload[..6]);
+        payload[6..].copy_from_slice(&checksum);
+
+        let mut id = String::from(PUZZLE_ID_PREFIX);
+        id.push_str(&alloc::format!("{}-", PUZZLE_ID_VERSION));
+        id.push_str(&base32_encode(&payload));
+        id
+    }
+
+    pub fn decode(id: &str) -> Result<PuzzleId, PuzzleIdError> {
+        let id = id.trim().to_ascii_lowercase();
+        let (version, body) = id
+            .strip_prefix(PUZZLE_ID_PREFIX)
+            .and_then(|rest| rest.split_once('-'))
+            .ok_or(PuzzleIdError::InvalidEncoding)?;
+        let version: u8 = version
+            .parse()
+            .map_err(|_| PuzzleIdError::InvalidEncoding)?;
+        if version != PUZZLE_ID_VERSION {
+            return Err(PuzzleIdError::UnsupportedVersion(version));
+        }
+        let payload: [u8; PAYLOAD_LEN] = base32_decode(body)?
+            .try_into()
+            .map_err(|_| PuzzleIdError::InvalidEncoding)?;
+        if payload[6..] != checksum(version, &payload[..6]) {
+            return Err(PuzzleIdError::BadChecksum);
+        }
+        let difficulty = Difficulty::from_u8(payload[4]).ok_or(PuzzleIdError::InvalidDifficulty)?;
+        if ![VARIANT_CLASSIC, VARIANT_X].contains(&payload[5]) {
+            return Err(PuzzleIdError::UnsupportedVariant);
+        }
+        Ok(PuzzleId {
+            seed: u32::from_be_bytes(payload[..4].try_into().unwrap()),
+            difficulty,
+            variant: payload[5],
+        })
+    }
+
+    // the board the guests regenerate from the seed.
+    pub fn puzzle(&self) -> Result<Board, SudokuError> {
+        Board::try_from_seed(self.seed, Some(self.difficulty))
+    }
+
+    pub fn variant(&self) -> Variant {
+        match self.variant {
+            VARIANT_X => Variant::X,
+            _ => Variant::Classic,
+        }
+    }
+}
+
+// as the id string, "zk1-...", so a deserialized id has passed decode's checks.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PuzzleId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.encode())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PuzzleId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        PuzzleId::decode(&id).map_err(|e| {
+            serde::de::Error::custom(alloc::format!("invalid puzzle id '{}' ({:?})", id, e))
+        })
+    }
+}
+
+fn checksum(version: u8, bytes: &[u8]) -> [u8; 2] {
+    let mut preimage = Vec::with_capacity(1 + bytes.len());
+    preimage.push(version);
+    preimage.extend_from_slice(bytes);
+    let digest = sha256(&preimage);
+    [digest[0], digest[1]]
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(5));
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[(buffer >> bits) as usize & 31] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (5 - bits)) as usize & 31] as char);
+    }
+    out
+}
+
+// refuses leftover bits that aren't zero, so every payload has exactly one id.
+fn base32_decode(text: &str) -> Result<Vec<u8>, PuzzleIdError> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(PuzzleIdError::InvalidEncoding)?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    if bits >= 5 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(PuzzleIdError::InvalidEncoding);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod puzzle_id_tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        for (seed, difficulty, variant) in [
+            (666, Difficulty::Medium, Variant::Classic),
+            (0, Difficulty::Easy, Variant::X),
+            (u32::MAX, Difficulty::Hard, Variant::Classic),
+        ] {
+            let id = PuzzleId::new(seed, difficulty, &variant).unwrap();
+            let encoded = id.encode();
+            assert!(encoded.starts_with("zk1-"));
+            // 8 bytes are 13 base32 characters.
+            assert_eq!(encoded.len(), 4 + 13);
+            assert_eq!(PuzzleId::decode(&encoded), Ok(id));
+            assert_eq!(PuzzleId::decode(&encoded.to_uppercase()), Ok(id));
+            assert_eq!(id.variant(), variant);
+        }
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let id = PuzzleId::new(666, Difficulty::Medium, &Variant::Classic).unwrap();
+        assert_eq!(
+            id.puzzle().unwrap(),
+            Board::from_seed(666, Some(Difficulty::Medium))
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let id = PuzzleId::new(666, Difficulty::Medium, &Variant::Classic)
+            .unwrap()
+            .encode();
+        assert_eq!(
+            PuzzleId::decode(&id.replacen("zk1", "zk2", 1)),
+            Err(PuzzleIdError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            PuzzleId::decode(&id[3..]),
+            Err(PuzzleIdError::InvalidEncoding)
+        );
+        assert_eq!(
+            PuzzleId::decode(&id[..id.len() - 1]),
+            Err(PuzzleIdError::InvalidEncoding)
+        );
+        assert_eq!(
+            PuzzleId::decode(&(id.clone() + "1")),
+            Err(PuzzleIdError::InvalidEncoding)
+        );
+
+        // a typo in the body.
+        let first = id.chars().nth(4).unwrap();
+        let typo = if first == 'a' { "b" } else { "a" };
+        let mut mistyped = id.clone();
+        mistyped.replace_range(4..5, typo);
+        assert_eq!(PuzzleId::decode(&mistyped), Err(PuzzleIdError::BadChecksum));
+
+        assert_eq!(
+            PuzzleId::new(1, Difficulty::Easy, &Variant::Killer(Vec::new())),
+            Err(PuzzleIdError::UnsupportedVariant)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let id = PuzzleId::new(666, Difficulty::Medium, &Variant::X).unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, alloc::format!("\"{}\"", id.encode()));
+        assert_eq!(serde_json::from_str::<PuzzleId>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<PuzzleId>(&json.replacen("zk1", "zk2", 1)).is_err());
+    }
+}
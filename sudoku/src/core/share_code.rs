@@ -0,0 +1,183 @@
+// share codes, any board (a puzzle, or a game in progress) as a string players can paste in a
+// chat. unlike a puzzle id it holds the cells themselves, so it also works for boards that
+// don't come from a seed:
+//
+//     base64url(version u8 | packed cells 41 bytes | crc32 4 bytes)
+//
+// the cells are `Board::to_packed`, the crc32 (the ieee one of zip and png) is over the bytes
+// before it, big endian, and base64url is rfc 4648's url-safe alphabet without padding, 62
+// characters in all. the crc always comes last, so a corrupted code is reported as such even
+// when the version byte is what got hit. whitespace around the code is ignored.
+use crate::core::board::{Board, PACKED_LEN};
+extern crate alloc;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// bumped whenever the layout changes, older codes are refused rather than misread.
+pub const SHARE_CODE_VERSION: u8 = 1;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+// version, packed cells and crc.
+const PAYLOAD_LEN: usize = 1 + PACKED_LEN + 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareCodeError {
+    // not base64url, or too short to hold a checksum.
+    InvalidEncoding,
+    BadChecksum,
+    UnsupportedVersion(u8),
+    // the checksum matches but the cells don't decode, a code made by something else.
+    InvalidCells,
+}
+
+impl Board {
+    pub fn to_share_code(&self) -> String {
+        let mut payload = Vec::with_capacity(PAYLOAD_LEN);
+        payload.push(SHARE_CODE_VERSION);
+        payload.extend_from_slice(&self.to_packed());
+        let crc = crc32(&payload);
+        payload.extend_from_slice(&crc.to_be_bytes());
+        base64url_encode(&payload)
+    }
+
+    pub fn from_share_code(code: &str) -> Result<Board, ShareCodeError> {
+        let payload = base64url_decode(code.trim())?;
+        let Some((body, crc)) = payload.split_last_chunk::<4>() else {
+            return Err(ShareCodeError::InvalidEncoding);
+        };
+        if crc32(body) != u32::from_be_bytes(*crc) {
+            return Err(ShareCodeError::BadChecksum);
+        }
+        match body.split_first() {
+            Some((&SHARE_CODE_VERSION, packed)) => {
+                Board::from_packed(packed).map_err(|_| ShareCodeError::InvalidCells)
+            }
+            Some((&version, _)) => Err(ShareCodeError::UnsupportedVersion(version)),
+            None => Err(ShareCodeError::InvalidEncoding),
+        }
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() * 8).div_ceil(6));
+    let (mut buffer, mut bits) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 6 {
+            bits -= 6;
+            out.push(ALPHABET[(buffer >> bits) as usize & 63] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[(buffer << (6 - bits)) as usize & 63] as char);
+    }
+    out
+}
+
+// refuses leftover bits that aren't zero, so every payload has exactly one code.
+fn base64url_decode(text: &str) -> Result<Vec<u8>, ShareCodeError> {
+    let mut out = Vec::with_capacity(text.len() * 6 / 8);
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in text.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(ShareCodeError::InvalidEncoding)?;
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    if bits >= 6 || buffer & ((1 << bits) - 1) != 0 {
+        return Err(ShareCodeError::InvalidEncoding);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod share_code_tests {
+    use super::*;
+    use crate::core::board::Difficulty;
+    use crate::core::solver::DancingLinks;
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        for board in [puzzle, solution, Board { cells: [0; 81] }] {
+            let code = board.to_share_code();
+            assert_eq!(code.len(), 62);
+            assert!(code.bytes().all(|c| ALPHABET.contains(&c)));
+            assert_eq!(Board::from_share_code(&code), Ok(board.clone()));
+            assert_eq!(
+                Board::from_share_code(&alloc::format!(" {}\n", code)),
+                Ok(board)
+            );
+        }
+        // the check value of the crc32 this uses.
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let code = Board::from_seed(42, Some(Difficulty::Hard)).to_share_code();
+
+        // every single character typo is caught.
+        for i in 0..code.len() {
+            let mut mistyped = code.clone().into_bytes();
+            mistyped[i] = if mistyped[i] == b'A' { b'B' } else { b'A' };
+            let mistyped = String::from_utf8(mistyped).unwrap();
+            assert!(matches!(
+                Board::from_share_code(&mistyped),
+                Err(ShareCodeError::BadChecksum | ShareCodeError::InvalidEncoding)
+            ));
+        }
+        let payload = base64url_decode(&code).unwrap();
+        assert_eq!(
+            Board::from_share_code(&base64url_encode(&payload[..40])),
+            Err(ShareCodeError::BadChecksum)
+        );
+        assert_eq!(
+            Board::from_share_code(&code.replacen('A', "+", 1)),
+            Err(ShareCodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            Board::from_share_code("AAAA"),
+            Err(ShareCodeError::InvalidEncoding)
+        );
+
+        let mut payload = payload.clone();
+        payload[0] = 2;
+        let crc = crc32(&payload[..PAYLOAD_LEN - 4]);
+        payload[PAYLOAD_LEN - 4..].copy_from_slice(&crc.to_be_bytes());
+        assert_eq!(
+            Board::from_share_code(&base64url_encode(&payload)),
+            Err(ShareCodeError::UnsupportedVersion(2))
+        );
+
+        // a well-formed code whose first cell is 15.
+        let mut payload = base64url_decode(&code).unwrap();
+        payload[1] |= 0x0f;
+        let crc = crc32(&payload[..PAYLOAD_LEN - 4]);
+        payload[PAYLOAD_LEN - 4..].copy_from_slice(&crc.to_be_bytes());
+        assert_eq!(
+            Board::from_share_code(&base64url_encode(&payload)),
+            Err(ShareCodeError::InvalidCells)
+        );
+    }
+}
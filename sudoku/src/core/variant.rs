@@ -0,0 +1,220 @@
+// sudoku variants, extra constraints checked on top of the classic rules.
+// the guests read a variant next to the puzzle and validate the user's board against it.
+use crate::core::board::Board;
+use crate::core::commitment::{puzzle_hash, sha256};
+extern crate alloc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+// the first byte of a variant's encoding, and what the guests commit as the variant.
+pub const VARIANT_CLASSIC: u8 = 0;
+pub const VARIANT_X: u8 = 1;
+pub const VARIANT_KILLER: u8 = 2;
+
+// a killer cage, the values in `cells` (row-wise indices) are distinct and add up to `sum`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cage {
+    pub sum: u8,
+    pub cells: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Variant {
+    #[default]
+    Classic,
+    // both main diagonals hold 1-9 once each as well.
+    X,
+    Killer(Vec<Cage>),
+}
+
+impl Variant {
+    pub fn kind(&self) -> u8 {
+        match self {
+            Variant::Classic => VARIANT_CLASSIC,
+            Variant::X => VARIANT_X,
+            Variant::Killer(_) => VARIANT_KILLER,
+        }
+    }
+
+    // [kind], followed for killer by [sum, cell count, cells..] per cage.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.kind()];
+        if let Variant::Killer(cages) = self {
+            for cage in cages {
+                bytes.push(cage.sum);
+                bytes.push(cage.cells.len() as u8);
+                bytes.extend_from_slice(&cage.cells);
+            }
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Variant, &'static str> {
+        match bytes {
+            [VARIANT_CLASSIC] => Ok(Variant::Classic),
+            [VARIANT_X] => Ok(Variant::X),
+            [VARIANT_KILLER, cages_bytes @ ..] => {
+                let mut rest = cages_bytes;
+                let mut cages = Vec::new();
+                while let [sum, len, tail @ ..] = rest {
+                    let len = *len as usize;
+                    if tail.len() < len {
+                        return Err("cage runs past the end of the input");
+                    }
+                    let (cells, tail) = tail.split_at(len);
+                    if cells.is_empty() || cells.iter().any(|&cell| cell >= 81) {
+                        return Err("cage cell out of bounds");
+                    }
+                    cages.push(Cage {
+                        sum: *sum,
+                        cells: cells.to_vec(),
+                    });
+                    rest = tail;
+                }
+                if !rest.is_empty() {
+                    return Err("cage runs past the end of the input");
+                }
+                Ok(Variant::Killer(cages))
+            }
+            _ => Err("unknown variant"),
+        }
+    }
+
+    // a solved board under the classic rules that also meets the variant's constraints.
+    pub fn validate(&self, board: &Board) -> bool {
+        if !board.validate() {
+            return false;
+        }
+        match self {
+            Variant::Classic => true,
+            Variant::X => {
+                let main = (0..9).map(|i| board.cells[i * 10]);
+                let anti = (0..9).map(|i| board.cells[i * 8 + 8]);
+                all_distinct(main) && all_distinct(anti)
+            }
+            Variant::Killer(cages) => cages.iter().all(|cage| {
+                let values = cage.cells.iter().map(|&cell| board.cells[cell as usize]);
+                all_distinct(values.clone())
+                    && values.map(|value| value as u32).sum::<u32>() == cage.sum as u32
+            }),
+        }
+    }
+
+    // classic puzzles keep commitment::puzzle_hash. the other variants hash their encoding in
+    // after the cells, a killer puzzle is mostly its cages and barely any givens.
+    pub fn puzzle_hash(&self, board: &Board) -> [u8; 32] {
+        match self {
+            Variant::Classic => puzzle_hash(board),
+            _ => {
+                let mut input = board.cells.to_vec();
+                input.extend_from_slice(&self.to_bytes());
+                sha256(&input)
+            }
+        }
+    }
+}
+
+// no value 1-9 appears twice.
+fn all_distinct(values: impl Iterator<Item = u8>) -> bool {
+    let mut seen = 0u16;
+    for value in values {
+        let bit = 1 << value;
+        if seen & bit != 0 {
+            return false;
+        }
+        seen |= bit;
+    }
+    true
+}
+
+#[cfg(test)]
+mod variant_tests {
+    use super::*;
+
+    fn board(digits: &str) -> Board {
+        let cells: Vec<u8> = digits.bytes().map(|b| b - b'0').collect();
+        Board::from_array(cells.try_into().unwrap()).unwrap()
+    }
+
+    // 1-9 on both diagonals.
+    const X_SOLUTION: &str =
+        "123456789456789123789123456214365897368972514597814632941638275832547961675291348";
+    // repeats 8 on the main diagonal.
+    const CLASSIC_SOLUTION: &str =
+        "927136845134258679568479132271843596653921487489765213712594368896312754345687921";
+
+    #[test]
+    fn test_x_variant() {
+        assert!(Variant::X.validate(&board(X_SOLUTION)));
+        assert!(Variant::Classic.validate(&board(CLASSIC_SOLUTION)));
+        assert!(!Variant::X.validate(&board(CLASSIC_SOLUTION)));
+    }
+
+    #[test]
+    fn test_killer_variant() {
+        let solution = board(X_SOLUTION);
+        // the first row split in two cages, 1+2+3+4 and 5+6+7+8+9.
+        let killer = Variant::Killer(vec![
+            Cage {
+                sum: 10,
+                cells: vec![0, 1, 2, 3],
+            },
+            Cage {
+                sum: 35,
+                cells: vec![4, 5, 6, 7, 8],
+            },
+        ]);
+        assert!(killer.validate(&solution));
+
+        let wrong_sum = Variant::Killer(vec![Cage {
+            sum: 11,
+            cells: vec![0, 1, 2, 3],
+        }]);
+        assert!(!wrong_sum.validate(&solution));
+
+        // 1 + 1 adds up, but repeats a value inside the cage.
+        let repeated = Variant::Killer(vec![Cage {
+            sum: 2,
+            cells: vec![0, 15],
+        }]);
+        assert_eq!(solution.cells[15], 1);
+        assert!(!repeated.validate(&solution));
+    }
+
+    #[test]
+    fn test_variant_bytes() {
+        let killer = Variant::Killer(vec![
+            Cage {
+                sum: 10,
+                cells: vec![0, 1, 2, 3],
+            },
+            Cage {
+                sum: 9,
+                cells: vec![80],
+            },
+        ]);
+        for variant in [Variant::Classic, Variant::X, killer] {
+            assert_eq!(Variant::from_bytes(&variant.to_bytes()), Ok(variant));
+        }
+
+        assert!(Variant::from_bytes(&[]).is_err());
+        assert!(Variant::from_bytes(&[3]).is_err());
+        assert!(Variant::from_bytes(&[VARIANT_X, 0]).is_err());
+        assert!(Variant::from_bytes(&[VARIANT_KILLER, 10, 2, 0]).is_err());
+        assert!(Variant::from_bytes(&[VARIANT_KILLER, 10, 1, 81]).is_err());
+        assert!(Variant::from_bytes(&[VARIANT_KILLER, 10, 0]).is_err());
+    }
+
+    #[test]
+    fn test_variant_puzzle_hash() {
+        let puzzle = Board::from_seed(666, None);
+        assert_eq!(Variant::Classic.puzzle_hash(&puzzle), puzzle_hash(&puzzle));
+        assert_ne!(Variant::X.puzzle_hash(&puzzle), puzzle_hash(&puzzle));
+        assert_ne!(
+            Variant::X.puzzle_hash(&puzzle),
+            Variant::Killer(vec![]).puzzle_hash(&puzzle)
+        );
+    }
+}
@@ -0,0 +1,219 @@
+// the board logic for native apps and game engines (Unity, Godot, iOS and Android), over a C
+// ABI. boards are buffers of 81 bytes, row-wise, 0 for empty, and difficulties the tier the
+// proofs commit (1 easy, 2 medium, 3 hard). every function returns SUDOKU_OK or one of the
+// negative codes below, validate and apply_moves a count or a verdict on success. the
+// declarations are in include/sudoku.h.
+//
+//     uint8_t puzzle[81], solution[81];
+//     sudoku_generate(42, 2, puzzle);
+//     sudoku_solve(puzzle, solution);
+//
+// built with `cargo rustc --lib --release --features ffi --crate-type cdylib` (or staticlib for
+// iOS), the crate stays an rlib for the guests.
+extern crate std;
+
+use core::slice;
+
+use crate::core::board::{Board, Difficulty};
+use crate::core::solver::DancingLinks;
+
+pub const SUDOKU_OK: i32 = 0;
+// a buffer pointer is null.
+pub const SUDOKU_NULL: i32 = -1;
+// the difficulty isn't 1, 2 or 3.
+pub const SUDOKU_BAD_DIFFICULTY: i32 = -2;
+// a cell above 9.
+pub const SUDOKU_BAD_BOARD: i32 = -3;
+// the seed doesn't generate a puzzle, or the puzzle has no solution.
+pub const SUDOKU_UNSOLVABLE: i32 = -4;
+// moves is null with a count, or the count doesn't fit in the returned int.
+pub const SUDOKU_BAD_MOVES: i32 = -5;
+
+/// Writes the puzzle the guests regenerate from `seed` into `out`.
+///
+/// # Safety
+///
+/// `out` is null or points to 81 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_generate(seed: u32, difficulty: u8, out: *mut u8) -> i32 {
+    let Some(difficulty) = Difficulty::from_u8(difficulty) else {
+        return SUDOKU_BAD_DIFFICULTY;
+    };
+    match Board::try_from_seed(seed, Some(difficulty)) {
+        Ok(board) => write(out, &board),
+        Err(_) => SUDOKU_UNSOLVABLE,
+    }
+}
+
+/// Writes the dancing links solution of `puzzle` into `out`.
+///
+/// # Safety
+///
+/// `puzzle` is null or points to 81 readable bytes, `out` is null or points to 81 writable
+/// bytes. they may be the same buffer.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_solve(puzzle: *const u8, out: *mut u8) -> i32 {
+    let puzzle = match read(puzzle) {
+        Ok(puzzle) => puzzle,
+        Err(code) => return code,
+    };
+    match DancingLinks::solve_board(&puzzle) {
+        Ok(solution) => write(out, &solution),
+        Err(_) => SUDOKU_UNSOLVABLE,
+    }
+}
+
+/// 1 when `board` is complete and follows the rules, what the guests check before committing
+/// valid, 0 when it isn't.
+///
+/// # Safety
+///
+/// `board` is null or points to 81 readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_validate(board: *const u8) -> i32 {
+    match read(board) {
+        Ok(board) => board.validate() as i32,
+        Err(code) => code,
+    }
+}
+
+/// Plays `count` moves of two bytes each, cell (0-80) then value (0 erases), on `board` with
+/// the rules of the session transcript guest: a move can't touch a given of `puzzle` or repeat
+/// a value in its row, column or box. stops at the first illegal move and returns how many were
+/// played, `count` when they all were.
+///
+/// # Safety
+///
+/// `puzzle` is null or points to 81 readable bytes, `board` is null or points to 81 writable
+/// bytes, `moves` is null or points to `2 * count` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sudoku_apply_moves(
+    puzzle: *const u8,
+    board: *mut u8,
+    moves: *const u8,
+    count: usize,
+) -> i32 {
+    let (puzzle, mut current) = match (read(puzzle), read(board)) {
+        (Ok(puzzle), Ok(current)) => (puzzle, current),
+        (Err(code), _) | (_, Err(code)) => return code,
+    };
+    let count = match i32::try_from(count) {
+        Ok(count) if !moves.is_null() || count == 0 => count,
+        _ => return SUDOKU_BAD_MOVES,
+    };
+    let moves = match count {
+        0 => &[][..],
+        _ => slice::from_raw_parts(moves, 2 * count as usize),
+    };
+    let played = moves
+        .chunks(2)
+        .take_while(|m| play_move(&puzzle, &mut current, m[0], m[1]))
+        .count();
+    write(board, &current);
+    played as i32
+}
+
+fn play_move(puzzle: &Board, board: &mut Board, cell: u8, value: u8) -> bool {
+    let cell = cell as usize;
+    if cell >= 81 || value > 9 || puzzle.cells[cell] != 0 {
+        return false;
+    }
+    if value != 0 && !board.can_place(cell, value) {
+        return false;
+    }
+    board.cells[cell] = value;
+    true
+}
+
+unsafe fn read(cells: *const u8) -> Result<Board, i32> {
+    if cells.is_null() {
+        return Err(SUDOKU_NULL);
+    }
+    let cells: [u8; 81] = slice::from_raw_parts(cells, 81).try_into().unwrap();
+    Board::from_array(cells).map_err(|_| SUDOKU_BAD_BOARD)
+}
+
+unsafe fn write(out: *mut u8, board: &Board) -> i32 {
+    if out.is_null() {
+        return SUDOKU_NULL;
+    }
+    slice::from_raw_parts_mut(out, 81).copy_from_slice(&board.cells);
+    SUDOKU_OK
+}
+
+#[cfg(test)]
+mod ffi_tests {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn test_ffi() {
+        let (mut puzzle, mut solution) = ([0u8; 81], [0u8; 81]);
+        unsafe {
+            assert_eq!(sudoku_generate(42, 2, puzzle.as_mut_ptr()), SUDOKU_OK);
+            assert_eq!(puzzle, Board::from_seed(42, Some(Difficulty::Medium)).cells);
+            assert_eq!(
+                sudoku_generate(42, 4, puzzle.as_mut_ptr()),
+                SUDOKU_BAD_DIFFICULTY
+            );
+            assert_eq!(sudoku_validate(puzzle.as_ptr()), 0);
+
+            assert_eq!(
+                sudoku_solve(puzzle.as_ptr(), solution.as_mut_ptr()),
+                SUDOKU_OK
+            );
+            assert_eq!(sudoku_validate(solution.as_ptr()), 1);
+            assert_eq!(
+                sudoku_solve(ptr::null(), solution.as_mut_ptr()),
+                SUDOKU_NULL
+            );
+            let mut bad = puzzle;
+            bad[0] = 10;
+            assert_eq!(sudoku_validate(bad.as_ptr()), SUDOKU_BAD_BOARD);
+        }
+    }
+
+    #[test]
+    fn test_apply_moves() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Medium));
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let open: std::vec::Vec<usize> = (0..81).filter(|&i| puzzle.cells[i] == 0).collect();
+        let given = (0..81).find(|&i| puzzle.cells[i] != 0).unwrap();
+
+        let mut moves: std::vec::Vec<u8> = open
+            .iter()
+            .flat_map(|&i| [i as u8, solution.cells[i]])
+            .collect();
+        let mut board = puzzle.cells;
+        let count = moves.len() / 2;
+        unsafe {
+            let played = sudoku_apply_moves(
+                puzzle.cells.as_ptr(),
+                board.as_mut_ptr(),
+                moves.as_ptr(),
+                count,
+            );
+            assert_eq!(played, count as i32);
+            assert_eq!(sudoku_validate(board.as_ptr()), 1);
+        }
+
+        // the third move overwrites a given, the board keeps the first two.
+        moves[4] = given as u8;
+        let mut board = puzzle.cells;
+        unsafe {
+            let played = sudoku_apply_moves(
+                puzzle.cells.as_ptr(),
+                board.as_mut_ptr(),
+                moves.as_ptr(),
+                count,
+            );
+            assert_eq!(played, 2);
+            assert_eq!(
+                sudoku_apply_moves(puzzle.cells.as_ptr(), board.as_mut_ptr(), ptr::null(), 0),
+                0
+            );
+        }
+        assert_eq!(board[open[1]], solution.cells[open[1]]);
+        assert_eq!(board[open[2]], 0);
+    }
+}
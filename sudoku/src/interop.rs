@@ -0,0 +1,81 @@
+// conversions between Board and the crates.io sudoku crate's Sudoku, so its generator and
+// solver can cross-check this crate's before a board goes into a proof. both are 81 bytes,
+// row-wise, 0 for empty. a Sudoku always holds values 0-9, a Board only does when it was checked
+// (Board::from_array), so only that direction can fail.
+//
+//     let theirs = sudoku_crate::Sudoku::generate();
+//     let solution = theirs.solution().map(|solution| Board::from(&solution));
+//     assert_eq!(DancingLinks::solve_board(&Board::from(&theirs)).ok(), solution);
+use sudoku_crate::Sudoku;
+
+use crate::core::board::Board;
+use crate::core::error::SudokuError;
+
+impl From<&Sudoku> for Board {
+    fn from(sudoku: &Sudoku) -> Self {
+        Board {
+            cells: sudoku.to_bytes(),
+        }
+    }
+}
+
+impl TryFrom<&Board> for Sudoku {
+    type Error = SudokuError;
+
+    fn try_from(board: &Board) -> Result<Self, SudokuError> {
+        Sudoku::from_bytes(board.cells).map_err(|_| SudokuError::InvalidValue)
+    }
+}
+
+#[cfg(test)]
+mod interop_tests {
+    use super::*;
+    use crate::core::board::Difficulty;
+    use crate::core::solver::DancingLinks;
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Medium));
+        let sudoku = Sudoku::try_from(&puzzle).unwrap();
+        assert_eq!(Board::from(&sudoku), puzzle);
+
+        let mut bad = puzzle.clone();
+        bad.cells[0] = 10;
+        assert!(Sudoku::try_from(&bad).is_err());
+    }
+
+    // the two solvers agree on this crate's puzzles, and this crate's solver on theirs.
+    #[test]
+    fn test_agrees_with_sudoku_crate() {
+        for seed in [42, 666, 2200] {
+            for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+                let puzzle = Board::from_seed(seed, Some(difficulty));
+                let sudoku = Sudoku::try_from(&puzzle).unwrap();
+                assert_eq!(
+                    DancingLinks::count_solutions(&puzzle, 2),
+                    Ok(sudoku.count_at_most(2))
+                );
+                // solution() is only there for a unique one.
+                if let Some(theirs) = sudoku.solution() {
+                    assert_eq!(
+                        DancingLinks::solve_board(&puzzle).unwrap(),
+                        Board::from(&theirs)
+                    );
+                }
+            }
+        }
+        for _ in 0..5 {
+            let sudoku = Sudoku::generate();
+            let solution = Board::from(&sudoku.solution().unwrap());
+            assert!(solution.validate());
+            assert_eq!(
+                DancingLinks::solve_board(&Board::from(&sudoku)).unwrap(),
+                solution
+            );
+            assert_eq!(
+                DancingLinks::count_solutions(&Board::from(&sudoku), 2),
+                Ok(1)
+            );
+        }
+    }
+}
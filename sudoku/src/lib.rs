@@ -1,10 +1,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#[cfg(feature = "rkyv")]
+pub mod archive;
 pub mod core;
-
-#[cfg(not(feature = "std"))]
-#[macro_export]
-macro_rules! println {
-    ($($arg:tt)*) => {
-        // No-op in no_std or custom implementation
-    };
-}
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "sudoku-crate")]
+pub mod interop;
+pub mod log;
+#[cfg(feature = "qr")]
+pub mod qr;
+#[cfg(feature = "wasm")]
+pub mod wasm;
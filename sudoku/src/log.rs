@@ -0,0 +1,74 @@
+// logging facade, usable the same way from std builds and from the zkVM guests.
+// `log!` and `trace!` take the same arguments as println!, where the line ends up depends on
+// the features:
+//   std         printed to stdout
+//   custom-log  handed to a `sudoku_log(line: &str)` function the guest defines, same idea as
+//               custom-sha256 (e.g. forwarding to openvm::io::println)
+//   neither     dropped without ever being formatted, so guests don't pay for it
+// `trace!` is for the noisy solver internals and stays silent unless the trace feature is on.
+#[cfg(all(feature = "custom-log", not(feature = "std")))]
+extern crate alloc;
+
+use core::fmt::Arguments;
+
+#[cfg(all(feature = "custom-log", not(feature = "std")))]
+extern "Rust" {
+    fn sudoku_log(line: &str);
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn write(args: Arguments) {
+    #[cfg(feature = "std")]
+    std::println!("{}", args);
+    // the guest has to define sudoku_log, otherwise this fails to link.
+    #[cfg(all(feature = "custom-log", not(feature = "std")))]
+    unsafe {
+        sudoku_log(&alloc::format!("{}", args))
+    }
+    #[cfg(not(any(feature = "std", feature = "custom-log")))]
+    let _ = args;
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub fn write_trace(args: Arguments) {
+    #[cfg(feature = "trace")]
+    write(args);
+    #[cfg(not(feature = "trace"))]
+    let _ = args;
+}
+
+#[macro_export]
+macro_rules! log {
+    ($($arg:tt)*) => {
+        $crate::log::write(format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::write_trace(format_args!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+#[cfg(not(any(feature = "std", feature = "custom-log")))]
+mod log_tests {
+    use core::fmt::{self, Display};
+
+    struct Unformattable;
+
+    impl Display for Unformattable {
+        fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+            panic!("formatted without a sink");
+        }
+    }
+
+    #[test]
+    fn test_no_sink_skips_formatting() {
+        crate::log!("{}", Unformattable);
+        crate::trace!("{}", Unformattable);
+    }
+}
@@ -1,28 +1,1744 @@
+use std::io::{BufRead, Read, Write};
+
+use sudoku::core::backtrack::Backtracker;
 use sudoku::core::board::{Board, Difficulty};
-use sudoku::core::solver::DancingLinks;
+use sudoku::core::canonical::canonical_form;
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::daily::{daily_puzzle, daily_seed, Date, DAILY_DIFFICULTY};
+use sudoku::core::logic::{grade, solve_logically, LogicSolver};
+use sudoku::core::puzzle_id::PuzzleId;
+use sudoku::core::solver::{DancingLinks, SearchEvent, SolveStep, SolveStepper};
+use sudoku::core::variant::Variant;
+
+#[cfg(feature = "pdf")]
+mod pdf;
+
+const USAGE: &str = "usage:
+  sudoku [play] [--seed N] [--difficulty easy|medium|hard]
+  sudoku generate [--count N] [--seed N] [--difficulty easy|medium|hard] [--format line|json]
+                  [--seeds] [--solutions] [--archive FILE.rkyv]
+  sudoku print [--count N] [--seed N] [--difficulty easy|medium|hard] [--out FILE.txt|FILE.pdf]
+  sudoku qr [FILE|-] --out FILE.png
+  sudoku scan IMAGE
+  sudoku solve [FILE|-] [--count] [--limit N]
+  sudoku rate [FILE|-]
+  sudoku check [FILE|-]
+  sudoku hint [FILE|-]
+  sudoku canonicalize [FILE|-]
+  sudoku bench [FILE|-] [--runs N]
+  sudoku daily [--date YYYY-MM-DD]
+  sudoku import FILE.sdk|FILE.sdm|FILE [--format json|line] [--check] [--solutions]
+  sudoku prove --puzzle-id ID [--solution FILE] [--backend sp1|openvm] [zk-sudoku prove options]
+  sudoku verify FILE.zkproof|FILE [--puzzle FILE|--puzzle-id ID] [zk-sudoku verify options]
+  sudoku crosscheck [FILE|-]
+  sudoku stats [--seeds A..B] [--difficulty easy|medium|hard]
+  sudoku watch [FILE|-] [--delay MS] [--limit N]";
+
+// grids side by side on a printed sheet.
+const SHEET_COLUMNS: usize = 2;
+
+// the puzzles bench solves unless it's given a file.
+const CORPUS: &str = include_str!("../corpus.txt");
+
+// the solvers bench compares. each returns the solution and how many search nodes it took.
+type Backend = fn(&Board) -> Result<(Board, usize), String>;
+const BACKENDS: [(&str, Backend); 3] =
+    [("dlx", dlx), ("backtrack", backtrack), ("logic+dlx", logic)];
+
+// solve --count stops counting here unless --limit says otherwise.
+const COUNT_LIMIT: usize = 1000;
+
+const HELP: &str = "commands:
+  r c v      put v (1-9) in row r, column c (rows and columns 1-9)
+  r c 0      erase row r, column c (also: e r c)
+  show       print the board again
+  help       print this
+  quit       give up";
 
-// basic usage
 fn main() {
-    let mut board = Board::from_seed(666, Some(Difficulty::Medium));
-    println!("Board generated! {}", board);
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("play") => play(&args[1..]),
+        Some("generate") => generate(&args[1..]),
+        Some("print") => print(&args[1..]),
+        Some("solve") => solve(&args[1..]),
+        Some("rate") => rate(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("hint") => hint(&args[1..]),
+        Some("canonicalize") => canonicalize(&args[1..]),
+        Some("qr") => qr(&args[1..]),
+        Some("scan") => scan(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("daily") => daily(&args[1..]),
+        Some("import") => import(&args[1..]),
+        Some("prove") => prove(&args[1..]),
+        Some("verify") => verify(&args[1..]),
+        Some("crosscheck") => crosscheck(&args[1..]),
+        Some("stats") => stats(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some(arg) if !arg.starts_with('-') => Err(format!("unknown command '{}'\n{}", arg, USAGE)),
+        _ => play(&args),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Place { cell: usize, value: u8 },
+    Erase { cell: usize },
+    Show,
+    Help,
+    Quit,
+}
+
+// a seeded puzzle played in the terminal. every move is checked against the cells already
+// filled in (Board::can_place), so the board never holds a conflict and is solved as soon as
+// it's full.
+fn play(args: &[String]) -> Result<(), String> {
+    let (seed, difficulty) = parse_play_args(args)?;
+    let puzzle = Board::try_from_seed(seed, Some(difficulty))
+        .map_err(|e| format!("can't generate seed {} ({:?})", seed, e))?;
+    let mut board = puzzle.clone();
+    println!(
+        "seed {}, {:?}, {} clues",
+        seed,
+        difficulty,
+        puzzle.clue_count()
+    );
+    println!("{}", render(&board, &puzzle));
+    println!("{}", HELP);
+
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        std::io::stdout().flush().map_err(|e| e.to_string())?;
+        let Some(line) = lines.next() else {
+            // end of input, same as quitting.
+            println!();
+            return Ok(());
+        };
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let command = match parse_command(&line) {
+            Ok(command) => command,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        match command {
+            Command::Place { cell, value } => {
+                if puzzle.cells[cell] != 0 {
+                    println!("{} is a given", position(cell));
+                    continue;
+                }
+                if !board.can_place(cell, value) {
+                    println!(
+                        "{} can't go in {}, it's already in its row, column or box",
+                        value,
+                        position(cell)
+                    );
+                    continue;
+                }
+                board.cells[cell] = value;
+                println!("{}", render(&board, &puzzle));
+                if board.validate() {
+                    println!("solved!");
+                    return Ok(());
+                }
+            }
+            Command::Erase { cell } => {
+                if puzzle.cells[cell] != 0 {
+                    println!("{} is a given", position(cell));
+                    continue;
+                }
+                board.cells[cell] = 0;
+                println!("{}", render(&board, &puzzle));
+            }
+            Command::Show => println!("{}", render(&board, &puzzle)),
+            Command::Help => println!("{}", HELP),
+            Command::Quit => {
+                let left = board.cells.iter().filter(|&&value| value == 0).count();
+                println!("{} cells left", left);
+                return Ok(());
+            }
+        }
+    }
+}
+
+// --seed defaults to the clock, --difficulty to medium.
+fn parse_play_args(args: &[String]) -> Result<(u32, Difficulty), String> {
+    let mut seed = None;
+    let mut difficulty = Difficulty::Medium;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => seed = Some(parse_number(value(arg, &mut args)?)?),
+            "--difficulty" => difficulty = parse_difficulty(value(arg, &mut args)?)?,
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    Ok((seed.unwrap_or_else(clock_seed), difficulty))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Format {
+    // one puzzle per line as 81 digits (0 for empty cells), preceded by its seed and followed
+    // by its solution when they're asked for, separated by spaces.
+    Line,
+    // one JSON object per line, {"seed", "difficulty", "puzzle", "solution"} with the boards
+    // as digit strings. the host's batch command reads it as is.
+    Json,
+}
+
+// puzzles for other programs and datasets: --count seeded puzzles, from --seed on. with
+// --archive they go into an rkyv archive instead, with their seeds and solutions.
+fn generate(args: &[String]) -> Result<(), String> {
+    let mut count = 1;
+    let mut seed = None;
+    let mut difficulty = Difficulty::Medium;
+    let mut format = Format::Line;
+    let (mut seeds, mut solutions) = (false, false);
+    let mut archive = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--count" => count = parse_number(value(arg, &mut args)?)?,
+            "--seed" => seed = Some(parse_number(value(arg, &mut args)?)?),
+            "--difficulty" => difficulty = parse_difficulty(value(arg, &mut args)?)?,
+            "--format" => {
+                format = match value(arg, &mut args)? {
+                    "line" => Format::Line,
+                    "json" => Format::Json,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--seeds" => seeds = true,
+            "--solutions" => solutions = true,
+            "--archive" => archive = Some(value(arg, &mut args)?),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+
+    let first = seed.unwrap_or_else(clock_seed);
+    if let Some(path) = archive {
+        return generate_archive(path, first, count, difficulty);
+    }
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    for i in 0..count {
+        let seed = first.wrapping_add(i);
+        let puzzle = Board::try_from_seed(seed, Some(difficulty))
+            .map_err(|e| format!("can't generate seed {} ({:?})", seed, e))?;
+        let solution = match solutions {
+            true => Some(
+                DancingLinks::solve_board(&puzzle)
+                    .map_err(|e| format!("can't solve seed {} ({})", seed, e))?,
+            ),
+            false => None,
+        };
+        let seed = seeds.then_some(seed);
+        let line = puzzle_line(&format, seed, difficulty, &puzzle, solution.as_ref());
+        writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "rkyv")]
+fn generate_archive(
+    path: &str,
+    first: u32,
+    count: u32,
+    difficulty: Difficulty,
+) -> Result<(), String> {
+    use sudoku::archive::{PuzzleArchive, PuzzleRecord};
+    let mut records = Vec::with_capacity(count as usize);
+    for i in 0..count {
+        let seed = first.wrapping_add(i);
+        let puzzle = Board::try_from_seed(seed, Some(difficulty))
+            .map_err(|e| format!("can't generate seed {} ({:?})", seed, e))?;
+        let solution = DancingLinks::solve_board(&puzzle)
+            .map_err(|e| format!("can't solve seed {} ({})", seed, e))?;
+        records.push(PuzzleRecord::new(seed, difficulty, &puzzle, &solution));
+    }
+    let bytes = PuzzleArchive::new(records).to_bytes();
+    std::fs::write(path, &bytes).map_err(|e| format!("{}: {}", path, e))?;
+    println!("{} puzzles in {}", count, path);
+    Ok(())
+}
+
+#[cfg(not(feature = "rkyv"))]
+fn generate_archive(_: &str, _: u32, _: u32, _: Difficulty) -> Result<(), String> {
+    Err("archives need the rkyv feature (cargo build --features rkyv)".into())
+}
+
+// one puzzle in the --format, the seed and solution only when they're given.
+fn puzzle_line(
+    format: &Format,
+    seed: Option<u32>,
+    difficulty: Difficulty,
+    puzzle: &Board,
+    solution: Option<&Board>,
+) -> String {
+    match format {
+        Format::Line => {
+            let mut fields = Vec::new();
+            fields.extend(seed.map(|seed| seed.to_string()));
+            fields.push(digits(puzzle));
+            fields.extend(solution.map(digits));
+            fields.join(" ")
+        }
+        Format::Json => {
+            let mut fields = Vec::new();
+            fields.extend(seed.map(|seed| format!("\"seed\":{}", seed)));
+            fields.push(format!(
+                "\"difficulty\":\"{}\"",
+                format!("{:?}", difficulty).to_lowercase()
+            ));
+            fields.push(format!("\"puzzle\":\"{}\"", digits(puzzle)));
+            if let Some(solution) = solution {
+                fields.push(format!("\"solution\":\"{}\"", digits(solution)));
+            }
+            format!("{{{}}}", fields.join(","))
+        }
+    }
+}
+
+// a sheet of --count puzzles to solve on paper, printed or written to --out: ascii text, or a
+// pdf when the file ends in .pdf and the pdf feature is on. each grid is titled with its puzzle
+// id, which regenerates the same board to prove the solution against.
+fn print(args: &[String]) -> Result<(), String> {
+    let mut count = 4;
+    let mut seed = None;
+    let mut difficulty = Difficulty::Medium;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--count" => count = parse_number(value(arg, &mut args)?)?,
+            "--seed" => seed = Some(parse_number(value(arg, &mut args)?)?),
+            "--difficulty" => difficulty = parse_difficulty(value(arg, &mut args)?)?,
+            "--out" => out = Some(value(arg, &mut args)?),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+
+    let first = seed.unwrap_or_else(clock_seed);
+    let puzzles = (0..count)
+        .map(|i| {
+            let id = PuzzleId::new(first.wrapping_add(i), difficulty, &Variant::Classic)
+                .map_err(|e| format!("{:?}", e))?;
+            let puzzle = id
+                .puzzle()
+                .map_err(|e| format!("can't generate seed {} ({:?})", id.seed, e))?;
+            Ok((id, puzzle))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let Some(path) = out else {
+        print!("{}", sheet(&puzzles));
+        return Ok(());
+    };
+    let bytes = match path.to_lowercase().ends_with(".pdf") {
+        #[cfg(feature = "pdf")]
+        true => pdf::sheet(&puzzles),
+        #[cfg(not(feature = "pdf"))]
+        true => return Err("pdf sheets need the pdf feature (cargo build --features pdf)".into()),
+        false => sheet(&puzzles).into_bytes(),
+    };
+    std::fs::write(path, bytes).map_err(|e| format!("{}: {}", path, e))?;
+    println!("{} puzzles in {}", count, path);
+    Ok(())
+}
+
+// "3. medium, zk1-...", above each grid of a sheet.
+fn title(index: usize, id: &PuzzleId) -> String {
+    format!(
+        "{}. {}, {}",
+        index + 1,
+        format!("{:?}", id.difficulty).to_lowercase(),
+        id.encode()
+    )
+}
+
+// the puzzles SHEET_COLUMNS to a row, each grid under its title.
+fn sheet(puzzles: &[(PuzzleId, Board)]) -> String {
+    let mut out = String::new();
+    for (row, chunk) in puzzles.chunks(SHEET_COLUMNS).enumerate() {
+        if row > 0 {
+            out.push('\n');
+        }
+        let blocks: Vec<Vec<String>> = chunk
+            .iter()
+            .enumerate()
+            .map(|(i, (id, puzzle))| {
+                let mut lines = vec![title(row * SHEET_COLUMNS + i, id), String::new()];
+                lines.extend(grid(puzzle));
+                lines
+            })
+            .collect();
+        for line in 0..blocks[0].len() {
+            let line = blocks
+                .iter()
+                .map(|block| format!("{:<31}", block[line]))
+                .collect::<Vec<_>>()
+                .join("    ");
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+    }
+    out
+}
+
+// a grid for paper: 31 characters wide, empty cells as dots.
+fn grid(puzzle: &Board) -> Vec<String> {
+    grid_of(|cell| match puzzle.cells[cell] {
+        0 => " . ".into(),
+        value => format!(" {} ", value),
+    })
+}
+
+// a grid of the three characters cell gives each cell.
+fn grid_of(cell: impl Fn(usize) -> String) -> Vec<String> {
+    let rule = "+---------+---------+---------+".to_string();
+    let mut lines = Vec::new();
+    for row in 0..9 {
+        if row % 3 == 0 {
+            lines.push(rule.clone());
+        }
+        let mut line = String::from("|");
+        for col in 0..9 {
+            line.push_str(&cell(row * 9 + col));
+            if col % 3 == 2 {
+                line.push('|');
+            }
+        }
+        lines.push(line);
+    }
+    lines.push(rule);
+    lines
+}
 
+// print the solution of a puzzle read from a file (or stdin) as 9 rows of digits. a puzzle
+// with no solution or more than one is an error, --count prints how many it has instead.
+fn solve(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut count = false;
+    let mut limit = COUNT_LIMIT;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--count" => count = true,
+            "--limit" => limit = parse_number(value(arg, &mut args)?)? as usize,
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let puzzle = read_board(path)?;
+
+    if count {
+        // --limit 0 would never stop.
+        let solutions = DancingLinks::count_solutions(&puzzle, limit.max(1))?;
+        match solutions >= limit.max(1) {
+            true => println!("{} or more solutions", solutions),
+            false => println!("{} solutions", solutions),
+        }
+        return Ok(());
+    }
+    match DancingLinks::count_solutions(&puzzle, 2)? {
+        0 => return Err("the puzzle has no solution".into()),
+        1 => {}
+        _ => return Err("the puzzle has more than one solution, see --count".into()),
+    }
+    let solution = DancingLinks::solve_board(&puzzle)?;
+    for row in solution.cells.chunks(9) {
+        println!(
+            "{}",
+            row.iter()
+                .map(|value| value.to_string())
+                .collect::<String>()
+        );
+    }
+    Ok(())
+}
+
+// grade a puzzle by the hardest technique it takes to solve by hand (core::logic).
+fn rate(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let puzzle = read_board(path)?;
+    // a grade is only meaningful for a puzzle with one solution to find.
+    match DancingLinks::count_solutions(&puzzle, 2)? {
+        0 => return Err("the puzzle has no solution".into()),
+        1 => {}
+        _ => return Err("the puzzle has more than one solution".into()),
+    }
+    let grade = grade(&puzzle)?;
+    match grade.difficulty {
+        Some(difficulty) => println!("difficulty: {}", format!("{:?}", difficulty).to_lowercase()),
+        None => println!("difficulty: beyond hard, the grader's techniques don't solve it"),
+    }
+    match (grade.hardest, grade.difficulty) {
+        (Some(technique), Some(_)) => println!("hardest technique: {}", technique.name()),
+        (Some(technique), None) => {
+            println!("hardest technique: {}, then guessing", technique.name())
+        }
+        (None, _) => println!("hardest technique: none, the puzzle is already full"),
+    }
+    println!("clues: {}", grade.clue_count);
+    Ok(())
+}
+
+// list everything wrong with a filled or partial grid, an error if there's anything.
+fn check(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let board = read_board(path)?;
+    let violations = board.violations();
+    for violation in &violations {
+        println!("{}", violation);
+    }
+    let empty = 81 - board.clue_count() as usize;
+    match (violations.len(), empty) {
+        (0, 0) => println!("solved"),
+        (0, empty) => println!("no violations so far, {} cells empty", empty),
+        (count, _) => return Err(format!("{} violations", count)),
+    }
+    Ok(())
+}
+
+// the next move a person could make on the grid, found by core::logic's simplest technique
+// that applies.
+fn hint(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let board = read_board(path)?;
+    if board.validate() {
+        println!("solved, no moves left");
+        return Ok(());
+    }
+    // a wrong value that doesn't repeat anything yet still leads the techniques astray.
+    if DancingLinks::count_solutions(&board, 1)? == 0 {
+        return Err("the grid can't be completed anymore, see check".into());
+    }
+    match LogicSolver::new(&board)?.next_step() {
+        Some(step) => println!("{}", step),
+        None => println!("none of the techniques applies, the next move takes guessing"),
+    }
+    Ok(())
+}
+
+// the canonical form (core::canonical) of a puzzle, as one line of digits, and its hash.
+fn canonicalize(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let puzzle = read_board(path)?;
+    let canonical = canonical_form(&puzzle);
+    println!("canonical form: {}", digits(&canonical));
+    println!("canonical hash: 0x{}", hex(&puzzle_hash(&canonical)));
+    Ok(())
+}
+
+// a board's share code as a qr code (sudoku::qr), saved as a png to print or send.
+#[cfg(feature = "qr")]
+fn qr(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(value(arg, &mut args)?),
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let out = out.ok_or(format!("qr needs --out FILE.png\n{}", USAGE))?;
+    let board = read_board(path)?;
+    board
+        .to_qr_image(8)
+        .save(out)
+        .map_err(|e| format!("{}: {}", out, e))?;
+    println!("share code: {}", board.to_share_code());
+    println!("qr code in {}", out);
+    Ok(())
+}
+
+// the boards in the qr codes of an image, a photo of a printed sheet or a png from qr, one
+// line of digits each. codes that don't read are reported, and fail the command if none does.
+#[cfg(feature = "qr")]
+fn scan(args: &[String]) -> Result<(), String> {
+    let [path] = args else {
+        return Err(format!("scan takes one image\n{}", USAGE));
+    };
+    let image = image::open(path)
+        .map_err(|e| format!("{}: {}", path, e))?
+        .to_luma8();
+    let results = sudoku::qr::boards_from_qr_image(&image);
+    if results.is_empty() {
+        return Err(format!("{}: no qr code found", path));
+    }
+    let mut read = 0;
+    for result in results {
+        match result {
+            Ok(board) => {
+                println!("{}", digits(&board));
+                read += 1;
+            }
+            Err(e) => eprintln!("unreadable code ({:?})", e),
+        }
+    }
+    match read {
+        0 => Err(format!("{}: no code read as a board", path)),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(not(feature = "qr"))]
+fn qr(_: &[String]) -> Result<(), String> {
+    Err("qr codes need the qr feature (cargo build --features qr)".into())
+}
+
+#[cfg(not(feature = "qr"))]
+fn scan(_: &[String]) -> Result<(), String> {
+    Err("qr codes need the qr feature (cargo build --features qr)".into())
+}
+
+// the puzzle of the day (core::daily), today's in utc unless --date says otherwise, with the
+// commitment to it the guests expose as puzzle_hash.
+fn daily(args: &[String]) -> Result<(), String> {
+    let mut date = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--date" => {
+                let text = value(arg, &mut args)?;
+                date = Some(
+                    Date::parse(text)
+                        .ok_or_else(|| format!("'{}' isn't a date (YYYY-MM-DD)", text))?,
+                );
+            }
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let date = match date {
+        Some(date) => date,
+        None => {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_err(|e| e.to_string())?;
+            Date::from_days((now.as_secs() / 86_400) as u32)
+        }
+    };
+    let puzzle = daily_puzzle(&date).map_err(|e| format!("can't generate the puzzle ({:?})", e))?;
+    let id = PuzzleId::new(daily_seed(&date), DAILY_DIFFICULTY, &Variant::Classic)
+        .map_err(|e| format!("{:?}", e))?;
+    println!(
+        "puzzle of {}, {}, seed {}, {}",
+        date,
+        format!("{:?}", DAILY_DIFFICULTY).to_lowercase(),
+        id.seed,
+        id.encode()
+    );
+    for line in grid(&puzzle) {
+        println!("{}", line);
+    }
+    println!("puzzle: {}", digits(&puzzle));
+    println!("puzzle hash: 0x{}", hex(&puzzle_hash(&puzzle)));
+    Ok(())
+}
+
+// puzzles from other programs: a .sdk file (sadman sudoku, one puzzle as 9 rows of digits and
+// dots, # lines for metadata), or a .sdm or any other file of one 81 character puzzle per line.
+// printed one per line as {"name", "puzzle", "solution"} objects, what the host's batch command
+// reads, or with --format line as digits. --check leaves out (and reports) the puzzles without
+// exactly one solution, --solutions checks too and adds the solution.
+fn import(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut json = true;
+    let (mut check, mut solutions) = (false, false);
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => {
+                json = match value(arg, &mut args)? {
+                    "json" => true,
+                    "line" => false,
+                    other => return Err(format!("unknown format '{}'", other)),
+                }
+            }
+            "--check" => check = true,
+            "--solutions" => solutions = true,
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let path = std::path::Path::new(path.ok_or(format!("import needs a FILE\n{}", USAGE))?);
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let puzzles = import_puzzles(path, &text).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    let mut left_out = 0;
+    for (name, puzzle) in &puzzles {
+        if check || solutions {
+            let count = DancingLinks::count_solutions(puzzle, 2)?;
+            if count != 1 {
+                let count = if count == 0 { "no" } else { "more than one" };
+                eprintln!("{}: {} solution, left out", name, count);
+                left_out += 1;
+                continue;
+            }
+        }
+        let solution = match solutions {
+            true => Some(DancingLinks::solve_board(puzzle)?),
+            false => None,
+        };
+        let line = match json {
+            true => {
+                let mut fields = vec![
+                    format!("\"name\":{}", json_string(name)),
+                    format!("\"puzzle\":\"{}\"", digits(puzzle)),
+                ];
+                fields.extend(
+                    solution.map(|solution| format!("\"solution\":\"{}\"", digits(&solution))),
+                );
+                format!("{{{}}}", fields.join(","))
+            }
+            false => {
+                let mut fields = vec![digits(puzzle)];
+                fields.extend(solution.map(|solution| digits(&solution)));
+                fields.join(" ")
+            }
+        };
+        writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+    }
+    out.flush().map_err(|e| e.to_string())?;
+    eprintln!(
+        "imported {} of {} puzzles",
+        puzzles.len() - left_out,
+        puzzles.len()
+    );
+    Ok(())
+}
+
+// the puzzles of a file in the format its extension names, each named after the file (and
+// numbered when there are several).
+fn import_puzzles(path: &std::path::Path, text: &str) -> Result<Vec<(String, Board)>, String> {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    if extension.as_deref() == Some("sdk") {
+        // the rows of the [Puzzle] section when there is one (sadman's later files also have
+        // the player's [State]), otherwise the rows outside the # lines.
+        let rows = text.lines().map(str::trim);
+        let rows: Vec<&str> = match text.lines().any(|line| line.trim() == "[Puzzle]") {
+            true => rows
+                .skip_while(|&row| row != "[Puzzle]")
+                .skip(1)
+                .take_while(|row| !row.starts_with('['))
+                .collect(),
+            false => rows.collect(),
+        };
+        let rows: Vec<&str> = rows
+            .into_iter()
+            .filter(|row| !row.is_empty() && !row.starts_with('#'))
+            .collect();
+        if rows.len() != 9 || rows.iter().any(|row| row.chars().count() != 9) {
+            return Err("a .sdk puzzle is 9 rows of 9 cells".into());
+        }
+        return Ok(vec![(stem, parse_board(&rows.concat())?)]);
+    }
+
+    // the first word of a line is the puzzle, collections often follow it with a rating.
+    let mut puzzles = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let Some(cells) = line.split_whitespace().next() else {
+            continue;
+        };
+        if cells.starts_with('#') {
+            continue;
+        }
+        let error = |e: String| format!("line {}: {}", number + 1, e);
+        if cells.len() != 81 {
+            return Err(error(format!("expected 81 cells, got {}", cells.len())));
+        }
+        let puzzle = parse_board(cells).map_err(error)?;
+        puzzles.push((format!("{}-{}", stem, puzzles.len() + 1), puzzle));
+    }
+    if puzzles.is_empty() {
+        return Err("no puzzles".into());
+    }
+    Ok(puzzles)
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// proving is the host's (host/, the zk-sudoku binary), which depends on this crate and so can't
+// be linked into it. prove runs `zk-sudoku prove` with the same arguments instead.
+fn prove(args: &[String]) -> Result<(), String> {
+    run_host("prove", args)
+}
+
+// verifying is the host's too: a .zkproof bundle goes to `zk-sudoku verify-bundle`, which
+// checks it against the puzzle id it names, anything else to `zk-sudoku verify` as a proof
+// saved by prove. --puzzle or --puzzle-id checks it's for that puzzle as well.
+fn verify(args: &[String]) -> Result<(), String> {
+    let (command, args) = verify_args(args)?;
+    run_host(command, &args)
+}
+
+fn verify_args(args: &[String]) -> Result<(&'static str, Vec<String>), String> {
+    let Some(path) = args.first().filter(|arg| !arg.starts_with('-')) else {
+        return Err(format!("verify needs a FILE\n{}", USAGE));
+    };
+    let (command, flag) = match path.to_lowercase().ends_with(".zkproof") {
+        true => ("verify-bundle", "--bundle"),
+        false => ("verify", "--proof"),
+    };
+    let mut host_args = vec![flag.to_string(), path.clone()];
+    host_args.extend_from_slice(&args[1..]);
+    Ok((command, host_args))
+}
+
+// the zk-sudoku binary ZK_SUDOKU_HOST names, or the one on the PATH, running command.
+fn run_host(command: &str, args: &[String]) -> Result<(), String> {
+    let host = std::env::var_os("ZK_SUDOKU_HOST").unwrap_or_else(|| "zk-sudoku".into());
+    let status = host_command(&host, command, args)
+        .status()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => format!(
+                "this runs `{} {}`, which isn't installed: cargo install --path host, \
+                 or point ZK_SUDOKU_HOST at it",
+                host.to_string_lossy(),
+                command
+            ),
+            _ => format!("{}: {}", host.to_string_lossy(), e),
+        })?;
+    // the host has said what went wrong already, this only says which command it was.
+    if !status.success() {
+        return Err(format!(
+            "`{} {}` failed ({})",
+            host.to_string_lossy(),
+            command,
+            status
+        ));
+    }
+    Ok(())
+}
+
+fn host_command(host: &std::ffi::OsStr, command: &str, args: &[String]) -> std::process::Command {
+    let mut host = std::process::Command::new(host);
+    host.arg(command).args(args);
+    host
+}
+
+// the dancing links search solving a puzzle, step by step (core::solver's SolveStepper): each
+// column it chooses, each value it places or takes back, the board so far and, for every open
+// cell, the size of its column, the values it can still take. --delay milliseconds apart, the
+// first --limit steps.
+fn watch(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut delay = 200;
+    let mut limit = 10_000;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--delay" => delay = parse_number(value(arg, &mut args)?)?,
+            "--limit" => limit = parse_number(value(arg, &mut args)?)?,
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let puzzle = read_board(path)?;
+    let steps = SolveStepper::new(&puzzle, limit as usize)?;
+    // in a terminal each frame replaces the last, otherwise they follow each other.
+    let terminal = std::io::IsTerminal::is_terminal(&std::io::stdout());
+    for (index, step) in steps.enumerate() {
+        if terminal {
+            print!("\x1b[2J\x1b[H");
+        } else if index > 0 {
+            println!();
+        }
+        println!("{}", watch_frame(index, &step));
+        std::thread::sleep(std::time::Duration::from_millis(delay as u64));
+    }
+    Ok(())
+}
+
+// what happened at the step, and the board next to the candidate counts. the cell the step
+// placed or took back is in brackets.
+fn watch_frame(index: usize, step: &SolveStep) -> String {
+    let (event, marked) = match &step.event {
+        SearchEvent::Start => ("givens placed".to_string(), None),
+        SearchEvent::Choose { column, size } => (
+            format!("choose {}, {} ways to do it", describe_column(column), size),
+            None,
+        ),
+        SearchEvent::DeadEnd { column } => (
+            format!("dead end, no way left: {}", describe_column(column)),
+            None,
+        ),
+        SearchEvent::Place { cell, value } => (
+            format!("place {} at r{}c{}", value, cell / 9 + 1, cell % 9 + 1),
+            Some(*cell),
+        ),
+        SearchEvent::Undo { cell, value } => (
+            format!("take back {} at r{}c{}", value, cell / 9 + 1, cell % 9 + 1),
+            Some(*cell),
+        ),
+        SearchEvent::Solved => ("solved".to_string(), None),
+    };
+    let cell = |cell: usize, text: String| match marked == Some(cell) {
+        true => format!("[{}]", text),
+        false => format!(" {} ", text),
+    };
+    let board = grid_of(|i| match step.board.cells[i] {
+        0 => cell(i, ".".into()),
+        value => cell(i, value.to_string()),
+    });
+    let candidates = grid_of(|i| match (step.board.cells[i], step.candidates[i]) {
+        (0, count) => cell(i, count.to_string()),
+        _ => cell(i, " ".into()),
+    });
+    let mut out = format!(
+        "step {}: {}\n{} of 324 constraints left\n{:<31}    {}\n",
+        index, event, step.columns, "board", "candidates per open cell"
+    );
+    for (board, candidates) in board.iter().zip(&candidates) {
+        out.push_str(&format!("{}    {}\n", board, candidates));
+    }
+    out.pop();
+    out
+}
+
+// a column of the exact cover matrix, R3C4, R3#5, C3#5 or B3#5, in words.
+fn describe_column(column: &str) -> String {
+    let Some((unit, value)) = column.split_once('#') else {
+        let cell = column.to_lowercase();
+        return format!("{} needs a value", cell);
+    };
+    let unit = match unit.split_at(1) {
+        ("R", index) => format!("row {}", index),
+        ("C", index) => format!("column {}", index),
+        ("B", index) => format!("box {}", index),
+        _ => unit.to_string(),
+    };
+    format!("{} needs a {}", unit, value)
+}
+
+// every solver over a corpus, the bundled one or FILE's, with the best time of --runs and the
+// nodes each puzzle took: a machine independent measure of the work, for logic the steps its
+// techniques took and then the nodes of the search finishing what they left.
+fn bench(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    let mut runs = 3;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--runs" => runs = parse_number(value(arg, &mut args)?)?.max(1),
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let corpus = match path {
+        None => parse_corpus(CORPUS)?,
+        Some(_) => parse_corpus(&read_text(path)?)?,
+    };
+
+    let width = corpus
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(6);
+    let mut header = format!("{:<width$} {:>5}", "puzzle", "clues");
+    for (name, _) in BACKENDS {
+        header.push_str(&format!(" {:>10} {:>8}", format!("{} ms", name), "nodes"));
+    }
+    println!("{}", header);
+    let mut totals = [(0.0, 0); BACKENDS.len()];
+    for (name, puzzle) in &corpus {
+        let mut line = format!("{:<width$} {:>5}", name, puzzle.clue_count());
+        for (total, (backend, solve)) in totals.iter_mut().zip(BACKENDS) {
+            let mut best = f64::MAX;
+            let mut nodes = 0;
+            for _ in 0..runs {
+                let start = std::time::Instant::now();
+                let (solution, count) =
+                    solve(puzzle).map_err(|e| format!("{}: {} failed ({})", name, backend, e))?;
+                best = best.min(start.elapsed().as_secs_f64() * 1000.0);
+                nodes = count;
+                // a fast wrong answer isn't worth timing.
+                if !solves(puzzle, &solution) {
+                    return Err(format!("{}: {} got a wrong solution", name, backend));
+                }
+            }
+            total.0 += best;
+            total.1 += nodes;
+            line.push_str(&format!(" {:>10.2} {:>8}", best, nodes));
+        }
+        println!("{}", line);
+    }
+    let mut line = format!("{:<width$} {:>5}", "total", "");
+    for (time, nodes) in totals {
+        line.push_str(&format!(" {:>10.2} {:>8}", time, nodes));
+    }
+    println!("{}", line);
+    Ok(())
+}
+
+fn dlx(puzzle: &Board) -> Result<(Board, usize), String> {
     let mut dl = DancingLinks::new();
     dl.init_header_row();
-    dl.init_constraint_matrix();
-    let sol = dl.solve_with_partial(&board).unwrap();
-    let solution_board = DancingLinks::to_sudoku_board(sol);
+    dl.init_constraint_matrix()?;
+    let solution = dl.solve_with_partial(puzzle)?;
+    Ok((DancingLinks::to_sudoku_board(solution), dl.nodes()))
+}
+
+fn backtrack(puzzle: &Board) -> Result<(Board, usize), String> {
+    let mut solver = Backtracker::new(puzzle)?;
+    let solution = solver.solve().ok_or("No solution found")?;
+    Ok((solution, solver.nodes()))
+}
+
+// core::logic's techniques as far as they go, then dlx.
+fn logic(puzzle: &Board) -> Result<(Board, usize), String> {
+    let trace = solve_logically(puzzle)?;
+    if trace.solved {
+        return Ok((trace.board, trace.steps.len()));
+    }
+    let (solution, nodes) = dlx(&trace.board)?;
+    Ok((solution, trace.steps.len() + nodes))
+}
+
+// a complete board that keeps the puzzle's givens.
+fn solves(puzzle: &Board, solution: &Board) -> bool {
+    solution.validate()
+        && puzzle
+            .cells
+            .iter()
+            .zip(solution.cells)
+            .all(|(&given, cell)| given == 0 || given == cell)
+}
+
+// every solver on the puzzle in FILE (or each puzzle of a corpus, see parse_corpus), reporting
+// where they disagree: on how many solutions there are, on whether there's one to find, on the
+// solution when it's the only one, or with a wrong one. an error if any of them do. the checks
+// are dlx, backtrack and logic+dlx, whose search is dlx's: there's no SAT solver to compare.
+fn crosscheck(args: &[String]) -> Result<(), String> {
+    let mut path = None;
+    for arg in args {
+        match arg.as_str() {
+            _ if path.is_none() && (arg == "-" || !arg.starts_with('-')) => path = Some(arg),
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    let text = read_text(path)?;
+    let corpus = match parse_board(&text) {
+        Ok(puzzle) => vec![("puzzle".to_string(), puzzle)],
+        Err(_) => parse_corpus(&text)?,
+    };
+    let mut disagreeing = 0;
+    for (name, puzzle) in &corpus {
+        match crosscheck_puzzle(puzzle) {
+            Ok(summary) => println!("{}: {}", name, summary),
+            Err(problems) => {
+                for problem in problems {
+                    println!("{}: {}", name, problem);
+                }
+                disagreeing += 1;
+            }
+        }
+    }
+    match disagreeing {
+        0 => Ok(()),
+        _ => Err(format!(
+            "the solvers disagree on {} of {} puzzles",
+            disagreeing,
+            corpus.len()
+        )),
+    }
+}
+
+fn crosscheck_puzzle(puzzle: &Board) -> Result<String, Vec<String>> {
+    let mut problems = Vec::new();
+    let counted = DancingLinks::count_solutions(puzzle, 2);
+    let backtracked = Backtracker::count_solutions(puzzle, 2);
+    if counted != backtracked {
+        problems.push(format!(
+            "dlx counts {:?} solutions, backtrack {:?}",
+            counted, backtracked
+        ));
+    }
+    let solvable = counted != Ok(0);
+    let mut first: Option<(&str, Board)> = None;
+    for (name, solve) in BACKENDS {
+        match solve(puzzle) {
+            Ok((solution, _)) if !solves(puzzle, &solution) => {
+                problems.push(format!("{} solves it wrong: {}", name, digits(&solution)))
+            }
+            Ok(_) if !solvable => problems.push(format!("{} solves a puzzle dlx can't", name)),
+            // with more than one solution, any of them will do.
+            Ok((solution, _)) => match &first {
+                Some((other, found)) if counted == Ok(1) && *found != solution => {
+                    problems.push(format!(
+                        "{} and {} find different solutions to a unique puzzle",
+                        other, name
+                    ))
+                }
+                Some(_) => {}
+                None => first = Some((name, solution)),
+            },
+            Err(e) if solvable => problems.push(format!("{} finds no solution ({})", name, e)),
+            Err(_) => {}
+        }
+    }
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+    Ok(match counted {
+        Ok(0) => "ok, no solution".into(),
+        Ok(1) => "ok, one solution".into(),
+        _ => "ok, more than one solution".into(),
+    })
+}
+
+// what the generator made of a range of seeds.
+#[derive(Debug, Default, PartialEq)]
+struct Stats {
+    // how many puzzles have each clue count.
+    clues: std::collections::BTreeMap<u8, u32>,
+    unique: u32,
+    ambiguous: u32,
+    unsolvable: u32,
+    // the unique puzzles by graded difficulty, easy, medium, hard and beyond the techniques.
+    grades: [u32; 4],
+    // seeds the generator fails on.
+    failures: Vec<u32>,
+}
+
+// the generator's output for every seed in --seeds (end exclusive) at --difficulty: the clue
+// counts, how many puzzles have one solution, and how hard the unique ones grade (core::logic).
+fn stats(args: &[String]) -> Result<(), String> {
+    let mut seeds = 0..1000;
+    let mut difficulty = Difficulty::Medium;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seeds" => seeds = parse_seed_range(value(arg, &mut args)?)?,
+            "--difficulty" => difficulty = parse_difficulty(value(arg, &mut args)?)?,
+            _ => return Err(format!("unknown argument '{}'\n{}", arg, USAGE)),
+        }
+    }
+    if seeds.is_empty() {
+        return Err(format!("no seeds in {}..{}", seeds.start, seeds.end));
+    }
+    let total = seeds.len() as u32;
+    println!(
+        "seeds {}..{}, {}",
+        seeds.start,
+        seeds.end,
+        format!("{:?}", difficulty).to_lowercase()
+    );
+    let stats = seed_stats(seeds, difficulty)?;
+    let percent = |count: u32| 100.0 * count as f64 / total as f64;
+
+    let generated: u32 = stats.clues.values().sum();
+    if generated > 0 {
+        let clues: u32 = stats
+            .clues
+            .iter()
+            .map(|(&clues, &count)| clues as u32 * count)
+            .sum();
+        println!(
+            "clues: {} to {}, {:.1} on average",
+            stats.clues.keys().next().unwrap(),
+            stats.clues.keys().last().unwrap(),
+            clues as f64 / generated as f64
+        );
+        let most = *stats.clues.values().max().unwrap();
+        for (clues, &count) in &stats.clues {
+            let bar = "#".repeat((count as usize * 40).div_ceil(most as usize));
+            println!(
+                "  {:>2} {:>7} {:>5.1}% {}",
+                clues,
+                count,
+                percent(count),
+                bar
+            );
+        }
+    }
+    println!("solutions:");
+    for (label, count) in [
+        ("one", stats.unique),
+        ("more than one", stats.ambiguous),
+        ("none", stats.unsolvable),
+    ] {
+        println!("  {:<13} {:>7} {:>5.1}%", label, count, percent(count));
+    }
+    println!("graded difficulty of the unique puzzles:");
+    for (label, count) in ["easy", "medium", "hard", "beyond hard"]
+        .iter()
+        .zip(stats.grades)
+    {
+        let share = 100.0 * count as f64 / stats.unique.max(1) as f64;
+        println!("  {:<13} {:>7} {:>5.1}%", label, count, share);
+    }
+    if !stats.failures.is_empty() {
+        let seeds: Vec<String> = stats.failures.iter().map(u32::to_string).collect();
+        println!(
+            "generation failed for {} seeds: {}",
+            seeds.len(),
+            seeds.join(" ")
+        );
+    }
+    Ok(())
+}
+
+fn seed_stats(seeds: std::ops::Range<u32>, difficulty: Difficulty) -> Result<Stats, String> {
+    let mut stats = Stats::default();
+    for seed in seeds {
+        let Ok(puzzle) = Board::try_from_seed(seed, Some(difficulty)) else {
+            stats.failures.push(seed);
+            continue;
+        };
+        *stats.clues.entry(puzzle.clue_count()).or_default() += 1;
+        match Backtracker::count_solutions(&puzzle, 2)? {
+            0 => stats.unsolvable += 1,
+            1 => {
+                stats.unique += 1;
+                let tier = match grade(&puzzle)?.difficulty {
+                    Some(difficulty) => difficulty as usize - 1,
+                    None => 3,
+                };
+                stats.grades[tier] += 1;
+            }
+            _ => stats.ambiguous += 1,
+        }
+    }
+    Ok(stats)
+}
+
+// A..B, B not included.
+fn parse_seed_range(text: &str) -> Result<std::ops::Range<u32>, String> {
+    let (start, end) = text
+        .split_once("..")
+        .ok_or_else(|| format!("'{}' isn't a seed range (A..B)", text))?;
+    Ok(parse_number(start)?..parse_number(end)?)
+}
+
+// one puzzle per line, in any format parse_board takes on one line, optionally after a name
+// (generate --seeds lines are named by their seed). blank lines and # comments are skipped.
+fn parse_corpus(text: &str) -> Result<Vec<(String, Board)>, String> {
+    let mut corpus = Vec::new();
+    for (number, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let error = |e: String| format!("line {}: {}", number + 1, e);
+        let (name, cells) = match line.split_once(char::is_whitespace) {
+            Some((name, cells)) if !name.starts_with('[') => (name.to_string(), cells),
+            _ => ((corpus.len() + 1).to_string(), line),
+        };
+        corpus.push((name, parse_board(cells).map_err(error)?));
+    }
+    if corpus.is_empty() {
+        return Err("no puzzles".into());
+    }
+    Ok(corpus)
+}
+
+// the board in FILE, or on stdin for - or no FILE, in the formats parse_board takes.
+fn read_board(path: Option<&String>) -> Result<Board, String> {
+    parse_board(&read_text(path)?)
+}
+
+fn read_text(path: Option<&String>) -> Result<String, String> {
+    let mut text = String::new();
+    match path.map(String::as_str) {
+        None | Some("-") => std::io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|e| format!("stdin: {}", e))?,
+        Some(path) => std::fs::File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut text))
+            .map_err(|e| format!("{}: {}", path, e))?,
+    };
+    Ok(text)
+}
+
+// 81 digits with 0 or . for empty cells, or a JSON array of 81 cells or of 9 rows of 9.
+// whitespace (and in JSON, the brackets and commas) is ignored.
+fn parse_board(text: &str) -> Result<Board, String> {
+    let json = text.trim_start().starts_with('[');
+    let cells = text
+        .chars()
+        .filter(|&c| !(c.is_whitespace() || json && matches!(c, '[' | ']' | ',')))
+        .map(|c| match c {
+            '.' if !json => Ok(0),
+            _ => c
+                .to_digit(10)
+                .map(|d| d as u8)
+                .ok_or(format!("invalid cell '{}'", c)),
+        })
+        .collect::<Result<Vec<u8>, String>>()?;
+    let cells: [u8; 81] = cells
+        .try_into()
+        .map_err(|cells: Vec<u8>| format!("expected 81 cells, got {}", cells.len()))?;
+    Board::from_array(cells).map_err(|e| format!("{:?}", e))
+}
+
+// the value following a --flag.
+fn value<'a>(flag: &str, args: &mut impl Iterator<Item = &'a String>) -> Result<&'a str, String> {
+    args.next()
+        .map(String::as_str)
+        .ok_or_else(|| format!("{} needs a value\n{}", flag, USAGE))
+}
+
+fn parse_number(text: &str) -> Result<u32, String> {
+    text.parse()
+        .map_err(|_| format!("'{}' isn't a number", text))
+}
+
+fn clock_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|time| time.subsec_nanos())
+        .unwrap_or(666)
+}
+
+fn digits(board: &Board) -> String {
+    board.cells.iter().map(|value| value.to_string()).collect()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn parse_difficulty(text: &str) -> Result<Difficulty, String> {
+    match text {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(format!("unknown difficulty '{}'", text)),
+    }
+}
+
+// rows, columns and values are 1-9, a value of 0 erases.
+fn parse_command(line: &str) -> Result<Command, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let digit = |word: &str, min: u8| -> Result<u8, String> {
+        match word.parse::<u8>() {
+            Ok(n) if (min..=9).contains(&n) => Ok(n),
+            _ => Err(format!("'{}' isn't a number from {} to 9", word, min)),
+        }
+    };
+    let cell = |row: &str, col: &str| -> Result<usize, String> {
+        Ok((digit(row, 1)? as usize - 1) * 9 + digit(col, 1)? as usize - 1)
+    };
+    match words.as_slice() {
+        ["q" | "quit" | "exit"] => Ok(Command::Quit),
+        ["h" | "help" | "?"] => Ok(Command::Help),
+        ["s" | "show"] => Ok(Command::Show),
+        ["e" | "erase", row, col] => Ok(Command::Erase {
+            cell: cell(row, col)?,
+        }),
+        [row, col, value] => {
+            let cell = cell(row, col)?;
+            match digit(value, 0)? {
+                0 => Ok(Command::Erase { cell }),
+                value => Ok(Command::Place { cell, value }),
+            }
+        }
+        _ => Err("expected 'r c v', 'e r c' or a command, try help".into()),
+    }
+}
+
+fn position(cell: usize) -> String {
+    format!("row {} column {}", cell / 9 + 1, cell % 9 + 1)
+}
+
+// the board with row and column numbers, empty cells as dots and the user's values marked
+// with a * (the givens aren't).
+fn render(board: &Board, puzzle: &Board) -> String {
+    let mut out = String::from("     1  2  3   4  5  6   7  8  9\n");
+    for row in 0..9 {
+        if row % 3 == 0 {
+            out.push_str("   +---------+---------+---------+\n");
+        }
+        out.push_str(&format!(" {} |", row + 1));
+        for col in 0..9 {
+            let cell = row * 9 + col;
+            match (board.cells[cell], puzzle.cells[cell]) {
+                (0, _) => out.push_str(" . "),
+                (value, 0) => out.push_str(&format!(" {}*", value)),
+                (value, _) => out.push_str(&format!(" {} ", value)),
+            }
+            if col % 3 == 2 {
+                out.push('|');
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str("   +---------+---------+---------+");
+    out
+}
+
+#[cfg(test)]
+mod main_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command("1 1 5"),
+            Ok(Command::Place { cell: 0, value: 5 })
+        );
+        assert_eq!(
+            parse_command(" 9  9 9 "),
+            Ok(Command::Place { cell: 80, value: 9 })
+        );
+        assert_eq!(parse_command("2 3 0"), Ok(Command::Erase { cell: 11 }));
+        assert_eq!(parse_command("e 2 3"), Ok(Command::Erase { cell: 11 }));
+        assert_eq!(parse_command("quit"), Ok(Command::Quit));
+        assert!(parse_command("0 1 5").is_err());
+        assert!(parse_command("1 10 5").is_err());
+        assert!(parse_command("1 1 x").is_err());
+        assert!(parse_command("1 1").is_err());
+    }
+
+    #[test]
+    fn test_render_marks_user_values() {
+        let puzzle = Board::from_seed(666, Some(Difficulty::Medium));
+        let mut board = puzzle.clone();
+        let empty = puzzle.cells.iter().position(|&value| value == 0).unwrap();
+        let rendered = render(&board, &puzzle);
+        assert_eq!(rendered.lines().count(), 14);
+        assert!(!rendered.contains('*'));
+
+        board.cells[empty] = 4;
+        assert_eq!(render(&board, &puzzle).matches('*').count(), 1);
+    }
+
+    #[test]
+    fn test_puzzle_line() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let line = puzzle_line(&Format::Line, None, Difficulty::Hard, &puzzle, None);
+        assert_eq!(line, digits(&puzzle));
+        assert_eq!(line.len(), 81);
+
+        let line = puzzle_line(
+            &Format::Line,
+            Some(42),
+            Difficulty::Hard,
+            &puzzle,
+            Some(&solution),
+        );
+        let fields: Vec<&str> = line.split(' ').collect();
+        assert_eq!(fields, ["42", &digits(&puzzle), &digits(&solution)]);
+
+        let json = puzzle_line(&Format::Json, Some(42), Difficulty::Hard, &puzzle, None);
+        assert_eq!(
+            json,
+            format!(
+                "{{\"seed\":42,\"difficulty\":\"hard\",\"puzzle\":\"{}\"}}",
+                digits(&puzzle)
+            )
+        );
+    }
+
+    #[test]
+    fn test_sheet() {
+        let puzzles: Vec<(PuzzleId, Board)> = (0..3)
+            .map(|seed| {
+                let id = PuzzleId::new(seed, Difficulty::Easy, &Variant::Classic).unwrap();
+                (id, id.puzzle().unwrap())
+            })
+            .collect();
+        let sheet = sheet(&puzzles);
+        // two rows of grids, 15 lines each with the title, apart by a blank line.
+        assert_eq!(sheet.lines().count(), 31);
+        assert!(sheet.lines().all(|line| line.len() <= 31 * 2 + 4));
+        for (index, (id, puzzle)) in puzzles.iter().enumerate() {
+            assert!(sheet.contains(&title(index, id)));
+            assert_eq!(
+                PuzzleId::decode(&id.encode()).unwrap().puzzle().unwrap(),
+                *puzzle
+            );
+        }
+        assert!(sheet.starts_with("1. easy, zk1-"));
+
+        // the givens, row by row, are the first grid's.
+        let first: String = sheet
+            .lines()
+            .filter(|line| line.starts_with('|'))
+            .take(9)
+            .flat_map(|line| {
+                line[..31]
+                    .chars()
+                    .filter(|c| c.is_ascii_digit() || *c == '.')
+            })
+            .map(|c| if c == '.' { '0' } else { c })
+            .collect();
+        assert_eq!(first, digits(&puzzles[0].1));
+    }
+
+    #[test]
+    fn test_corpus() {
+        let corpus = parse_corpus(CORPUS).unwrap();
+        assert!(corpus.iter().any(|(_, puzzle)| puzzle.clue_count() == 17));
+        for (name, puzzle) in &corpus {
+            assert_eq!(
+                DancingLinks::count_solutions(puzzle, 2),
+                Ok(1),
+                "{} doesn't have one solution",
+                name
+            );
+            let (dlx_solution, _) = dlx(puzzle).unwrap();
+            let (logic_solution, _) = logic(puzzle).unwrap();
+            assert_eq!(dlx_solution, logic_solution, "{}", name);
+        }
+
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let text = format!("# seeds\n\n42 {}\n{}\n", digits(&puzzle), digits(&puzzle));
+        let corpus = parse_corpus(&text).unwrap();
+        assert_eq!(
+            corpus,
+            [("42".into(), puzzle.clone()), ("2".into(), puzzle)]
+        );
+        assert!(parse_corpus("# nothing\n").is_err());
+        assert!(parse_corpus("name 123").unwrap_err().starts_with("line 1:"));
+    }
+
+    #[test]
+    fn test_import_puzzles() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let rows: Vec<String> = digits(&puzzle)
+            .replace('0', ".")
+            .as_bytes()
+            .chunks(9)
+            .map(|row| String::from_utf8(row.to_vec()).unwrap())
+            .collect();
+        let sdk = format!("#Aauthor\n#Dfrom a book\n{}\n", rows.join("\n"));
+        let path = std::path::Path::new("dir/book.SDK");
+        assert_eq!(
+            import_puzzles(path, &sdk),
+            Ok(vec![("book".to_string(), puzzle.clone())])
+        );
+        let sections = format!(
+            "[Puzzle]\n{}\n[State]\n{}\n",
+            rows.join("\n"),
+            rows.join("\n")
+        );
+        assert_eq!(import_puzzles(path, &sections).unwrap()[0].1, puzzle);
+        assert!(import_puzzles(path, &rows[..8].join("\n")).is_err());
+
+        let other = Board::from_seed(666, Some(Difficulty::Medium));
+        let lines = format!(
+            "{}\n\n{} 1.2/1.2/1.2\n",
+            digits(&puzzle),
+            digits(&other).replace('0', ".")
+        );
+        for name in ["top.sdm", "top.txt", "top"] {
+            assert_eq!(
+                import_puzzles(std::path::Path::new(name), &lines),
+                Ok(vec![
+                    ("top-1".to_string(), puzzle.clone()),
+                    ("top-2".to_string(), other.clone())
+                ])
+            );
+        }
+        let error = import_puzzles(std::path::Path::new("top.sdm"), "123\n").unwrap_err();
+        assert!(error.starts_with("line 1:"));
+        assert!(import_puzzles(std::path::Path::new("top.sdm"), "\n").is_err());
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("top-1"), "\"top-1\"");
+        assert_eq!(json_string("a \"b\"\\\n"), "\"a \\\"b\\\"\\\\\\u000a\"");
+    }
+
+    #[test]
+    fn test_host_command() {
+        let args: Vec<String> = ["--puzzle-id", "zk1-aaaafgqcaawqi", "--backend", "sp1"]
+            .map(String::from)
+            .to_vec();
+        let host = host_command("zk-sudoku".as_ref(), "prove", &args);
+        assert_eq!(host.get_program(), "zk-sudoku");
+        let passed: Vec<_> = host.get_args().collect();
+        assert_eq!(
+            passed,
+            [
+                "prove",
+                "--puzzle-id",
+                "zk1-aaaafgqcaawqi",
+                "--backend",
+                "sp1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_verify_args() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            verify_args(&args(&[
+                "solve.zkproof",
+                "--puzzle-id",
+                "zk1-aaaafgqcaawqi"
+            ])),
+            Ok((
+                "verify-bundle",
+                args(&[
+                    "--bundle",
+                    "solve.zkproof",
+                    "--puzzle-id",
+                    "zk1-aaaafgqcaawqi"
+                ])
+            ))
+        );
+        assert_eq!(
+            verify_args(&args(&["proof.bin", "--puzzle", "puzzle.txt"])),
+            Ok((
+                "verify",
+                args(&["--proof", "proof.bin", "--puzzle", "puzzle.txt"])
+            ))
+        );
+        assert!(verify_args(&args(&["--puzzle", "puzzle.txt"])).is_err());
+        assert!(verify_args(&[]).is_err());
+    }
+
+    #[test]
+    fn test_crosscheck_puzzle() {
+        let puzzle = Board::from_seed(666, Some(Difficulty::Medium));
+        assert_eq!(crosscheck_puzzle(&puzzle), Ok("ok, one solution".into()));
+        let seeded = Board::from_seed(2200, None);
+        assert_eq!(
+            crosscheck_puzzle(&seeded),
+            Ok("ok, more than one solution".into())
+        );
+        let mut cells = [0; 81];
+        cells[..9].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 0]);
+        cells[17] = 9;
+        assert_eq!(
+            crosscheck_puzzle(&Board { cells }),
+            Ok("ok, no solution".into())
+        );
+    }
+
+    #[test]
+    fn test_seed_stats() {
+        assert_eq!(parse_seed_range("10..20"), Ok(10..20));
+        assert!(parse_seed_range("10").is_err());
+        assert!(parse_seed_range("10..x").is_err());
+
+        let stats = seed_stats(0..20, Difficulty::Hard).unwrap();
+        assert_eq!(stats.clues.values().sum::<u32>(), 20);
+        assert_eq!(stats.unique + stats.ambiguous + stats.unsolvable, 20);
+        assert_eq!(stats.grades.iter().sum::<u32>(), stats.unique);
+        assert!(stats.failures.is_empty());
+        for seed in 0..20 {
+            let puzzle = Board::from_seed(seed, Some(Difficulty::Hard));
+            assert!(stats.clues.contains_key(&puzzle.clue_count()));
+        }
+    }
+
+    #[test]
+    fn test_watch_frame() {
+        assert_eq!(describe_column("R3C4"), "r3c4 needs a value");
+        assert_eq!(describe_column("R3#5"), "row 3 needs a 5");
+        assert_eq!(describe_column("C9#1"), "column 9 needs a 1");
+        assert_eq!(describe_column("B2#7"), "box 2 needs a 7");
 
-    let user_input = vec![
-        7, 5, 3, 8, 2, 1, 6, 9, 4, 1, 2, 4, 3, 6, 9, 5, 7, 8, 6, 8, 9, 4, 5, 7, 1, 2, 3, 2, 9, 1,
-        5, 7, 3, 8, 4, 6, 8, 4, 7, 2, 1, 6, 9, 3, 5, 5, 3, 6, 9, 4, 8, 2, 1, 7, 3, 7, 2, 1, 8, 5,
-        4, 6, 9, 4, 6, 5, 7, 9, 2, 3, 8, 1, 9, 1, 8, 6, 3, 4, 7, 5, 2,
-    ];
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let steps: Vec<SolveStep> = SolveStepper::new(&puzzle, 3).unwrap().collect();
+        let frame = watch_frame(0, &steps[0]);
+        assert!(frame.starts_with("step 0: givens placed\n"));
+        assert_eq!(frame.lines().count(), 3 + 13);
+        assert!(frame.lines().skip(3).all(|line| line.len() == 31 + 4 + 31));
+        let frame = watch_frame(2, &steps[2]);
+        let SearchEvent::Place { cell, value } = steps[2].event else {
+            panic!("expected a placement, got {:?}", steps[2].event);
+        };
+        assert!(frame.contains(&format!(
+            "place {} at r{}c{}",
+            value,
+            cell / 9 + 1,
+            cell % 9 + 1
+        )));
+        assert_eq!(frame.matches(&format!("[{}]", value)).count(), 1);
+    }
 
-    // unless you unwrap this, the function doesn't panic.
-    board.apply_user_input_to_board(user_input);
-    println!("User playing board {}", board);
+    #[test]
+    fn test_parse_board() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let text = digits(&puzzle);
+        assert_eq!(parse_board(&text), Ok(puzzle.clone()));
+        assert_eq!(parse_board(&text.replace('0', ".")), Ok(puzzle.clone()));
 
-    let valid = board.validate();
+        let rows: Vec<String> = puzzle
+            .cells
+            .chunks(9)
+            .map(|row| format!("{:?}", row))
+            .collect();
+        assert_eq!(
+            parse_board(&format!("[{}]", rows.join(",\n"))),
+            Ok(puzzle.clone())
+        );
+        assert_eq!(
+            parse_board(&format!("{:?}", puzzle.cells.to_vec())),
+            Ok(puzzle.clone())
+        );
 
-    println!("user solution is {}", valid);
+        assert!(parse_board(&text[1..]).is_err());
+        assert!(parse_board(&text.replacen('0', "x", 1)).is_err());
+        // a JSON cell is a number, not a dot.
+        assert!(parse_board(&format!("[.{}]", &text[1..])).is_err());
+    }
 }
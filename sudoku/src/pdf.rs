@@ -0,0 +1,199 @@
+// printable sheets as a pdf: a4 pages of four grids, two by two, each under its title. the
+// file is written by hand, uncompressed, with the standard helvetica fonts every reader has,
+// so it takes no dependencies.
+use sudoku::core::board::Board;
+use sudoku::core::puzzle_id::PuzzleId;
+
+use crate::{title, SHEET_COLUMNS};
+
+// a4 in points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+const PER_PAGE: usize = 4;
+const CELL: f32 = 25.0;
+const MARGIN: f32 = 50.0;
+// helvetica's digits are 0.556 em wide.
+const DIGIT_WIDTH: f32 = 0.556;
+const DIGIT_SIZE: f32 = 16.0;
+// the side of a module of the qr codes under the grids, and the space above them.
+#[cfg(feature = "qr")]
+const QR_MODULE: f32 = 2.5;
+#[cfg(feature = "qr")]
+const QR_GAP: f32 = 15.0;
+
+pub fn sheet(puzzles: &[(PuzzleId, Board)]) -> Vec<u8> {
+    let pages: Vec<String> = puzzles
+        .chunks(PER_PAGE)
+        .enumerate()
+        .map(|(page, chunk)| {
+            chunk
+                .iter()
+                .enumerate()
+                .map(|(i, (id, puzzle))| {
+                    let slot = page * PER_PAGE + i;
+                    let x = MARGIN + (i % SHEET_COLUMNS) as f32 * (PAGE_WIDTH - 2.0 * MARGIN) / 2.0;
+                    let top = PAGE_HEIGHT - MARGIN - (i / SHEET_COLUMNS) as f32 * 370.0;
+                    grid(&title(slot, id), puzzle, x, top)
+                })
+                .collect()
+        })
+        .collect();
+
+    // 1 catalog, 2 pages, 3 and 4 fonts, then a page and its contents for each page.
+    let kids: Vec<String> = (0..pages.len())
+        .map(|page| format!("{} 0 R", 5 + page * 2))
+        .collect();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold >>".to_string(),
+    ];
+    for (page, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            6 + page * 2
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut out = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.extend(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).into_bytes());
+    }
+    let xref = out.len();
+    out.extend(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).into_bytes());
+    for offset in offsets {
+        out.extend(format!("{:010} 00000 n \n", offset).into_bytes());
+    }
+    out.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .into_bytes(),
+    );
+    out
+}
+
+// the drawing of one titled grid, its top left corner at x, top: thin lines between the
+// cells, thick ones around the boxes, and the givens centered in their cells. with the qr
+// feature the puzzle's share code goes under it, so an app can scan the puzzle in.
+fn grid(title: &str, puzzle: &Board, x: f32, top: f32) -> String {
+    let mut out = format!("BT /F2 11 Tf {} {} Td ({}) Tj ET\n", x, top - 11.0, title);
+    let top = top - 25.0;
+    let size = CELL * 9.0;
+    for i in 0..=9 {
+        let offset = i as f32 * CELL;
+        let width = if i % 3 == 0 { 2.0 } else { 0.5 };
+        out.push_str(&format!(
+            "{} w {} {} m {} {} l S {} {} m {} {} l S\n",
+            width,
+            x,
+            top - offset,
+            x + size,
+            top - offset,
+            x + offset,
+            top,
+            x + offset,
+            top - size
+        ));
+    }
+    for (cell, &value) in puzzle.cells.iter().enumerate() {
+        if value == 0 {
+            continue;
+        }
+        let left = x + (cell % 9) as f32 * CELL + (CELL - DIGIT_WIDTH * DIGIT_SIZE) / 2.0;
+        let baseline = top - (cell / 9 + 1) as f32 * CELL + (CELL - 0.7 * DIGIT_SIZE) / 2.0;
+        out.push_str(&format!(
+            "BT /F1 {} Tf {:.1} {:.1} Td ({}) Tj ET\n",
+            DIGIT_SIZE, left, baseline, value
+        ));
+    }
+    #[cfg(feature = "qr")]
+    out.push_str(&qr(puzzle, x, top - size - QR_GAP));
+    out
+}
+
+// one filled square per dark module, the page around it is the quiet zone.
+#[cfg(feature = "qr")]
+fn qr(puzzle: &Board, x: f32, top: f32) -> String {
+    let code = puzzle.to_qr();
+    let width = code.width();
+    let mut out = String::new();
+    for (i, color) in code.to_colors().into_iter().enumerate() {
+        if color == qrcode::Color::Dark {
+            out.push_str(&format!(
+                "{:.1} {:.1} {} {} re\n",
+                x + (i % width) as f32 * QR_MODULE,
+                top - (i / width + 1) as f32 * QR_MODULE,
+                QR_MODULE,
+                QR_MODULE
+            ));
+        }
+    }
+    out.push_str("f\n");
+    out
+}
+
+#[cfg(test)]
+mod pdf_tests {
+    use super::*;
+    use sudoku::core::board::Difficulty;
+    use sudoku::core::variant::Variant;
+
+    #[test]
+    fn test_sheet() {
+        let puzzles: Vec<(PuzzleId, Board)> = (0..5)
+            .map(|seed| {
+                let id = PuzzleId::new(seed, Difficulty::Hard, &Variant::Classic).unwrap();
+                (id, id.puzzle().unwrap())
+            })
+            .collect();
+        let pdf = String::from_utf8(sheet(&puzzles)).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+        for (index, (id, _)) in puzzles.iter().enumerate() {
+            assert!(pdf.contains(&format!("({})", title(index, id))));
+        }
+        #[cfg(feature = "qr")]
+        {
+            let dark = |(_, puzzle): &(PuzzleId, Board)| {
+                let colors = puzzle.to_qr().to_colors();
+                colors
+                    .into_iter()
+                    .filter(|&c| c == qrcode::Color::Dark)
+                    .count()
+            };
+            let modules: usize = puzzles.iter().map(dark).sum();
+            assert_eq!(pdf.matches(" re\n").count(), modules);
+        }
+
+        // every xref entry points at its object.
+        let xref = pdf.rfind("\nxref\n").unwrap() + 1;
+        assert!(pdf.ends_with(&format!("startxref\n{}\n%%EOF\n", xref)));
+        let entries = pdf[xref..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "));
+        for (i, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// share codes as QR codes, so a puzzle on a sheet printed by `sudoku print` can be scanned into
+// an app instead of typed in 81 digits. the QR holds exactly `Board::to_share_code`, 62
+// characters, which fit a version 4 code (33 modules square) at error correction level M, enough
+// to survive a crease or a smudge. reading takes the codes found in a grayscale image, of any
+// size and in any rotation, through `Board::from_share_code`, so a misread is caught by the
+// code's checksum rather than turning into another board.
+//
+//     board.to_qr_image(8).save("puzzle.png")?;
+//     ...
+//     let board = Board::from_qr_image(&image::open("photo.jpg")?.to_luma8())?;
+extern crate alloc;
+use alloc::vec::Vec;
+
+use image::{GrayImage, Luma};
+use qrcode::bits::Bits;
+use qrcode::{EcLevel, QrCode, Version};
+
+use crate::core::board::Board;
+use crate::core::share_code::ShareCodeError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrError {
+    // no QR code in the image.
+    NoCode,
+    // a code was found but not read, too damaged, blurred or skewed.
+    Unreadable,
+    // a code was read but isn't a share code.
+    ShareCode(ShareCodeError),
+}
+
+impl Board {
+    // the code as one byte mode segment, 508 of version 4's 512 bits at level M. left to
+    // itself qrcode splits it into segments that don't always fit and moves to version 5.
+    pub fn to_qr(&self) -> QrCode {
+        let mut bits = Bits::new(Version::Normal(4));
+        bits.push_byte_data(self.to_share_code().as_bytes())
+            .and_then(|_| bits.push_terminator(EcLevel::M))
+            .and_then(|_| QrCode::with_bits(bits, EcLevel::M))
+            .expect("share codes always fit a version 4 qr code")
+    }
+
+    // `module` pixels a side for each module, with the four module quiet zone scanners need.
+    pub fn to_qr_image(&self, module: u32) -> GrayImage {
+        self.to_qr()
+            .render::<Luma<u8>>()
+            .module_dimensions(module, module)
+            .build()
+    }
+
+    // the board of the first code in `image` that reads as a share code. a photo of a whole
+    // sheet holds several, `boards_from_qr_image` has them all.
+    pub fn from_qr_image(image: &GrayImage) -> Result<Board, QrError> {
+        let mut first_error = None;
+        for result in boards_from_qr_image(image) {
+            match result {
+                Ok(board) => return Ok(board),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        Err(first_error.unwrap_or(QrError::NoCode))
+    }
+}
+
+// every code found in `image`, read or not, in the order the detector found them.
+pub fn boards_from_qr_image(image: &GrayImage) -> Vec<Result<Board, QrError>> {
+    let mut prepared = rqrr::PreparedImage::prepare(image.clone());
+    prepared
+        .detect_grids()
+        .iter()
+        .map(|grid| {
+            let (_, content) = grid.decode().map_err(|_| QrError::Unreadable)?;
+            Board::from_share_code(&content).map_err(QrError::ShareCode)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod qr_tests {
+    use super::*;
+    use crate::core::board::Difficulty;
+    use crate::core::solver::DancingLinks;
+    use image::imageops;
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        for board in [puzzle, solution, Board { cells: [0; 81] }] {
+            assert_eq!(board.to_qr().width(), 33);
+            let image = board.to_qr_image(4);
+            assert_eq!(Board::from_qr_image(&image), Ok(board.clone()));
+            // scanned upside down, or at another size.
+            assert_eq!(
+                Board::from_qr_image(&imageops::rotate180(&image)),
+                Ok(board.clone())
+            );
+            assert_eq!(Board::from_qr_image(&board.to_qr_image(9)), Ok(board));
+        }
+    }
+
+    #[test]
+    fn test_several_codes() {
+        let boards: Vec<Board> = [1, 2]
+            .iter()
+            .map(|&seed| Board::from_seed(seed, Some(Difficulty::Easy)))
+            .collect();
+        let codes: Vec<GrayImage> = boards.iter().map(|board| board.to_qr_image(4)).collect();
+        let (width, height) = codes[0].dimensions();
+        let mut sheet = GrayImage::from_pixel(width * 2, height, Luma([255]));
+        imageops::overlay(&mut sheet, &codes[0], 0, 0);
+        imageops::overlay(&mut sheet, &codes[1], width as i64, 0);
+        let mut read: Vec<Board> = boards_from_qr_image(&sheet)
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        read.sort_by_key(|board| board.cells);
+        let mut expected = boards.clone();
+        expected.sort_by_key(|board| board.cells);
+        assert_eq!(read, expected);
+    }
+
+    #[test]
+    fn test_errors() {
+        let blank = GrayImage::from_pixel(200, 200, Luma([255]));
+        assert_eq!(Board::from_qr_image(&blank), Err(QrError::NoCode));
+
+        let other = QrCode::new("https://example.com")
+            .unwrap()
+            .render::<Luma<u8>>()
+            .module_dimensions(4, 4)
+            .build();
+        assert_eq!(
+            Board::from_qr_image(&other),
+            Err(QrError::ShareCode(ShareCodeError::InvalidEncoding))
+        );
+    }
+}
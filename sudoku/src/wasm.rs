@@ -0,0 +1,129 @@
+// the board logic for javascript, so a browser game generates, checks and validates boards with
+// the same code the guests prove. boards go back and forth as Uint8Arrays of 81 cells, row-wise,
+// 0 for empty, and difficulties as the tier the proofs commit (1 easy, 2 medium, 3 hard).
+//
+//     import init, { generate, applyInput, canPlace, validate } from "./sudoku.js";
+//     await init();
+//     const puzzle = generate(42, 2);
+//     canPlace(puzzle, 0, 5);
+//
+// the crate stays an rlib for the guests, so the module is built as a cdylib on the command line:
+//
+//     cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//     wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/sudoku.wasm
+extern crate alloc;
+use alloc::format;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::core::board::{Board, Difficulty};
+use crate::core::commitment::puzzle_hash;
+use crate::core::solver::DancingLinks;
+
+// the puzzle the guests regenerate from the seed.
+#[wasm_bindgen]
+pub fn generate(seed: u32, difficulty: u8) -> Result<Vec<u8>, JsError> {
+    let difficulty = Difficulty::from_u8(difficulty)
+        .ok_or_else(|| JsError::new(&format!("unknown difficulty {}", difficulty)))?;
+    let board = Board::try_from_seed(seed, Some(difficulty))
+        .map_err(|e| JsError::new(&format!("can't generate seed {} ({:?})", seed, e)))?;
+    Ok(board.cells.to_vec())
+}
+
+// the user's cells on top of the puzzle, as the guests apply them: an error if one changes a
+// given.
+#[wasm_bindgen(js_name = applyInput)]
+pub fn apply_input(puzzle: &[u8], input: &[u8]) -> Result<Vec<u8>, JsError> {
+    let mut board = board(puzzle)?;
+    board
+        .apply_user_input(&board_cells(input)?)
+        .map_err(JsError::new)?;
+    Ok(board.cells.to_vec())
+}
+
+// whether value can go into cell (0-80) without repeating in its row, column or box, see
+// Board::can_place.
+#[wasm_bindgen(js_name = canPlace)]
+pub fn can_place(board_cells: &[u8], cell: usize, value: u8) -> Result<bool, JsError> {
+    if cell >= 81 || !(1..=9).contains(&value) {
+        return Err(JsError::new(&format!(
+            "can't put {} in cell {}",
+            value, cell
+        )));
+    }
+    Ok(board(board_cells)?.can_place(cell, value))
+}
+
+// no value repeated in a row, column or box, empty cells allowed.
+#[wasm_bindgen(js_name = isConflictFree)]
+pub fn is_conflict_free(board_cells: &[u8]) -> Result<bool, JsError> {
+    Ok(board(board_cells)?.is_conflict_free())
+}
+
+// a complete board following the rules, what the guests check before committing valid.
+#[wasm_bindgen]
+pub fn validate(board_cells: &[u8]) -> Result<bool, JsError> {
+    Ok(board(board_cells)?.validate())
+}
+
+// the puzzle with input applied is solved, the guests' verdict on a submission.
+#[wasm_bindgen(js_name = checkSolution)]
+pub fn check_solution(puzzle: &[u8], input: &[u8]) -> Result<bool, JsError> {
+    let mut board = board(puzzle)?;
+    Ok(board.apply_user_input(&board_cells(input)?).is_ok() && board.validate())
+}
+
+// the dancing links solution, an error when there's none.
+#[wasm_bindgen]
+pub fn solve(puzzle: &[u8]) -> Result<Vec<u8>, JsError> {
+    let solution = DancingLinks::solve_board(&board(puzzle)?).map_err(JsError::new)?;
+    Ok(solution.cells.to_vec())
+}
+
+// the sha256 the guests commit as the puzzle hash, 32 bytes.
+#[wasm_bindgen(js_name = puzzleHash)]
+pub fn puzzle_hash_of(puzzle: &[u8]) -> Result<Vec<u8>, JsError> {
+    Ok(puzzle_hash(&board(puzzle)?).to_vec())
+}
+
+fn board(cells: &[u8]) -> Result<Board, JsError> {
+    Board::from_array(board_cells(cells)?).map_err(|_| JsError::new("a cell above 9"))
+}
+
+fn board_cells(cells: &[u8]) -> Result<[u8; 81], JsError> {
+    cells
+        .try_into()
+        .map_err(|_| JsError::new(&format!("expected 81 cells, got {}", cells.len())))
+}
+
+#[cfg(test)]
+mod wasm_tests {
+    use super::*;
+
+    // only what doesn't fail, JsError needs a javascript host.
+    #[test]
+    fn test_bindings() {
+        let puzzle = generate(42, Difficulty::Medium as u8).unwrap();
+        assert_eq!(
+            puzzle,
+            Board::from_seed(42, Some(Difficulty::Medium))
+                .cells
+                .to_vec()
+        );
+        assert!(is_conflict_free(&puzzle).unwrap());
+        assert!(!validate(&puzzle).unwrap());
+
+        let solution = solve(&puzzle).unwrap();
+        assert!(validate(&solution).unwrap());
+        assert!(check_solution(&puzzle, &solution).unwrap());
+        assert_eq!(apply_input(&puzzle, &solution).unwrap(), solution);
+
+        let empty = puzzle.iter().position(|&cell| cell == 0).unwrap();
+        assert!(can_place(&puzzle, empty, solution[empty]).unwrap());
+        let mut wrong = solution.clone();
+        wrong[empty] = solution[empty] % 9 + 1;
+        assert!(!check_solution(&puzzle, &wrong).unwrap());
+        assert_eq!(puzzle_hash_of(&puzzle).unwrap().len(), 32);
+    }
+}
@@ -0,0 +1,6 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ProofDocument } from "./ProofDocument";
+import type { PuzzleDocument } from "./PuzzleDocument";
+import type { SolutionDocument } from "./SolutionDocument";
+
+export type DocumentBody = { "type": "puzzle" } & PuzzleDocument | { "type": "solution" } & SolutionDocument | { "type": "proof" } & ProofDocument;
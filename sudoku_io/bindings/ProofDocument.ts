@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PublicValuesDocument } from "./PublicValuesDocument";
+
+export type ProofDocument = { backend: string, provedOn: string, vkeyHash: string, programDigest: string, puzzleId?: string, publicValues: PublicValuesDocument, };
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PublicValuesDocument = { puzzleHash: string, solutionCommitment: string, valid: boolean, difficulty: number, clueCount: number, seed: number, nullifier: string, epoch: number, rewardAddress: string, status: number, policyHash: string, organizerKey: string, variant: number, logicVersion: number, cellsRoot: string, givensHash: string, nonce: string, };
@@ -0,0 +1,13 @@
+fn main() {
+    // the `proto` feature's messages, generated from proto/ by prost with a vendored protoc so
+    // building doesn't need one installed. see src/proto.rs.
+    #[cfg(feature = "proto")]
+    {
+        println!("cargo:rerun-if-changed=proto");
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("no vendored protoc");
+        prost_build::Config::new()
+            .protoc_executable(protoc)
+            .compile_protos(&["proto/zk_sudoku/v1/sudoku.proto"], &["proto"])
+            .expect("failed to compile the protobuf definitions");
+    }
+}
@@ -0,0 +1,98 @@
+// everything a guest or host reads from outside: the byte encodings (boards, variants,
+// policies, envelopes, bundles, public values, ids and share codes), a solving session's moves
+// replayed on a puzzle, and whole submissions through verify_input. malformed input has to come
+// back as an error, never a panic, and what does decode has to survive a round trip.
+//
+//     cargo +nightly fuzz run inputs -- -rss_limit_mb=0
+//
+// the solver's matrix is a web of Rc links that's never freed, so every generated or solved
+// puzzle leaks it and a long run outgrows libFuzzer's default 2GB limit.
+#![no_main]
+
+use libfuzzer_sys::arbitrary::{self, Arbitrary};
+use libfuzzer_sys::fuzz_target;
+use sudoku::core::board::Board;
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::puzzle_id::PuzzleId;
+use sudoku_io::bundle::ProofBundle;
+use sudoku_io::envelope::ProofEnvelope;
+use sudoku_io::{
+    check_transcript, decode_cells, decode_variant, replay_moves, verify_input, Move, Policy,
+    ProofInput, PublicValues, PuzzleSource, STATUS_OK,
+};
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    bytes: Vec<u8>,
+    puzzle: Board,
+    moves: Vec<Move>,
+    submission: ProofInput,
+}
+
+fuzz_target!(|input: Input| {
+    parse(&input.bytes);
+    play(&input.puzzle, &input.moves);
+    verify(&input.puzzle, input.submission);
+});
+
+fn parse(bytes: &[u8]) {
+    if let Ok(cells) = decode_cells(bytes) {
+        if let Ok(board) = Board::from_array(cells) {
+            assert_eq!(Board::from_packed(&board.to_packed()).unwrap(), board);
+        }
+    }
+    if let Ok(variant) = decode_variant(bytes) {
+        assert_eq!(decode_variant(&variant.to_bytes()), Ok(variant));
+    }
+    if let Ok(policy) = Policy::from_bytes(bytes) {
+        assert_eq!(policy.to_bytes(), bytes);
+    }
+    if let Ok(envelope) = ProofEnvelope::decode(bytes) {
+        assert_eq!(ProofEnvelope::decode(&envelope.encode()), Ok(envelope));
+    }
+    if let Ok(bundle) = ProofBundle::decode(bytes) {
+        assert!(ProofBundle::decode(&bundle.encode()).is_ok());
+    }
+    if let Ok(values) = PublicValues::abi_decode(bytes) {
+        assert_eq!(PublicValues::abi_decode(&values.abi_encode()), Ok(values));
+    }
+    if let Ok(text) = core::str::from_utf8(bytes) {
+        if let Ok(id) = PuzzleId::decode(text) {
+            assert_eq!(PuzzleId::decode(&id.encode()), Ok(id));
+        }
+        if let Ok(board) = Board::from_share_code(text) {
+            assert_eq!(Board::from_share_code(&board.to_share_code()), Ok(board));
+        }
+    }
+}
+
+fn play(puzzle: &Board, moves: &[Move]) {
+    let mut board = puzzle.clone();
+    let illegal = replay_moves(puzzle, &mut board, moves);
+    assert!(illegal.is_none_or(|index| index < moves.len()));
+    // no move, legal or not, touches a given.
+    assert!((0..81).all(|i| puzzle.cells[i] == 0 || board.cells[i] == puzzle.cells[i]));
+
+    let values = check_transcript(puzzle.clone(), moves);
+    assert_eq!(values.illegal_move as usize, illegal.unwrap_or(moves.len()));
+    if values.valid {
+        assert!(illegal.is_none() && board.validate() && board.is_conflict_free());
+    }
+}
+
+// the submission as it comes, and again with the arbitrary puzzle supplied under its own hash,
+// which a fuzzer would never guess, so the board checks are reached.
+fn verify(puzzle: &Board, submission: ProofInput) {
+    let supplied = ProofInput {
+        puzzle: PuzzleSource::Puzzle {
+            cells: puzzle.cells.to_vec(),
+            expected_hash: puzzle_hash(puzzle),
+        },
+        ..submission.clone()
+    };
+    for submission in [submission, supplied] {
+        if let Ok(values) = verify_input(&submission, |_, _, _| false) {
+            assert!(!values.valid || values.status == STATUS_OK);
+        }
+    }
+}
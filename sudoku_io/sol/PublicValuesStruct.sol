@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.20;
+
+/// @notice Public values committed by the zk-sudoku verifier program
+/// (sudoku_io::PublicValues), decode them with abi.decode(publicValues, (PublicValuesStruct)).
+struct PublicValuesStruct {
+    bytes32 puzzleHash;
+    bytes32 solutionCommitment;
+    bool valid;
+    uint8 difficulty;
+    uint32 seed;
+    bytes32 nullifier;
+    uint8 clueCount;
+    uint64 epoch;
+    address rewardAddress;
+    uint8 status;
+    bytes32 policyHash;
+    bytes32 organizerKey;
+    uint8 variant;
+    uint32 logicVersion;
+    bytes32 cellsRoot;
+    bytes32 givensHash;
+    bytes32 nonce;
+}
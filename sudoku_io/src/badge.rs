@@ -0,0 +1,257 @@
+// ERC-721 metadata for completion badges, minted once a proof has been verified: the JSON a
+// badge's tokenURI points to, and an SVG of the puzzle's silhouette, where its givens are without
+// the digits, so a badge doesn't hand out the puzzle it's for.
+//
+//     {"name": "zk-sudoku zk1-aaaafgqcaawqi", "description": "...", "image": "ipfs://...",
+//      "attributes": [{"trait_type": "Puzzle", "value": "zk1-aaaafgqcaawqi"}, ...]}
+//
+// the attributes are the puzzle (its id, or its hash for a supplied puzzle), the difficulty
+// tier, the date the badge was made and the proof's nullifier, which is what the contracts
+// record, so a badge maps to one completion. without an image URI the SVG goes inline as
+// `image_data`, which marketplaces render for badges kept entirely on chain.
+//
+//     let id = bundle.puzzle_id.as_ref();
+//     let metadata = BadgeMetadata::new(&values, &puzzle, id, &date, None)?;
+//     std::fs::write("badge.json", metadata.to_json())?;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+use sudoku::core::board::{Board, Difficulty};
+use sudoku::core::commitment::puzzle_hash;
+use sudoku::core::daily::Date;
+
+use crate::puzzle_id::PuzzleId;
+use crate::PublicValues;
+
+// the side of a cell in the SVG, which is 9 cells and a margin of half a cell on each side.
+const CELL: usize = 40;
+const SIZE: usize = 10 * CELL;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeError {
+    // the proof doesn't show a solve, the status it committed.
+    InvalidSolution(u8),
+    // the board isn't the puzzle the proof is for.
+    PuzzleMismatch,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadgeMetadata {
+    pub name: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    // the SVG itself when there's no image URI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_data: Option<String>,
+    pub attributes: Vec<Attribute>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+impl BadgeMetadata {
+    // `values` are the verified proof's, `puzzle` the board they're for and `date` the day of
+    // the badge. `image` is where the SVG will be hosted, if it's not going inline.
+    pub fn new(
+        values: &PublicValues,
+        puzzle: &Board,
+        puzzle_id: Option<&PuzzleId>,
+        date: &Date,
+        image: Option<&str>,
+    ) -> Result<BadgeMetadata, BadgeError> {
+        if !values.valid {
+            return Err(BadgeError::InvalidSolution(values.status));
+        }
+        if puzzle_hash(puzzle) != values.puzzle_hash {
+            return Err(BadgeError::PuzzleMismatch);
+        }
+        let name = match puzzle_id {
+            Some(id) => id.encode(),
+            None => hex(&values.puzzle_hash),
+        };
+        let difficulty = match Difficulty::from_u8(values.difficulty) {
+            Some(difficulty) => format!("{:?}", difficulty),
+            None => "Unrated".into(),
+        };
+        let attribute = |trait_type: &str, value: String| Attribute {
+            trait_type: trait_type.into(),
+            value,
+        };
+        Ok(BadgeMetadata {
+            name: format!("zk-sudoku {}", name),
+            description: format!(
+                "A {} sudoku with {} clues, solved and proven in zero knowledge on {}.",
+                difficulty.to_lowercase(),
+                values.clue_count,
+                date
+            ),
+            image: image.map(String::from),
+            image_data: image.is_none().then(|| silhouette_svg(puzzle)),
+            attributes: alloc::vec![
+                attribute("Puzzle", name),
+                attribute("Difficulty", difficulty),
+                attribute("Date", format!("{}", date)),
+                attribute("Nullifier", hex(&values.nullifier)),
+            ],
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("badge metadata always serializes")
+    }
+}
+
+// the grid with a dot on every given, the same for every puzzle with the same givens.
+pub fn silhouette_svg(puzzle: &Board) -> String {
+    let margin = CELL / 2;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {0} {0}\" width=\"{0}\" \
+         height=\"{0}\"><rect width=\"{0}\" height=\"{0}\" fill=\"#f8f6f0\"/>",
+        SIZE
+    );
+    for (i, _) in puzzle
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|(_, &cell)| cell != 0)
+    {
+        svg.push_str(&format!(
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"#1f2937\"/>",
+            margin + (i % 9) * CELL + CELL / 2,
+            margin + (i / 9) * CELL + CELL / 2,
+            CELL * 3 / 10
+        ));
+    }
+    // thin cell lines, the box lines over them.
+    for line in 0..=9 {
+        let at = margin + line * CELL;
+        let width = if line % 3 == 0 { 3 } else { 1 };
+        svg.push_str(&format!(
+            "<path d=\"M{0} {1}H{2}M{1} {0}V{2}\" stroke=\"#1f2937\" stroke-width=\"{3}\"/>",
+            margin,
+            at,
+            SIZE - margin,
+            width
+        ));
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::from("0x"), |s, b| s + &format!("{:02x}", b))
+}
+
+#[cfg(test)]
+mod badge_tests {
+    use super::*;
+    use sudoku::core::variant::Variant;
+
+    fn values(puzzle: &Board) -> PublicValues {
+        PublicValues {
+            puzzle_hash: puzzle_hash(puzzle),
+            valid: true,
+            difficulty: Difficulty::Hard as u8,
+            seed: 42,
+            nullifier: [0xab; 32],
+            clue_count: puzzle.clue_count(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_metadata() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let id = PuzzleId::new(42, Difficulty::Hard, &Variant::Classic).unwrap();
+        let date = Date::new(2026, 10, 16).unwrap();
+        let metadata =
+            BadgeMetadata::new(&values(&puzzle), &puzzle, Some(&id), &date, None).unwrap();
+        assert_eq!(metadata.name, format!("zk-sudoku {}", id.encode()));
+        assert!(metadata.description.contains("hard sudoku"));
+        assert!(metadata.description.ends_with("on 2026-10-16."));
+        assert_eq!(metadata.image, None);
+        assert_eq!(metadata.image_data, Some(silhouette_svg(&puzzle)));
+
+        let json: serde_json::Value = serde_json::from_str(&metadata.to_json()).unwrap();
+        assert!(json.get("image").is_none());
+        let attributes = json["attributes"].as_array().unwrap();
+        let value = |trait_type: &str| {
+            attributes
+                .iter()
+                .find(|a| a["trait_type"] == trait_type)
+                .map(|a| String::from(a["value"].as_str().unwrap()))
+        };
+        assert_eq!(value("Puzzle"), Some(id.encode()));
+        assert_eq!(value("Difficulty").as_deref(), Some("Hard"));
+        assert_eq!(value("Date").as_deref(), Some("2026-10-16"));
+        assert_eq!(value("Nullifier"), Some(hex(&[0xab; 32])));
+
+        // a supplied, unrated puzzle with its SVG hosted elsewhere.
+        let unrated = PublicValues {
+            difficulty: 0,
+            ..values(&puzzle)
+        };
+        let metadata =
+            BadgeMetadata::new(&unrated, &puzzle, None, &date, Some("ipfs://badge.svg")).unwrap();
+        assert_eq!(
+            metadata.name,
+            format!("zk-sudoku {}", hex(&puzzle_hash(&puzzle)))
+        );
+        assert_eq!(metadata.image.as_deref(), Some("ipfs://badge.svg"));
+        assert_eq!(metadata.image_data, None);
+        assert_eq!(metadata.attributes[1].value, "Unrated");
+        assert_eq!(
+            serde_json::from_str::<BadgeMetadata>(&metadata.to_json()).unwrap(),
+            metadata
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let date = Date::new(2026, 10, 16).unwrap();
+        let invalid = PublicValues {
+            valid: false,
+            status: 6,
+            ..values(&puzzle)
+        };
+        assert_eq!(
+            BadgeMetadata::new(&invalid, &puzzle, None, &date, None),
+            Err(BadgeError::InvalidSolution(6))
+        );
+        let other = Board::from_seed(43, Some(Difficulty::Hard));
+        assert_eq!(
+            BadgeMetadata::new(&values(&puzzle), &other, None, &date, None),
+            Err(BadgeError::PuzzleMismatch)
+        );
+    }
+
+    #[test]
+    fn test_silhouette_svg() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let svg = silhouette_svg(&puzzle);
+        assert!(svg.starts_with("<svg ") && svg.ends_with("</svg>"));
+        assert_eq!(svg.matches("<circle").count(), puzzle.clue_count() as usize);
+        assert_eq!(svg.matches("<path").count(), 10);
+        // no digit of the puzzle leaks, only where its givens are.
+        let mut same_givens = puzzle.clone();
+        for cell in same_givens.cells.iter_mut().filter(|cell| **cell != 0) {
+            *cell = *cell % 9 + 1;
+        }
+        assert_eq!(silhouette_svg(&same_givens), svg);
+        assert_eq!(
+            silhouette_svg(&Board { cells: [0; 81] })
+                .matches("<circle")
+                .count(),
+            0
+        );
+    }
+}
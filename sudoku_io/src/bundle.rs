@@ -0,0 +1,394 @@
+// a proof packed as one file players can pass around as evidence of a solve, a plain ustar
+// archive (`tar tf` lists it) holding:
+//
+//     manifest.txt       "key: value" lines, see below
+//     public-values.bin  the abi encoded PublicValues
+//     proof.bin          the backend's own proof encoding
+//
+// the manifest has the bundle version, the backend and what it proved on, the logic version of
+// the guest, the hash
+// of the verifying key and the program digest as 0x hex, and the puzzle id when the puzzle was
+// generated from a seed. together they're everything a ProofEnvelope holds, and decoding checks
+// the manifest says what the public values do. entries are written in that order with a zero
+// mtime, so the same proof always makes the same bytes, and unknown entries are ignored.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use sudoku::core::board::Difficulty;
+use sudoku::core::variant::{Variant, VARIANT_CLASSIC, VARIANT_X};
+
+use crate::envelope::{Backend, ProofEnvelope, ProvedOn};
+use crate::puzzle_id::{PuzzleId, PuzzleIdError};
+use crate::PublicValues;
+
+pub const BUNDLE_EXTENSION: &str = "zkproof";
+
+// bumped whenever the manifest or the entries change, older bundles are refused rather than
+// misread.
+pub const BUNDLE_VERSION: u8 = 2;
+
+const MANIFEST: &str = "manifest.txt";
+const PUBLIC_VALUES: &str = "public-values.bin";
+const PROOF: &str = "proof.bin";
+
+const BLOCK: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleError {
+    // not a ustar archive, a header checksum is off or it's cut short.
+    InvalidArchive,
+    MissingEntry(&'static str),
+    // a manifest line that isn't "key: value", or a value that doesn't parse.
+    InvalidManifest(&'static str),
+    UnsupportedVersion(u8),
+    UnknownBackend,
+    InvalidPublicValues,
+    // the manifest and the public values disagree about the named value.
+    Mismatch(&'static str),
+    PuzzleId(PuzzleIdError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofBundle {
+    pub envelope: ProofEnvelope,
+    // LOGIC_VERSION of the guest that made the proof.
+    pub logic_version: u32,
+    pub puzzle_id: Option<PuzzleId>,
+}
+
+impl ProofBundle {
+    // the logic version and puzzle id are read from the envelope's public values.
+    pub fn new(envelope: ProofEnvelope) -> Result<Self, BundleError> {
+        let values = PublicValues::abi_decode(&envelope.public_values)
+            .map_err(|_| BundleError::InvalidPublicValues)?;
+        Ok(ProofBundle {
+            logic_version: values.logic_version,
+            puzzle_id: seeded_id(&values),
+            envelope,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .fold(String::from("0x"), |s, b| s + &format!("{:02x}", b))
+        };
+        let mut manifest = format!(
+            "version: {}\nbackend: {}\nproved-on: {}\nlogic-version: {}\nvkey-hash: {}\n\
+             program-digest: {}\n",
+            BUNDLE_VERSION,
+            self.envelope.backend.name(),
+            self.envelope.proved_on.name(),
+            self.logic_version,
+            hex(&self.envelope.vkey_hash),
+            hex(&self.envelope.program_digest),
+        );
+        if let Some(id) = &self.puzzle_id {
+            manifest.push_str(&format!("puzzle-id: {}\n", id.encode()));
+        }
+
+        let mut archive = Vec::new();
+        for (name, data) in [
+            (MANIFEST, manifest.as_bytes()),
+            (PUBLIC_VALUES, &self.envelope.public_values[..]),
+            (PROOF, &self.envelope.proof[..]),
+        ] {
+            archive.extend_from_slice(&header(name, data.len()));
+            archive.extend_from_slice(data);
+            archive.resize(archive.len().next_multiple_of(BLOCK), 0);
+        }
+        // two zero blocks end the archive.
+        archive.resize(archive.len() + 2 * BLOCK, 0);
+        archive
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<ProofBundle, BundleError> {
+        let entries = entries(bytes)?;
+        let entry = |name: &'static str| {
+            entries
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, data)| *data)
+                .ok_or(BundleError::MissingEntry(name))
+        };
+        let manifest = core::str::from_utf8(entry(MANIFEST)?)
+            .map_err(|_| BundleError::InvalidManifest("manifest"))?;
+        let field = |key: &'static str| {
+            manifest
+                .lines()
+                .filter_map(|line| line.split_once(": "))
+                .find(|(k, _)| *k == key)
+                .map(|(_, value)| value.trim())
+        };
+        let required = |key: &'static str| field(key).ok_or(BundleError::InvalidManifest(key));
+        let unhex = |key: &'static str| -> Result<Vec<u8>, BundleError> {
+            let value = required(key)?
+                .strip_prefix("0x")
+                .ok_or(BundleError::InvalidManifest(key))?;
+            if value.len() % 2 != 0 {
+                return Err(BundleError::InvalidManifest(key));
+            }
+            (0..value.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&value[i..i + 2], 16))
+                .collect::<Result<_, _>>()
+                .map_err(|_| BundleError::InvalidManifest(key))
+        };
+
+        let version: u8 = required("version")?
+            .parse()
+            .map_err(|_| BundleError::InvalidManifest("version"))?;
+        if version != BUNDLE_VERSION {
+            return Err(BundleError::UnsupportedVersion(version));
+        }
+        let backend =
+            Backend::from_name(required("backend")?).ok_or(BundleError::UnknownBackend)?;
+        let proved_on = ProvedOn::from_name(required("proved-on")?)
+            .ok_or(BundleError::InvalidManifest("proved-on"))?;
+        let logic_version: u32 = required("logic-version")?
+            .parse()
+            .map_err(|_| BundleError::InvalidManifest("logic-version"))?;
+        let puzzle_id = field("puzzle-id")
+            .map(PuzzleId::decode)
+            .transpose()
+            .map_err(BundleError::PuzzleId)?;
+
+        let bundle = ProofBundle {
+            envelope: ProofEnvelope {
+                backend,
+                proved_on,
+                vkey_hash: unhex("vkey-hash")?,
+                program_digest: unhex("program-digest")?,
+                public_values: entry(PUBLIC_VALUES)?.to_vec(),
+                proof: entry(PROOF)?.to_vec(),
+            },
+            logic_version,
+            puzzle_id,
+        };
+
+        // the manifest is only a summary, the public values are what the proof proves.
+        let values = PublicValues::abi_decode(&bundle.envelope.public_values)
+            .map_err(|_| BundleError::InvalidPublicValues)?;
+        if values.logic_version != bundle.logic_version {
+            return Err(BundleError::Mismatch("logic-version"));
+        }
+        if seeded_id(&values) != bundle.puzzle_id {
+            return Err(BundleError::Mismatch("puzzle-id"));
+        }
+        Ok(bundle)
+    }
+}
+
+// the id of a seeded puzzle, supplied puzzles (unrated, seed 0) and killer cages have none.
+fn seeded_id(values: &PublicValues) -> Option<PuzzleId> {
+    let difficulty = Difficulty::from_u8(values.difficulty)?;
+    let variant = match values.variant {
+        VARIANT_CLASSIC => Variant::Classic,
+        VARIANT_X => Variant::X,
+        _ => return None,
+    };
+    PuzzleId::new(values.seed, difficulty, &variant).ok()
+}
+
+// a ustar header for a regular file, owned by root with mode 0644.
+fn header(name: &str, size: usize) -> [u8; BLOCK] {
+    let mut header = [0; BLOCK];
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    header[100..108].copy_from_slice(b"0000644\0");
+    header[108..116].copy_from_slice(b"0000000\0");
+    header[116..124].copy_from_slice(b"0000000\0");
+    header[124..136].copy_from_slice(format!("{:011o}\0", size).as_bytes());
+    header[136..148].copy_from_slice(b"00000000000\0");
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    let checksum = checksum(&header);
+    header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+    header
+}
+
+// the sum of the header's bytes with the checksum field taken as spaces.
+fn checksum(header: &[u8]) -> u32 {
+    header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| if (148..156).contains(&i) { b' ' } else { b } as u32)
+        .sum()
+}
+
+// the regular files in the archive, up to the first zero block.
+fn entries(bytes: &[u8]) -> Result<Vec<(String, &[u8])>, BundleError> {
+    let mut entries = Vec::new();
+    let mut rest = bytes;
+    loop {
+        if rest.len() < BLOCK {
+            return Err(BundleError::InvalidArchive);
+        }
+        let (header, tail) = rest.split_at(BLOCK);
+        if header.iter().all(|&b| b == 0) {
+            return Ok(entries);
+        }
+        if &header[257..262] != b"ustar" || octal(&header[148..156])? != checksum(header) as u64 {
+            return Err(BundleError::InvalidArchive);
+        }
+        let size = octal(&header[124..136])? as usize;
+        let padded = size
+            .checked_next_multiple_of(BLOCK)
+            .ok_or(BundleError::InvalidArchive)?;
+        if tail.len() < padded {
+            return Err(BundleError::InvalidArchive);
+        }
+        // '0' and NUL are both regular files, directories and the like carry nothing here.
+        if header[156] == b'0' || header[156] == 0 {
+            let name = &header[..100];
+            let name = &name[..name.iter().position(|&b| b == 0).unwrap_or(100)];
+            let name = core::str::from_utf8(name).map_err(|_| BundleError::InvalidArchive)?;
+            entries.push((String::from(name), &tail[..size]));
+        }
+        rest = &tail[padded..];
+    }
+}
+
+// a NUL or space terminated octal field.
+fn octal(field: &[u8]) -> Result<u64, BundleError> {
+    let digits = field
+        .iter()
+        .take_while(|&&b| b != 0 && b != b' ')
+        .try_fold(0u64, |value, &b| match b {
+            b'0'..=b'7' => value.checked_mul(8).map(|v| v + (b - b'0') as u64),
+            _ => None,
+        });
+    digits.ok_or(BundleError::InvalidArchive)
+}
+
+#[cfg(test)]
+mod bundle_tests {
+    use super::*;
+    use crate::{puzzle_from_seed, verify_input, ProofInput, PuzzleSource};
+    use alloc::vec;
+    use sudoku::core::solver::DancingLinks;
+
+    fn envelope(values: &PublicValues) -> ProofEnvelope {
+        ProofEnvelope {
+            backend: Backend::Sp1,
+            proved_on: ProvedOn::Cpu,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![5; 32],
+            public_values: values.abi_encode().to_vec(),
+            proof: vec![9; 700],
+        }
+    }
+
+    fn seeded() -> PublicValues {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let input = ProofInput::new(
+            PuzzleSource::Seed {
+                seed: 666,
+                difficulty: Difficulty::Medium as u8,
+            },
+            solution.cells.to_vec(),
+        );
+        verify_input(&input, |_, _, _| false).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let values = seeded();
+        let bundle = ProofBundle::new(envelope(&values)).unwrap();
+        assert_eq!(bundle.logic_version, crate::LOGIC_VERSION);
+        assert_eq!(
+            bundle.puzzle_id.map(|id| id.encode()),
+            Some(String::from("zk1-aaaafgqcaawqi"))
+        );
+
+        let bytes = bundle.encode();
+        assert_eq!(bytes.len() % BLOCK, 0);
+        assert_eq!(&bytes[257..262], b"ustar");
+        assert_eq!(ProofBundle::decode(&bytes), Ok(bundle.clone()));
+        // the same proof makes the same file.
+        assert_eq!(bundle.encode(), bytes);
+
+        // supplied puzzles have no id.
+        let supplied = PublicValues {
+            seed: 0,
+            difficulty: crate::DIFFICULTY_UNRATED,
+            ..values
+        };
+        let bundle = ProofBundle::new(envelope(&supplied)).unwrap();
+        assert_eq!(bundle.puzzle_id, None);
+        assert_eq!(ProofBundle::decode(&bundle.encode()), Ok(bundle));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let bundle = ProofBundle::new(envelope(&seeded())).unwrap();
+        let bytes = bundle.encode();
+
+        assert_eq!(
+            ProofBundle::decode(&bytes[..1000]),
+            Err(BundleError::InvalidArchive)
+        );
+        let mut corrupt = bytes.clone();
+        corrupt[0] = b'x';
+        assert_eq!(
+            ProofBundle::decode(&corrupt),
+            Err(BundleError::InvalidArchive)
+        );
+
+        // entries re-packed with one left out or a manifest edited.
+        let repack = |skip: &str, edit: &dyn Fn(String) -> String| {
+            let mut archive = Vec::new();
+            for (name, data) in entries(&bytes).unwrap() {
+                if name == skip {
+                    continue;
+                }
+                let data = if name == MANIFEST {
+                    edit(String::from(core::str::from_utf8(data).unwrap())).into_bytes()
+                } else {
+                    data.to_vec()
+                };
+                archive.extend_from_slice(&header(&name, data.len()));
+                archive.extend_from_slice(&data);
+                archive.resize(archive.len().next_multiple_of(BLOCK), 0);
+            }
+            archive.resize(archive.len() + 2 * BLOCK, 0);
+            ProofBundle::decode(&archive)
+        };
+        assert_eq!(repack("", &|m| m), Ok(bundle.clone()));
+        assert_eq!(repack(PROOF, &|m| m), Err(BundleError::MissingEntry(PROOF)));
+        assert_eq!(
+            repack("", &|m| m.replace("version: 2", "version: 3")),
+            Err(BundleError::UnsupportedVersion(3))
+        );
+        assert_eq!(
+            repack("", &|m| m.replace("backend: sp1", "backend: risc0")),
+            Err(BundleError::UnknownBackend)
+        );
+        assert_eq!(
+            repack("", &|m| m.replace("vkey-hash: 0x", "vkey-hash: ")),
+            Err(BundleError::InvalidManifest("vkey-hash"))
+        );
+        assert_eq!(
+            repack("", &|m| m.replace(
+                &format!("logic-version: {}", crate::LOGIC_VERSION),
+                "logic-version: 1"
+            )),
+            Err(BundleError::Mismatch("logic-version"))
+        );
+        let other = PuzzleId::new(667, Difficulty::Medium, &Variant::Classic)
+            .unwrap()
+            .encode();
+        assert_eq!(
+            repack("", &|m| m.replace("zk1-aaaafgqcaawqi", &other)),
+            Err(BundleError::Mismatch("puzzle-id"))
+        );
+        assert_eq!(
+            repack("", &|m| m.replace("zk1-aaaafgqcaawqi", "zk1-baaafgqcaawqi")),
+            Err(BundleError::PuzzleId(PuzzleIdError::BadChecksum))
+        );
+    }
+}
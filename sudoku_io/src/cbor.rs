@@ -0,0 +1,225 @@
+// CBOR encodings of puzzles and proof envelopes, a compact alternative to the JSON documents for
+// the HTTP service and mobile clients that still describes itself: each is a map with string
+// keys, its format version first,
+//
+//     {"version": 1, "cells": h'0503...', "variant": "Classic"}
+//     {"version": 1, "backend": "sp1", "provedOn": "network", "vkeyHash": h'...', ...}
+//
+// boards and byte strings are CBOR byte strings rather than arrays of numbers, so a classic puzzle
+// is about a hundred bytes and an envelope only a few more than the proof in it. decoding checks
+// the cells are 0-9 and the cages on the board, like the guests' decoding.
+//
+//     let bytes = cbor::encode_envelope(&envelope);
+//     let envelope = cbor::decode_envelope(&bytes)?;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+use sudoku::core::board::Board;
+use sudoku::core::variant::Variant;
+
+use crate::envelope::{Backend, ProofEnvelope, ProvedOn};
+
+// bumped when an encoding changes in a way older readers would misread. encodings of another
+// version are refused.
+pub const CBOR_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CborError {
+    // not CBOR, or not the map expected.
+    InvalidCbor(String),
+    UnsupportedVersion(u32),
+    // a board that isn't 81 cells of 0-9.
+    InvalidCells,
+    InvalidVariant(&'static str),
+    UnknownBackend(String),
+    UnknownProvedOn(String),
+}
+
+#[derive(Serialize, Deserialize)]
+struct PuzzleCbor {
+    version: u32,
+    #[serde(with = "serde_bytes")]
+    cells: Vec<u8>,
+    variant: Variant,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EnvelopeCbor {
+    version: u32,
+    backend: String,
+    proved_on: String,
+    #[serde(with = "serde_bytes")]
+    vkey_hash: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    program_digest: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    public_values: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    proof: Vec<u8>,
+}
+
+pub fn encode_puzzle(puzzle: &Board, variant: &Variant) -> Vec<u8> {
+    encode(&PuzzleCbor {
+        version: CBOR_VERSION,
+        cells: puzzle.cells.to_vec(),
+        variant: variant.clone(),
+    })
+}
+
+pub fn decode_puzzle(bytes: &[u8]) -> Result<(Board, Variant), CborError> {
+    let puzzle: PuzzleCbor = decode(bytes)?;
+    check_version(puzzle.version)?;
+    let cells: [u8; 81] = puzzle
+        .cells
+        .try_into()
+        .map_err(|_| CborError::InvalidCells)?;
+    let board = Board::from_array(cells).map_err(|_| CborError::InvalidCells)?;
+    // the same checks the guests run on an encoded variant, which has room for 81 cells a cage.
+    if let Variant::Killer(cages) = &puzzle.variant {
+        if cages.iter().any(|cage| cage.cells.len() > 81) {
+            return Err(CborError::InvalidVariant("cage larger than the board"));
+        }
+    }
+    let variant =
+        Variant::from_bytes(&puzzle.variant.to_bytes()).map_err(CborError::InvalidVariant)?;
+    Ok((board, variant))
+}
+
+pub fn encode_envelope(envelope: &ProofEnvelope) -> Vec<u8> {
+    encode(&EnvelopeCbor {
+        version: CBOR_VERSION,
+        backend: envelope.backend.name().into(),
+        proved_on: envelope.proved_on.name().into(),
+        vkey_hash: envelope.vkey_hash.clone(),
+        program_digest: envelope.program_digest.clone(),
+        public_values: envelope.public_values.clone(),
+        proof: envelope.proof.clone(),
+    })
+}
+
+pub fn decode_envelope(bytes: &[u8]) -> Result<ProofEnvelope, CborError> {
+    let envelope: EnvelopeCbor = decode(bytes)?;
+    check_version(envelope.version)?;
+    Ok(ProofEnvelope {
+        backend: Backend::from_name(&envelope.backend)
+            .ok_or(CborError::UnknownBackend(envelope.backend))?,
+        proved_on: ProvedOn::from_name(&envelope.proved_on)
+            .ok_or(CborError::UnknownProvedOn(envelope.proved_on))?,
+        vkey_hash: envelope.vkey_hash,
+        program_digest: envelope.program_digest,
+        public_values: envelope.public_values,
+        proof: envelope.proof,
+    })
+}
+
+fn encode<T: Serialize>(value: &T) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    // writing to a Vec can't fail, and the maps have nothing CBOR can't represent.
+    ciborium::into_writer(value, &mut bytes).unwrap();
+    bytes
+}
+
+fn decode<T: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<T, CborError> {
+    ciborium::from_reader(bytes).map_err(|e| CborError::InvalidCbor(alloc::format!("{:?}", e)))
+}
+
+fn check_version(version: u32) -> Result<(), CborError> {
+    match version {
+        CBOR_VERSION => Ok(()),
+        _ => Err(CborError::UnsupportedVersion(version)),
+    }
+}
+
+#[cfg(test)]
+mod cbor_tests {
+    use super::*;
+    use crate::envelope::MOCK_PROOF;
+    use alloc::vec;
+    use sudoku::core::board::Difficulty;
+    use sudoku::core::variant::Cage;
+
+    #[test]
+    fn test_round_trips() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let killer = Variant::Killer(vec![Cage {
+            sum: 17,
+            cells: vec![0, 9],
+        }]);
+        let bytes = encode_puzzle(&puzzle, &killer);
+        // the 81 cells, the cage and the keys.
+        assert!(bytes.len() < 150);
+        assert_eq!(decode_puzzle(&bytes), Ok((puzzle.clone(), killer)));
+
+        let envelope = ProofEnvelope {
+            backend: Backend::OpenVm,
+            proved_on: ProvedOn::Cuda,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![8; 32],
+            public_values: vec![9; 544],
+            proof: vec![200; 1000],
+        };
+        let bytes = encode_envelope(&envelope);
+        // byte strings, not a CBOR number per byte.
+        assert!(bytes.len() < 32 + 32 + 544 + 1000 + 150);
+        assert_eq!(decode_envelope(&bytes), Ok(envelope));
+
+        let mock = ProofEnvelope {
+            proof: MOCK_PROOF.to_vec(),
+            ..decode_envelope(&bytes).unwrap()
+        };
+        assert!(decode_envelope(&encode_envelope(&mock)).unwrap().is_mock());
+    }
+
+    #[test]
+    fn test_errors() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        assert!(matches!(
+            decode_puzzle(b"not cbor"),
+            Err(CborError::InvalidCbor(_))
+        ));
+
+        let mut wrong = PuzzleCbor {
+            version: 2,
+            cells: puzzle.cells.to_vec(),
+            variant: Variant::Classic,
+        };
+        assert_eq!(
+            decode_puzzle(&encode(&wrong)),
+            Err(CborError::UnsupportedVersion(2))
+        );
+        wrong.version = CBOR_VERSION;
+        wrong.cells[0] = 10;
+        assert_eq!(decode_puzzle(&encode(&wrong)), Err(CborError::InvalidCells));
+        wrong.cells.truncate(80);
+        assert_eq!(decode_puzzle(&encode(&wrong)), Err(CborError::InvalidCells));
+
+        let cage = PuzzleCbor {
+            version: CBOR_VERSION,
+            cells: puzzle.cells.to_vec(),
+            variant: Variant::Killer(vec![Cage {
+                sum: 3,
+                cells: vec![0, 81],
+            }]),
+        };
+        assert!(matches!(
+            decode_puzzle(&encode(&cage)),
+            Err(CborError::InvalidVariant(_))
+        ));
+
+        let envelope = EnvelopeCbor {
+            version: CBOR_VERSION,
+            backend: "risc0".into(),
+            proved_on: "cpu".into(),
+            vkey_hash: vec![],
+            program_digest: vec![],
+            public_values: vec![],
+            proof: vec![],
+        };
+        assert_eq!(
+            decode_envelope(&encode(&envelope)),
+            Err(CborError::UnknownBackend("risc0".into()))
+        );
+    }
+}
@@ -0,0 +1,468 @@
+// the JSON documents third-party apps and services exchange puzzles, solutions and proofs in.
+// every document carries the format version and its type:
+//
+//     {"version": 1, "type": "puzzle", "cells": "530070000600195000...", "difficulty": 2}
+//     {"version": 1, "type": "solution", "puzzle": {...}, "cells": "534678912672195348..."}
+//     {"version": 1, "type": "proof", "backend": "sp1", "provedOn": "network", ...}
+//
+// boards are strings of 81 digits, row-wise, 0 for empty, byte strings 0x hex, keys camelCase
+// like the host's --json output. a proof document is a bundle's metadata and public values, not
+// the proof itself, which stays in the .zkproof.
+//
+// the JSON schema of these types is published as schema/document.v1.json. it's generated from
+// them with the `schema` feature, and `test_schema` fails when the two drift apart:
+//
+//     ZK_SUDOKU_UPDATE_SCHEMA=1 cargo test -p sudoku_io --features schema test_schema
+//
+// web frontends get them as TypeScript from ts-rs, written to bindings/ by the tests it adds:
+//
+//     cargo test -p sudoku_io --features typescript export_bindings
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+// the schemars and ts-rs derives expect std's prelude and vec! in scope.
+#[cfg(any(feature = "schema", feature = "typescript"))]
+extern crate std;
+#[cfg(any(feature = "schema", feature = "typescript"))]
+use std::prelude::rust_2021::*;
+#[cfg(feature = "schema")]
+use std::vec;
+
+use serde::{Deserialize, Serialize};
+use sudoku::core::board::Board;
+use sudoku::core::variant::{Cage, Variant};
+
+use crate::bundle::ProofBundle;
+use crate::PublicValues;
+
+// bumped when a document changes in a way older readers would misread. documents of another
+// version are refused.
+pub const DOCUMENT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocumentError {
+    InvalidJson(String),
+    UnsupportedVersion(u32),
+    // a board that isn't 81 digits.
+    InvalidCells,
+    // a hex string that doesn't decode, or decodes to the wrong length.
+    InvalidHex(&'static str),
+    InvalidVariant(&'static str),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+pub struct Document {
+    pub version: u32,
+    #[serde(flatten)]
+    pub body: DocumentBody,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DocumentBody {
+    Puzzle(PuzzleDocument),
+    Solution(SolutionDocument),
+    Proof(Box<ProofDocument>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct PuzzleDocument {
+    // the zk1-... id of a seeded puzzle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub id: Option<String>,
+    #[cfg_attr(feature = "schema", schemars(regex(pattern = r"^[0-9]{81}$")))]
+    pub cells: String,
+    // the tier the proofs commit, 1 easy to 3 hard. absent for unrated puzzles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub difficulty: Option<u8>,
+    #[serde(default, skip_serializing_if = "VariantDocument::is_classic")]
+    #[cfg_attr(feature = "typescript", ts(as = "Option<VariantDocument>", optional))]
+    pub variant: VariantDocument,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct SolutionDocument {
+    pub puzzle: PuzzleDocument,
+    #[cfg_attr(feature = "schema", schemars(regex(pattern = r"^[0-9]{81}$")))]
+    pub cells: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum VariantDocument {
+    #[default]
+    Classic,
+    X,
+    Killer {
+        cages: Vec<CageDocument>,
+    },
+}
+
+// a killer cage, the values in `cells` (row-wise indices) are distinct and add up to `sum`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+pub struct CageDocument {
+    pub sum: u8,
+    pub cells: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct ProofDocument {
+    // sp1, openvm or mock.
+    pub backend: String,
+    pub proved_on: String,
+    pub vkey_hash: String,
+    pub program_digest: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "typescript", ts(optional))]
+    pub puzzle_id: Option<String>,
+    pub public_values: PublicValuesDocument,
+}
+
+// PublicValues with byte strings as 0x hex, the keys the host prints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "typescript", derive(ts_rs::TS), ts(export))]
+#[serde(rename_all = "camelCase")]
+pub struct PublicValuesDocument {
+    pub puzzle_hash: String,
+    pub solution_commitment: String,
+    pub valid: bool,
+    pub difficulty: u8,
+    pub clue_count: u8,
+    pub seed: u32,
+    pub nullifier: String,
+    // a number in JSON, ts-rs would make it a bigint.
+    #[cfg_attr(feature = "typescript", ts(type = "number"))]
+    pub epoch: u64,
+    pub reward_address: String,
+    pub status: u8,
+    pub policy_hash: String,
+    pub organizer_key: String,
+    pub variant: u8,
+    pub logic_version: u32,
+    pub cells_root: String,
+    pub givens_hash: String,
+    pub nonce: String,
+}
+
+impl Document {
+    pub fn new(body: DocumentBody) -> Self {
+        Document {
+            version: DOCUMENT_VERSION,
+            body,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("documents always serialize")
+    }
+
+    // refuses documents of another version before reading the rest, so a later format's
+    // fields aren't misread as this one's.
+    pub fn from_json(text: &str) -> Result<Document, DocumentError> {
+        let invalid = |e: serde_json::Error| DocumentError::InvalidJson(e.to_string());
+        let value: serde_json::Value = serde_json::from_str(text).map_err(invalid)?;
+        match value.get("version").and_then(|v| v.as_u64()) {
+            Some(version) if version == DOCUMENT_VERSION as u64 => {}
+            Some(version) => return Err(DocumentError::UnsupportedVersion(version as u32)),
+            None => return Err(DocumentError::InvalidJson("no version".into())),
+        }
+        serde_json::from_value(value).map_err(invalid)
+    }
+}
+
+impl PuzzleDocument {
+    pub fn new(puzzle: &Board, variant: &Variant) -> Self {
+        PuzzleDocument {
+            id: None,
+            cells: cells_string(puzzle),
+            difficulty: None,
+            variant: variant.into(),
+        }
+    }
+
+    pub fn board(&self) -> Result<Board, DocumentError> {
+        parse_cells(&self.cells)
+    }
+}
+
+impl SolutionDocument {
+    pub fn board(&self) -> Result<Board, DocumentError> {
+        parse_cells(&self.cells)
+    }
+}
+
+impl VariantDocument {
+    fn is_classic(&self) -> bool {
+        *self == VariantDocument::Classic
+    }
+
+    // the variant with its cages checked the way the guests' decoding checks them.
+    pub fn variant(&self) -> Result<Variant, DocumentError> {
+        let variant = match self {
+            VariantDocument::Classic => Variant::Classic,
+            VariantDocument::X => Variant::X,
+            VariantDocument::Killer { cages } => {
+                if cages.iter().any(|cage| cage.cells.len() > 81) {
+                    return Err(DocumentError::InvalidVariant("cage larger than the board"));
+                }
+                Variant::Killer(
+                    cages
+                        .iter()
+                        .map(|cage| Cage {
+                            sum: cage.sum,
+                            cells: cage.cells.clone(),
+                        })
+                        .collect(),
+                )
+            }
+        };
+        Variant::from_bytes(&variant.to_bytes()).map_err(DocumentError::InvalidVariant)
+    }
+}
+
+impl From<&Variant> for VariantDocument {
+    fn from(variant: &Variant) -> Self {
+        match variant {
+            Variant::Classic => VariantDocument::Classic,
+            Variant::X => VariantDocument::X,
+            Variant::Killer(cages) => VariantDocument::Killer {
+                cages: cages
+                    .iter()
+                    .map(|cage| CageDocument {
+                        sum: cage.sum,
+                        cells: cage.cells.clone(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl From<&ProofBundle> for ProofDocument {
+    fn from(bundle: &ProofBundle) -> Self {
+        let envelope = &bundle.envelope;
+        // ProofBundle::new and decode have already checked them.
+        let values = PublicValues::abi_decode(&envelope.public_values).unwrap_or_default();
+        ProofDocument {
+            backend: envelope.backend.name().into(),
+            proved_on: envelope.proved_on.name().into(),
+            vkey_hash: hex(&envelope.vkey_hash),
+            program_digest: hex(&envelope.program_digest),
+            puzzle_id: bundle.puzzle_id.map(|id| id.encode()),
+            public_values: (&values).into(),
+        }
+    }
+}
+
+impl From<&PublicValues> for PublicValuesDocument {
+    fn from(values: &PublicValues) -> Self {
+        PublicValuesDocument {
+            puzzle_hash: hex(&values.puzzle_hash),
+            solution_commitment: hex(&values.solution_commitment),
+            valid: values.valid,
+            difficulty: values.difficulty,
+            clue_count: values.clue_count,
+            seed: values.seed,
+            nullifier: hex(&values.nullifier),
+            epoch: values.epoch,
+            reward_address: hex(&values.reward_address),
+            status: values.status,
+            policy_hash: hex(&values.policy_hash),
+            organizer_key: hex(&values.organizer_key),
+            variant: values.variant,
+            logic_version: values.logic_version,
+            cells_root: hex(&values.cells_root),
+            givens_hash: hex(&values.givens_hash),
+            nonce: hex(&values.nonce),
+        }
+    }
+}
+
+impl TryFrom<&PublicValuesDocument> for PublicValues {
+    type Error = DocumentError;
+
+    fn try_from(doc: &PublicValuesDocument) -> Result<Self, DocumentError> {
+        Ok(PublicValues {
+            puzzle_hash: unhex(&doc.puzzle_hash, "puzzleHash")?,
+            solution_commitment: unhex(&doc.solution_commitment, "solutionCommitment")?,
+            valid: doc.valid,
+            difficulty: doc.difficulty,
+            seed: doc.seed,
+            nullifier: unhex(&doc.nullifier, "nullifier")?,
+            clue_count: doc.clue_count,
+            epoch: doc.epoch,
+            reward_address: unhex(&doc.reward_address, "rewardAddress")?,
+            status: doc.status,
+            policy_hash: unhex(&doc.policy_hash, "policyHash")?,
+            organizer_key: unhex(&doc.organizer_key, "organizerKey")?,
+            variant: doc.variant,
+            logic_version: doc.logic_version,
+            cells_root: unhex(&doc.cells_root, "cellsRoot")?,
+            givens_hash: unhex(&doc.givens_hash, "givensHash")?,
+            nonce: unhex(&doc.nonce, "nonce")?,
+        })
+    }
+}
+
+pub fn cells_string(board: &Board) -> String {
+    board
+        .cells
+        .iter()
+        .map(|&cell| (b'0' + cell) as char)
+        .collect()
+}
+
+fn parse_cells(cells: &str) -> Result<Board, DocumentError> {
+    let digits: Vec<u8> = cells
+        .bytes()
+        .map(|c| c.is_ascii_digit().then(|| c - b'0'))
+        .collect::<Option<_>>()
+        .ok_or(DocumentError::InvalidCells)?;
+    let cells: [u8; 81] = digits.try_into().map_err(|_| DocumentError::InvalidCells)?;
+    Board::from_array(cells).map_err(|_| DocumentError::InvalidCells)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::from("0x"), |s, b| s + &format!("{:02x}", b))
+}
+
+fn unhex<const N: usize>(value: &str, name: &'static str) -> Result<[u8; N], DocumentError> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    let bytes: Vec<u8> = digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| match core::str::from_utf8(pair) {
+            Ok(pair) if pair.len() == 2 => u8::from_str_radix(pair, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<_>>()
+        .ok_or(DocumentError::InvalidHex(name))?;
+    bytes
+        .try_into()
+        .map_err(|_| DocumentError::InvalidHex(name))
+}
+
+#[cfg(test)]
+mod document_tests {
+    use super::*;
+    use crate::envelope::{Backend, ProofEnvelope, ProvedOn};
+    use crate::puzzle_id::PuzzleId;
+    use alloc::vec;
+    use sudoku::core::board::Difficulty;
+
+    #[test]
+    fn test_round_trip() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Medium));
+        let id = PuzzleId::new(42, Difficulty::Medium, &Variant::Classic).unwrap();
+        let doc = Document::new(DocumentBody::Puzzle(PuzzleDocument {
+            id: Some(id.encode()),
+            difficulty: Some(Difficulty::Medium as u8),
+            ..PuzzleDocument::new(&puzzle, &Variant::Classic)
+        }));
+        let json = doc.to_json();
+        assert!(json.contains("\"type\": \"puzzle\""));
+        assert!(!json.contains("variant"));
+        assert_eq!(Document::from_json(&json).unwrap(), doc);
+
+        let killer = Variant::Killer(vec![Cage {
+            sum: 3,
+            cells: vec![0, 1],
+        }]);
+        let doc = PuzzleDocument::new(&puzzle, &killer);
+        assert_eq!(doc.board().unwrap(), puzzle);
+        assert_eq!(doc.variant.variant().unwrap(), killer);
+
+        let values = PublicValues {
+            valid: true,
+            seed: 42,
+            nonce: [3; 32],
+            ..Default::default()
+        };
+        let bundle = ProofBundle::new(ProofEnvelope {
+            backend: Backend::Sp1,
+            proved_on: ProvedOn::Network,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![5; 32],
+            public_values: values.abi_encode().to_vec(),
+            proof: vec![9; 10],
+        })
+        .unwrap();
+        let proof = ProofDocument::from(&bundle);
+        assert_eq!(proof.backend, "sp1");
+        assert_eq!(
+            PublicValues::try_from(&proof.public_values).unwrap(),
+            values
+        );
+        let doc = Document::new(DocumentBody::Proof(Box::new(proof)));
+        assert_eq!(Document::from_json(&doc.to_json()).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_errors() {
+        let doc = |json: &str| Document::from_json(json);
+        assert_eq!(
+            doc(r#"{"version": 2, "type": "puzzle", "cells": ""}"#),
+            Err(DocumentError::UnsupportedVersion(2))
+        );
+        assert!(matches!(
+            doc(r#"{"type": "puzzle"}"#),
+            Err(DocumentError::InvalidJson(_))
+        ));
+        assert!(matches!(
+            doc(r#"{"version": 1, "type": "board"}"#),
+            Err(DocumentError::InvalidJson(_))
+        ));
+
+        let puzzle = PuzzleDocument {
+            cells: "12".into(),
+            ..PuzzleDocument::new(&Board::from_array([0; 81]).unwrap(), &Variant::X)
+        };
+        assert_eq!(puzzle.board(), Err(DocumentError::InvalidCells));
+        let cage = VariantDocument::Killer {
+            cages: vec![CageDocument {
+                sum: 3,
+                cells: vec![81],
+            }],
+        };
+        assert!(cage.variant().is_err());
+    }
+
+    // the published schema is what the types generate.
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_schema() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/document.v1.json");
+        let schema = serde_json::to_string_pretty(&schemars::schema_for!(Document)).unwrap() + "\n";
+        if std::env::var_os("ZK_SUDOKU_UPDATE_SCHEMA").is_some() {
+            std::fs::write(path, &schema).unwrap();
+        }
+        let published = std::fs::read_to_string(path).unwrap();
+        assert_eq!(
+            published, schema,
+            "schema/document.v1.json is out of date, see document.rs"
+        );
+    }
+}
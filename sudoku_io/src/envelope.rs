@@ -0,0 +1,288 @@
+// proofs saved to disk by the hosts, so one machine can prove and another verify later. the
+// envelope records which backend made the proof, the hash of the program's verifying key and
+// the digest of the program itself next to the public values and the backend's own proof
+// encoding, and where the proof was made:
+//
+//     magic "zkSD" | version u8 | backend u8 | proved on u8 | vkey hash | program digest |
+//     public values | proof
+//
+// the last four are byte strings, each prefixed with its length as a big endian u32.
+//
+// a mock envelope has MOCK_PROOF in place of the proof: the guest was only executed, nothing
+// about it can be checked, and verifiers refuse it unless told otherwise.
+use alloc::vec::Vec;
+
+pub const ENVELOPE_MAGIC: [u8; 4] = *b"zkSD";
+
+// bumped whenever the layout changes, older files are refused rather than misread.
+pub const ENVELOPE_VERSION: u8 = 3;
+
+// no backend's proof encoding starts like this.
+pub const MOCK_PROOF: &[u8] = b"zk-sudoku mock proof, executed only, not verifiable";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    Sp1 = 1,
+    OpenVm = 2,
+    Jolt = 3,
+}
+
+impl Backend {
+    pub fn from_u8(value: u8) -> Option<Backend> {
+        match value {
+            1 => Some(Backend::Sp1),
+            2 => Some(Backend::OpenVm),
+            3 => Some(Backend::Jolt),
+            _ => None,
+        }
+    }
+
+    // lowercase, as the hosts take it on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Backend::Sp1 => "sp1",
+            Backend::OpenVm => "openvm",
+            Backend::Jolt => "jolt",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Backend> {
+        [Backend::Sp1, Backend::OpenVm, Backend::Jolt]
+            .into_iter()
+            .find(|backend| backend.name() == name)
+    }
+}
+
+// the hardware a proof came from, only informational: it verifies the same wherever it was made.
+// mocks are "made" on the cpu that executed them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProvedOn {
+    Cpu = 1,
+    Cuda = 2,
+    Network = 3,
+}
+
+impl ProvedOn {
+    pub fn from_u8(value: u8) -> Option<ProvedOn> {
+        match value {
+            1 => Some(ProvedOn::Cpu),
+            2 => Some(ProvedOn::Cuda),
+            3 => Some(ProvedOn::Network),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProvedOn::Cpu => "cpu",
+            ProvedOn::Cuda => "cuda",
+            ProvedOn::Network => "network",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ProvedOn> {
+        [ProvedOn::Cpu, ProvedOn::Cuda, ProvedOn::Network]
+            .into_iter()
+            .find(|proved_on| proved_on.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeError {
+    // not an envelope, or cut short.
+    InvalidEncoding,
+    UnsupportedVersion(u8),
+    UnknownBackend(u8),
+    UnknownProvedOn(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofEnvelope {
+    pub backend: Backend,
+    pub proved_on: ProvedOn,
+    // the backend's hash of the verifying key the proof checks against, for sp1 the 32 bytes
+    // of `HashableKey::bytes32`.
+    pub vkey_hash: Vec<u8>,
+    // sha256 of the guest the proof ran, the ELF for sp1 and the transpiled executable for
+    // openvm, so a verifier can pin the exact program it accepts.
+    pub program_digest: Vec<u8>,
+    pub public_values: Vec<u8>,
+    pub proof: Vec<u8>,
+}
+
+impl ProofEnvelope {
+    pub fn is_mock(&self) -> bool {
+        self.proof == MOCK_PROOF
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            23 + self.vkey_hash.len()
+                + self.program_digest.len()
+                + self.public_values.len()
+                + self.proof.len(),
+        );
+        bytes.extend_from_slice(&ENVELOPE_MAGIC);
+        bytes.push(ENVELOPE_VERSION);
+        bytes.push(self.backend as u8);
+        bytes.push(self.proved_on as u8);
+        for field in [
+            &self.vkey_hash,
+            &self.program_digest,
+            &self.public_values,
+            &self.proof,
+        ] {
+            bytes.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(field);
+        }
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<ProofEnvelope, EnvelopeError> {
+        if bytes.len() < 7 || bytes[..4] != ENVELOPE_MAGIC {
+            return Err(EnvelopeError::InvalidEncoding);
+        }
+        if bytes[4] != ENVELOPE_VERSION {
+            return Err(EnvelopeError::UnsupportedVersion(bytes[4]));
+        }
+        let backend = Backend::from_u8(bytes[5]).ok_or(EnvelopeError::UnknownBackend(bytes[5]))?;
+        let proved_on =
+            ProvedOn::from_u8(bytes[6]).ok_or(EnvelopeError::UnknownProvedOn(bytes[6]))?;
+
+        let mut rest = &bytes[7..];
+        let mut field = || -> Result<Vec<u8>, EnvelopeError> {
+            if rest.len() < 4 {
+                return Err(EnvelopeError::InvalidEncoding);
+            }
+            let (len, tail) = rest.split_at(4);
+            let len = u32::from_be_bytes(len.try_into().unwrap()) as usize;
+            if tail.len() < len {
+                return Err(EnvelopeError::InvalidEncoding);
+            }
+            let (value, tail) = tail.split_at(len);
+            rest = tail;
+            Ok(value.to_vec())
+        };
+        let envelope = ProofEnvelope {
+            backend,
+            proved_on,
+            vkey_hash: field()?,
+            program_digest: field()?,
+            public_values: field()?,
+            proof: field()?,
+        };
+
+        // trailing bytes mean the file isn't what it claims to be.
+        if !rest.is_empty() {
+            return Err(EnvelopeError::InvalidEncoding);
+        }
+        Ok(envelope)
+    }
+}
+
+#[cfg(test)]
+mod envelope_tests {
+    use super::*;
+    use alloc::vec;
+
+    fn envelope() -> ProofEnvelope {
+        ProofEnvelope {
+            backend: Backend::Sp1,
+            proved_on: ProvedOn::Cuda,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![5; 32],
+            public_values: vec![1, 2, 3],
+            proof: vec![9; 100],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = envelope().encode();
+        assert_eq!(&bytes[..4], b"zkSD");
+        assert_eq!(bytes.len(), 7 + 4 * 4 + 32 + 32 + 3 + 100);
+        assert_eq!(ProofEnvelope::decode(&bytes), Ok(envelope()));
+
+        let empty = ProofEnvelope {
+            backend: Backend::Jolt,
+            proved_on: ProvedOn::Cpu,
+            vkey_hash: vec![],
+            program_digest: vec![],
+            public_values: vec![],
+            proof: vec![],
+        };
+        assert_eq!(ProofEnvelope::decode(&empty.encode()), Ok(empty));
+    }
+
+    #[test]
+    fn test_mock() {
+        assert!(!envelope().is_mock());
+        let mock = ProofEnvelope {
+            proof: MOCK_PROOF.to_vec(),
+            ..envelope()
+        };
+        assert!(mock.is_mock());
+        assert!(ProofEnvelope::decode(&mock.encode()).unwrap().is_mock());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&envelope()).unwrap();
+        assert!(json.starts_with("{\"backend\":\"Sp1\",\"proved_on\":\"Cuda\","));
+        assert_eq!(
+            serde_json::from_str::<ProofEnvelope>(&json).unwrap(),
+            envelope()
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let bytes = envelope().encode();
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] = b'x';
+        assert_eq!(
+            ProofEnvelope::decode(&bad_magic),
+            Err(EnvelopeError::InvalidEncoding)
+        );
+
+        let mut newer = bytes.clone();
+        newer[4] = ENVELOPE_VERSION + 1;
+        assert_eq!(
+            ProofEnvelope::decode(&newer),
+            Err(EnvelopeError::UnsupportedVersion(ENVELOPE_VERSION + 1))
+        );
+
+        let mut backend = bytes.clone();
+        backend[5] = 0;
+        assert_eq!(
+            ProofEnvelope::decode(&backend),
+            Err(EnvelopeError::UnknownBackend(0))
+        );
+
+        let mut proved_on = bytes.clone();
+        proved_on[6] = 9;
+        assert_eq!(
+            ProofEnvelope::decode(&proved_on),
+            Err(EnvelopeError::UnknownProvedOn(9))
+        );
+
+        // truncated in every field, and with trailing bytes.
+        for len in [0, 6, 9, 41, 76, bytes.len() - 1] {
+            assert_eq!(
+                ProofEnvelope::decode(&bytes[..len]),
+                Err(EnvelopeError::InvalidEncoding)
+            );
+        }
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            ProofEnvelope::decode(&trailing),
+            Err(EnvelopeError::InvalidEncoding)
+        );
+    }
+}
@@ -0,0 +1,44 @@
+// heap usage of the guest, sampled at the end of every phase! and logged through sudoku's log!
+// facade. the allocator belongs to the zkvm rather than the guest, so there is no wrapping it,
+// instead the guest defines how much of its heap is in use, same idea as sudoku_sha256:
+//
+//     #[no_mangle]
+//     fn sudoku_heap_used() -> usize { .. }
+//
+// the peak is the largest sample, allocations freed again within a phase don't show up in it.
+// with a bump allocator (sp1's default) nothing is freed and the last sample is the peak.
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+extern "Rust" {
+    fn sudoku_heap_used() -> usize;
+}
+
+// guests are single threaded, plain loads and stores are enough and don't need atomic
+// instructions the riscv targets lack.
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+// bytes of heap in use right now. the guest has to define sudoku_heap_used, otherwise this
+// fails to link.
+pub fn used() -> usize {
+    unsafe { sudoku_heap_used() }
+}
+
+// records the heap in use at the end of `phase` and returns it.
+pub fn sample(phase: &str) -> usize {
+    let used = used();
+    if used > PEAK.load(Ordering::Relaxed) {
+        PEAK.store(used, Ordering::Relaxed);
+    }
+    sudoku::log!("heap: {} {} bytes", phase, used);
+    used
+}
+
+pub fn peak() -> usize {
+    PEAK.load(Ordering::Relaxed)
+}
+
+// logs the peak, for the guest to call once it's done.
+pub fn report() {
+    sample("end");
+    sudoku::log!("heap-peak: {} bytes", peak());
+}
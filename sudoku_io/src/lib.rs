@@ -0,0 +1,1826 @@
+// input/output layout shared by the zkVM guests and the host scripts that feed them,
+// so every backend agrees on how a puzzle gets into the proof and what comes out of it.
+#![no_std]
+extern crate alloc;
+// the Arbitrary derive's code refers to ::std.
+#[cfg(feature = "arbitrary")]
+extern crate std;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+use sudoku::core::board::{Board, Difficulty, PACKED_LEN};
+use sudoku::core::commitment::{puzzle_hash, sha256, solution_hash};
+use sudoku::core::solver::DancingLinks;
+use sudoku::core::variant::Variant;
+
+#[cfg(feature = "badge")]
+pub mod badge;
+pub mod bundle;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+#[cfg(feature = "json")]
+pub mod document;
+pub mod envelope;
+#[cfg(feature = "heap-stats")]
+pub mod heap;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod puzzle_id;
+#[cfg(feature = "scale")]
+pub mod scale;
+#[cfg(feature = "sol")]
+pub mod sol;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+// wraps a phase of the guest (generation, applying input, validation, ...) in sp1 cycle-tracker
+// markers, which the bench harness reads back from the execution report. the markers go out
+// through sudoku's log! facade and are compiled out without the cycle-tracker feature. with
+// heap-stats the heap in use is sampled at the end of the phase as well, see `heap`.
+macro_rules! phase {
+    ($name:literal, $body:expr) => {{
+        #[cfg(feature = "cycle-tracker")]
+        sudoku::log!(concat!("cycle-tracker-report-start: ", $name));
+        let result = $body;
+        #[cfg(feature = "cycle-tracker")]
+        sudoku::log!(concat!("cycle-tracker-report-end: ", $name));
+        #[cfg(feature = "heap-stats")]
+        crate::heap::sample($name);
+        result
+    }};
+}
+
+// `phase!` for the guests' own work around this crate, reading the input ("decode") for one.
+#[cfg_attr(
+    not(any(feature = "cycle-tracker", feature = "heap-stats")),
+    allow(unused_variables)
+)]
+pub fn phase<T>(name: &str, body: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "cycle-tracker")]
+    sudoku::log!("cycle-tracker-report-start: {}", name);
+    let result = body();
+    #[cfg(feature = "cycle-tracker")]
+    sudoku::log!("cycle-tracker-report-end: {}", name);
+    #[cfg(feature = "heap-stats")]
+    crate::heap::sample(name);
+    result
+}
+
+// where the guest gets its puzzle from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PuzzleSource {
+    // regenerated from the seed and `Difficulty` discriminant using the crate's generator.
+    Seed {
+        seed: u32,
+        difficulty: u8,
+    },
+    // the (raw or packed) givens, along with the sha256 the host expects them to hash to.
+    Puzzle {
+        cells: Vec<u8>,
+        expected_hash: [u8; 32],
+    },
+}
+
+impl PuzzleSource {
+    // the board with the difficulty tier and seed (0 for supplied puzzles) to commit for it.
+    pub fn load(&self) -> Result<(Board, u8, u32), InputError> {
+        match self {
+            PuzzleSource::Seed { seed, difficulty } => {
+                puzzle_from_seed(*seed, *difficulty).map(|board| (board, *difficulty, *seed))
+            }
+            PuzzleSource::Puzzle {
+                cells,
+                expected_hash,
+            } => {
+                puzzle_from_cells(cells, expected_hash).map(|board| (board, DIFFICULTY_UNRATED, 0))
+            }
+        }
+    }
+}
+
+// everything the verifier guests read, as one value the host serializes and the guest
+// deserializes, so the inputs can't get out of order between the hosts and the guests.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ProofInput {
+    pub puzzle: PuzzleSource,
+    // the user's (raw or packed) board.
+    pub solution: Vec<u8>,
+    // private, keeps the solution commitment from being brute forced.
+    pub salt: [u8; 32],
+    pub reward_address: [u8; 20],
+    // opaque (tournament round, date, ...), echoed so contracts can enforce submission windows.
+    pub epoch: u64,
+    pub policy: Policy,
+    // the organizer's ed25519 key and its 64 byte signature over the puzzle hash, see
+    // `check_organizer`. None for unsigned puzzles.
+    pub organizer: Option<([u8; 32], Vec<u8>)>,
+    // `Variant::to_bytes` of the rules to check the board against.
+    pub variant: Vec<u8>,
+    // caller-supplied (a request id, ...), echoed so an integrator can bind each proof to the
+    // request it answers and refuse replays of older proofs.
+    pub nonce: [u8; 32],
+}
+
+impl ProofInput {
+    // a classic, unsigned submission without policy, epoch or reward address.
+    pub fn new(puzzle: PuzzleSource, solution: Vec<u8>) -> Self {
+        ProofInput {
+            puzzle,
+            solution,
+            salt: [0; 32],
+            reward_address: [0; 20],
+            epoch: 0,
+            policy: Policy::NONE,
+            organizer: None,
+            variant: Variant::Classic.to_bytes(),
+            nonce: [0; 32],
+        }
+    }
+}
+
+// the whole verifier guest apart from committing: load the puzzle, check the board under the
+// variant, the organizer's signature (through `verify_signature`, see `check_organizer`) and
+// bind the values to the submission. the policy is left to `apply_policy` after `finish`, so
+// its hash is committed for malformed input too.
+pub fn verify_input(
+    input: &ProofInput,
+    verify_signature: impl FnOnce(&[u8; 32], &[u8; 32], &[u8; 64]) -> bool,
+) -> Result<PublicValues, InputError> {
+    let (board, difficulty, seed) = input.puzzle.load()?;
+    let variant = decode_variant(&input.variant)?;
+    let mut values = verify_variant(board, difficulty, &variant, &input.solution, &input.salt)?;
+    if let Some((key, signature)) = &input.organizer {
+        values = check_organizer(values, key, signature, verify_signature)?;
+    }
+    Ok(bind(
+        values,
+        seed,
+        input.epoch,
+        input.reward_address,
+        input.nonce,
+    ))
+}
+
+// version of the logic behind a proof: puzzle generation and solving in the sudoku crate,
+// validation and the public values here. committed by the verifier guests so proofs made by an
+// older guest can be told apart after an upgrade, and checked by the hosts so they refuse an
+// elf built from different logic than their own. bump it with any change that can alter what
+// a guest commits for the same input.
+pub const LOGIC_VERSION: u32 = 5;
+
+// committed difficulty tier for puzzles that weren't generated by this crate.
+// otherwise it is the `Difficulty` discriminant the puzzle was generated with.
+pub const DIFFICULTY_UNRATED: u8 = 0;
+
+// committed status when the user's board was checked against the puzzle, whether or not it
+// turned out to be valid. anything else is the code of the `InputError` that stopped the check.
+pub const STATUS_OK: u8 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputError {
+    UnknownSource = 1,
+    InvalidDifficulty = 2,
+    InvalidEncoding = 3,
+    InvalidPuzzle = 4,
+    PuzzleHashMismatch = 5,
+    GivenMismatch = 6,
+    GeneratorFailed = 7,
+    PolicyViolation = 8,
+    BadSignature = 9,
+    InvalidVariant = 10,
+    CheckpointMismatch = 11,
+}
+
+impl InputError {
+    // error code committed as the status public value.
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+}
+
+// everything the guests commit. each zkVM encodes it its own way (abi encoding for sp1,
+// reveal slots for openvm, the return value for jolt).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "scale",
+    derive(
+        parity_scale_codec::Encode,
+        parity_scale_codec::Decode,
+        scale_info::TypeInfo
+    )
+)]
+pub struct PublicValues {
+    pub puzzle_hash: [u8; 32],
+    // salted hash of the user's board (see commitment::solution_hash), all zeros unless the
+    // board was checked.
+    pub solution_commitment: [u8; 32],
+    pub valid: bool,
+    pub difficulty: u8,
+    // seed the puzzle was generated from, 0 for supplied puzzles.
+    pub seed: u32,
+    // sha256(puzzle hash || reward address), a contract records it to pay each address at most
+    // once per puzzle.
+    pub nullifier: [u8; 32],
+    pub clue_count: u8,
+    pub epoch: u64,
+    pub reward_address: [u8; 20],
+    pub status: u8,
+    pub policy_hash: [u8; 32],
+    // ed25519 key of the organizer that signed the puzzle hash, all zeros for unsigned puzzles.
+    pub organizer_key: [u8; 32],
+    // `Variant::kind` of the rules the board was checked against.
+    pub variant: u8,
+    // LOGIC_VERSION of the guest that made the proof.
+    pub logic_version: u32,
+    // merkle root over the user's board, one salted leaf per cell (see `cells_root`), so single
+    // cells can be revealed later against this proof. all zeros unless the board was checked.
+    pub cells_root: [u8; 32],
+    // `givens_hash` of the puzzle the board was checked against.
+    pub givens_hash: [u8; 32],
+    // the submission's nonce, all zeros unless the caller set one.
+    pub nonce: [u8; 32],
+}
+
+// minimum requirements the verifier puts on the puzzle, so a reward can't be claimed with a
+// trivially easy puzzle the prover generated themselves. supplied puzzles are unrated, so
+// they can only meet a policy through `max_clues`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Policy {
+    pub min_difficulty: u8,
+    pub max_clues: u8,
+}
+
+impl Policy {
+    // accepts every puzzle.
+    pub const NONE: Policy = Policy {
+        min_difficulty: DIFFICULTY_UNRATED,
+        max_clues: 81,
+    };
+
+    // input stream encoding: [min difficulty, max clues].
+    pub fn to_bytes(&self) -> [u8; 2] {
+        [self.min_difficulty, self.max_clues]
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Policy, InputError> {
+        match bytes {
+            &[min_difficulty, max_clues] => Ok(Policy {
+                min_difficulty,
+                max_clues,
+            }),
+            _ => Err(InputError::InvalidEncoding),
+        }
+    }
+
+    // committed so a contract can check the proof was made under the policy it requires,
+    // sha256(abi.encodePacked(uint8 minDifficulty, uint8 maxClues)) on the solidity side.
+    pub fn hash(&self) -> [u8; 32] {
+        sha256(&self.to_bytes())
+    }
+
+    pub fn allows(&self, difficulty: u8, clue_count: u8) -> bool {
+        difficulty >= self.min_difficulty && clue_count <= self.max_clues
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy::NONE
+    }
+}
+
+// committed by the solvability guest, which solves the puzzle itself instead of checking a
+// user's board. `solution_hash` is the salted hash of the solution it found, all zeros when
+// it found nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolvabilityValues {
+    pub puzzle_hash: [u8; 32],
+    pub solvable: bool,
+    pub solution_hash: [u8; 32],
+}
+
+// committed by the uniqueness guest. `unique` is set when the puzzle has exactly one
+// solution, i.e. it is well-posed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UniquenessValues {
+    pub puzzle_hash: [u8; 32],
+    pub unique: bool,
+}
+
+// committed by the partial-progress guest. `filled` is how many cells the user filled in on
+// top of the givens, and is only non-zero when the board is `consistent`: it keeps the givens,
+// repeats no value in a row, column or box, and can still be completed to a full solution.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProgressValues {
+    pub puzzle_hash: [u8; 32],
+    pub consistent: bool,
+    pub clue_count: u8,
+    pub filled: u8,
+    pub status: u8,
+}
+
+// one move of a solving session: put `value` into `cell` (row-wise index), or erase the cell
+// when `value` is 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Move {
+    pub cell: u8,
+    pub value: u8,
+}
+
+// committed by the session transcript guest. `valid` is true when every move was legal and the
+// moves leave the board solved. `illegal_move` is the index of the first move that wasn't legal,
+// `move_count` when they all were. `transcript_hash` (see `transcript_hash`) binds the proof to
+// the session without putting the moves in the public values.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TranscriptValues {
+    pub puzzle_hash: [u8; 32],
+    pub valid: bool,
+    pub move_count: u32,
+    pub illegal_move: u32,
+    pub transcript_hash: [u8; 32],
+    pub status: u8,
+}
+
+// committed by the sp1 checkpoint guest, one link in a chain of proofs over a long solving
+// session where each proof extends the previous one instead of re-proving the session from the
+// start. `board_commitment` is `solution_hash` of the board after the link's moves under the
+// session's private salt, the next link opens it. `move_count` and `legal` cover the whole chain
+// so far. `previous_vkey` is the verifying key digest the previous link's proof was checked
+// against, zeros for the first link. each link requires the previous one to have committed
+// zeros or that same key, so a verifier that finds the checkpoint program's own key in the last
+// link knows every link ran the checkpoint program.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CheckpointValues {
+    pub puzzle_hash: [u8; 32],
+    pub board_commitment: [u8; 32],
+    pub move_count: u32,
+    pub legal: bool,
+    pub solved: bool,
+    pub previous_vkey: [u8; 32],
+    pub status: u8,
+}
+
+// what the checkpoint guest reads: the next stretch of moves (value 0 erases) on top of the
+// previous link, none for the first link of a chain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointInput {
+    pub puzzle: PuzzleSource,
+    // the same for every link of a chain.
+    pub salt: [u8; 32],
+    pub moves: Vec<Move>,
+    pub previous: Option<PreviousCheckpoint>,
+}
+
+// the link a checkpoint extends. the proof itself goes through sp1's proof stream, the guest
+// verifies it against `vkey` and the digest of `public_values`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PreviousCheckpoint {
+    pub vkey: [u32; 8],
+    // the abi encoded CheckpointValues the previous proof committed.
+    pub public_values: Vec<u8>,
+    // the (raw or packed) board the previous board commitment opens to.
+    pub board: Vec<u8>,
+}
+
+// what the solvability guest reads. the salt is chosen by the host and folded into the
+// committed solution hash, it stays private until the prover opens the commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolvabilityInput {
+    pub puzzle: PuzzleSource,
+    pub salt: [u8; 32],
+}
+
+// what the uniqueness guest reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UniquenessInput {
+    pub puzzle: PuzzleSource,
+}
+
+// what the progress guest reads: the user's (raw or packed) board, 0 for the cells not filled
+// in yet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressInput {
+    pub puzzle: PuzzleSource,
+    pub solution: Vec<u8>,
+}
+
+// what the transcript guest reads: the session's moves in the order they were made, value 0
+// erases a cell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptInput {
+    pub puzzle: PuzzleSource,
+    pub moves: Vec<Move>,
+}
+
+// what the batch guest reads, its submissions in the order their results are committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchInput {
+    pub submissions: Vec<BatchSubmission>,
+}
+
+// a puzzle and the user's (raw or packed) board for it. the results don't include the solution
+// commitment, so there's no salt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchSubmission {
+    pub puzzle: PuzzleSource,
+    pub solution: Vec<u8>,
+}
+
+// committed by the batch guest in place of one set of public values per submission.
+// `results_root` is the merkle root over `result_leaf` of every entry in input order, so a
+// service can prove the per-puzzle results it publishes against a single proof.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BatchValues {
+    pub count: u32,
+    pub valid_count: u32,
+    pub results_root: [u8; 32],
+}
+
+pub fn puzzle_from_seed(seed: u32, difficulty: u8) -> Result<Board, InputError> {
+    let difficulty = Difficulty::from_u8(difficulty).ok_or(InputError::InvalidDifficulty)?;
+    phase!(
+        "generate",
+        Board::try_from_seed(seed, Some(difficulty)).map_err(|_| InputError::GeneratorFailed)
+    )
+}
+
+// boards (puzzles and the user's solution) can go over the input stream either as 81 raw
+// cells or in the 41 byte packed form, which halves the cost of reading them in the guest.
+pub fn decode_cells(bytes: &[u8]) -> Result<[u8; 81], InputError> {
+    match bytes.len() {
+        81 => bytes.try_into().map_err(|_| InputError::InvalidEncoding),
+        PACKED_LEN => Board::from_packed(bytes)
+            .map(|board| board.cells)
+            .map_err(|_| InputError::InvalidEncoding),
+        _ => Err(InputError::InvalidEncoding),
+    }
+}
+
+// build the puzzle from externally supplied givens, refusing it unless it hashes to the
+// commitment the host claims. lets the prover handle puzzles this crate didn't generate.
+pub fn puzzle_from_cells(cells: &[u8], expected_hash: &[u8; 32]) -> Result<Board, InputError> {
+    let cells = decode_cells(cells)?;
+    let board = Board::from_array(cells).map_err(|_| InputError::InvalidPuzzle)?;
+
+    if phase!("hash", puzzle_hash(&board)) != *expected_hash {
+        return Err(InputError::PuzzleHashMismatch);
+    }
+
+    Ok(board)
+}
+
+// which cells of the puzzle are givens, bit i % 8 of byte i / 8 for cell i in row-wise order.
+pub fn givens_mask(puzzle: &Board) -> [u8; 11] {
+    let mut mask = [0; 11];
+    for (i, _) in puzzle
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|(_, &cell)| cell != 0)
+    {
+        mask[i / 8] |= 1 << (i % 8);
+    }
+    mask
+}
+
+// sha256 of the givens mask, committed as `givens_hash` so a verifier can tell the user's
+// board was checked against exactly the givens of the published puzzle.
+pub fn givens_hash(puzzle: &Board) -> [u8; 32] {
+    sha256(&givens_mask(puzzle))
+}
+
+// the user's board has to keep every given of the puzzle, found through its givens mask.
+// checked up front so the guest can commit GivenMismatch instead of applying a board that
+// overwrites the puzzle.
+pub fn check_givens(puzzle: &Board, user_input: &[u8; 81]) -> Result<(), InputError> {
+    let mask = givens_mask(puzzle);
+    let consistent = (0..81)
+        .filter(|i| mask[i / 8] & (1 << (i % 8)) != 0)
+        .all(|i| user_input[i] == puzzle.cells[i]);
+
+    if !consistent {
+        return Err(InputError::GivenMismatch);
+    }
+    Ok(())
+}
+
+// check the user's (raw or packed) board against the puzzle and fill in what gets committed,
+// apart from the values that depend on the submission rather than the board (see `bind`).
+// a board overwriting one of the givens is reported through the status, not as an error.
+// `salt` keeps the solution commitment from being brute forced from the puzzle.
+pub fn verify_solution(
+    puzzle: Board,
+    difficulty: u8,
+    user_input: &[u8],
+    salt: &[u8; 32],
+) -> Result<PublicValues, InputError> {
+    verify_variant(puzzle, difficulty, &Variant::Classic, user_input, salt)
+}
+
+// the user's cells go onto the board through a copied vec, or in place with fixed-buffers so the
+// verification path doesn't allocate.
+fn apply_input(puzzle: &mut Board, user_input: &[u8; 81]) -> Result<bool, &'static str> {
+    #[cfg(feature = "fixed-buffers")]
+    return puzzle.apply_user_input(user_input);
+    #[cfg(not(feature = "fixed-buffers"))]
+    puzzle.apply_user_input_to_board(Vec::from(user_input))
+}
+
+pub fn decode_variant(bytes: &[u8]) -> Result<Variant, InputError> {
+    Variant::from_bytes(bytes).map_err(|_| InputError::InvalidVariant)
+}
+
+// verify_solution under a variant's rules. the committed puzzle hash covers the variant's
+// constraints for anything but classic, see `Variant::puzzle_hash`.
+pub fn verify_variant(
+    mut puzzle: Board,
+    difficulty: u8,
+    variant: &Variant,
+    user_input: &[u8],
+    salt: &[u8; 32],
+) -> Result<PublicValues, InputError> {
+    let mut values = PublicValues {
+        puzzle_hash: phase!("hash", variant.puzzle_hash(&puzzle)),
+        variant: variant.kind(),
+        logic_version: LOGIC_VERSION,
+        givens_hash: phase!("hash", givens_hash(&puzzle)),
+        difficulty,
+        // count the givens of the board being verified, before the user's input fills it in.
+        clue_count: puzzle.clue_count(),
+        ..Default::default()
+    };
+    let user_input = decode_cells(user_input)?;
+
+    match check_givens(&puzzle, &user_input) {
+        Ok(()) => {
+            phase!("apply", apply_input(&mut puzzle, &user_input))
+                .map_err(|_| InputError::GivenMismatch)?;
+            values.valid = phase!("validate", variant.validate(&puzzle));
+            values.solution_commitment = phase!("hash", solution_hash(&puzzle, salt));
+            values.cells_root = phase!("hash", cells_root(&puzzle, salt));
+            values.status = STATUS_OK;
+        }
+        Err(e) => values.status = e.code(),
+    }
+    Ok(values)
+}
+
+// fill in the values that come from the submission rather than the board, the guest only
+// echoes them apart from the nullifier derived from the reward address.
+pub fn bind(
+    mut values: PublicValues,
+    seed: u32,
+    epoch: u64,
+    reward_address: [u8; 20],
+    nonce: [u8; 32],
+) -> PublicValues {
+    values.seed = seed;
+    values.epoch = epoch;
+    values.nonce = nonce;
+    values.reward_address = reward_address;
+    values.nullifier = nullifier(&values.puzzle_hash, &reward_address);
+    values
+}
+
+pub fn nullifier(puzzle_hash: &[u8; 32], reward_address: &[u8; 20]) -> [u8; 32] {
+    let mut preimage = [0; 52];
+    preimage[..32].copy_from_slice(puzzle_hash);
+    preimage[32..].copy_from_slice(reward_address);
+    sha256(&preimage)
+}
+
+// size of the abi encoded public values, seventeen static 32 byte words.
+pub const ABI_LEN: usize = 17 * 32;
+
+// abi encoding of the public values, the one definition the sp1 guest commits, the hosts
+// decode and the solidity verifier reads back as
+// struct PublicValuesStruct {
+//     bytes32 puzzleHash; bytes32 solutionCommitment; bool valid; uint8 difficulty;
+//     uint32 seed; bytes32 nullifier; uint8 clueCount; uint64 epoch; address rewardAddress;
+//     uint8 status; bytes32 policyHash; bytes32 organizerKey; uint8 variant;
+//     uint32 logicVersion; bytes32 cellsRoot; bytes32 givensHash; bytes32 nonce;
+// }
+// every field is static, so this is the same as abi.encode of the struct.
+impl PublicValues {
+    pub fn abi_encode(&self) -> [u8; ABI_LEN] {
+        let mut words = [[0; 32]; 17];
+        words[0] = self.puzzle_hash;
+        words[1] = self.solution_commitment;
+        words[2][31] = self.valid as u8;
+        words[3][31] = self.difficulty;
+        words[4][28..].copy_from_slice(&self.seed.to_be_bytes());
+        words[5] = self.nullifier;
+        words[6][31] = self.clue_count;
+        words[7][24..].copy_from_slice(&self.epoch.to_be_bytes());
+        words[8][12..].copy_from_slice(&self.reward_address);
+        words[9][31] = self.status;
+        words[10] = self.policy_hash;
+        words[11] = self.organizer_key;
+        words[12][31] = self.variant;
+        words[13][28..].copy_from_slice(&self.logic_version.to_be_bytes());
+        words[14] = self.cells_root;
+        words[15] = self.givens_hash;
+        words[16] = self.nonce;
+
+        let mut bytes = [0; ABI_LEN];
+        for (chunk, word) in bytes.chunks_exact_mut(32).zip(&words) {
+            chunk.copy_from_slice(word);
+        }
+        bytes
+    }
+
+    // the inverse of abi_encode, refusing anything a solidity decoder would refuse (wrong
+    // length, dirty padding in front of the smaller types).
+    pub fn abi_decode(bytes: &[u8]) -> Result<PublicValues, InputError> {
+        if bytes.len() != ABI_LEN {
+            return Err(InputError::InvalidEncoding);
+        }
+        let word = |i: usize| -> &[u8] { &bytes[i * 32..(i + 1) * 32] };
+        // the value of a word holding a `size` byte type, right aligned.
+        let small = |i: usize, size: usize| -> Result<&[u8], InputError> {
+            let (padding, value) = word(i).split_at(32 - size);
+            match padding.iter().all(|&b| b == 0) {
+                true => Ok(value),
+                false => Err(InputError::InvalidEncoding),
+            }
+        };
+        let valid = match small(2, 1)?[0] {
+            0 => false,
+            1 => true,
+            _ => return Err(InputError::InvalidEncoding),
+        };
+
+        Ok(PublicValues {
+            puzzle_hash: word(0).try_into().unwrap(),
+            solution_commitment: word(1).try_into().unwrap(),
+            valid,
+            difficulty: small(3, 1)?[0],
+            seed: u32::from_be_bytes(small(4, 4)?.try_into().unwrap()),
+            nullifier: word(5).try_into().unwrap(),
+            clue_count: small(6, 1)?[0],
+            epoch: u64::from_be_bytes(small(7, 8)?.try_into().unwrap()),
+            reward_address: small(8, 20)?.try_into().unwrap(),
+            status: small(9, 1)?[0],
+            policy_hash: word(10).try_into().unwrap(),
+            organizer_key: word(11).try_into().unwrap(),
+            variant: small(12, 1)?[0],
+            logic_version: u32::from_be_bytes(small(13, 4)?.try_into().unwrap()),
+            cells_root: word(14).try_into().unwrap(),
+            givens_hash: word(15).try_into().unwrap(),
+            nonce: word(16).try_into().unwrap(),
+        })
+    }
+}
+
+// hold the verified values to the verifier's policy. a puzzle that doesn't meet it can't count
+// as valid, whatever the user's board, and the status says why.
+pub fn apply_policy(mut values: PublicValues, policy: &Policy) -> PublicValues {
+    values.policy_hash = policy.hash();
+    if values.status == STATUS_OK && !policy.allows(values.difficulty, values.clue_count) {
+        values.valid = false;
+        values.status = InputError::PolicyViolation.code();
+    }
+    values
+}
+
+// an organizer's ed25519 signature over the puzzle hash, shows the puzzle was officially issued.
+// the check itself is left to the guest through `verify` (key, message, signature), so each
+// zkVM can route it through its own curve precompile.
+pub fn check_organizer(
+    mut values: PublicValues,
+    organizer_key: &[u8; 32],
+    signature: &[u8],
+    verify: impl FnOnce(&[u8; 32], &[u8; 32], &[u8; 64]) -> bool,
+) -> Result<PublicValues, InputError> {
+    let signature = signature
+        .try_into()
+        .map_err(|_| InputError::InvalidEncoding)?;
+    if !phase!(
+        "signature",
+        verify(organizer_key, &values.puzzle_hash, signature)
+    ) {
+        return Err(InputError::BadSignature);
+    }
+    values.organizer_key = *organizer_key;
+    Ok(values)
+}
+
+// run the solver on the puzzle and commit to what it found, so the proof shows the puzzle has
+// a solution (and which one, through its hash) without revealing it.
+pub fn check_solvable(puzzle: &Board, salt: &[u8; 32]) -> SolvabilityValues {
+    let mut values = SolvabilityValues {
+        puzzle_hash: phase!("hash", puzzle_hash(puzzle)),
+        ..Default::default()
+    };
+    if let Ok(solution) = phase!("solve", DancingLinks::solve_board(puzzle)) {
+        values.solvable = solution.validate();
+        if values.solvable {
+            values.solution_hash = solution_hash(&solution, salt);
+        }
+    }
+    values
+}
+
+// count solutions up to two, that is enough to tell a unique puzzle from an ambiguous one
+// without enumerating every solution in the guest.
+pub fn check_unique(puzzle: &Board) -> UniquenessValues {
+    UniquenessValues {
+        puzzle_hash: phase!("hash", puzzle_hash(puzzle)),
+        unique: phase!("count", DancingLinks::count_solutions(puzzle, 2)) == Ok(1),
+    }
+}
+
+// check a partially filled board against the puzzle. whether a placement is "correct" can't be
+// told without fixing a solution, so the cells count once the board is still solvable with
+// them in place (which is the same thing for a unique puzzle). the placements themselves stay
+// private, only the count is committed.
+pub fn check_progress(mut puzzle: Board, user_input: &[u8]) -> Result<ProgressValues, InputError> {
+    let mut values = ProgressValues {
+        puzzle_hash: phase!("hash", puzzle_hash(&puzzle)),
+        clue_count: puzzle.clue_count(),
+        ..Default::default()
+    };
+    let user_input = decode_cells(user_input)?;
+
+    match check_givens(&puzzle, &user_input) {
+        Ok(()) => {
+            phase!("apply", apply_input(&mut puzzle, &user_input))
+                .map_err(|_| InputError::GivenMismatch)?;
+            values.consistent = phase!(
+                "validate",
+                puzzle.is_conflict_free() && DancingLinks::solve_board(&puzzle).is_ok()
+            );
+            if values.consistent {
+                values.filled = puzzle.clue_count() - values.clue_count;
+            }
+            values.status = STATUS_OK;
+        }
+        Err(e) => values.status = e.code(),
+    }
+    Ok(values)
+}
+
+// replay a solving session against the puzzle, move by move. a move is legal when its cell and
+// value are in range, it leaves the givens alone, and a placed value doesn't repeat in the
+// cell's row, column or box at that point of the session. the replay stops at the first
+// illegal move.
+pub fn check_transcript(puzzle: Board, moves: &[Move]) -> TranscriptValues {
+    let mut values = TranscriptValues {
+        puzzle_hash: phase!("hash", puzzle_hash(&puzzle)),
+        move_count: moves.len() as u32,
+        illegal_move: moves.len() as u32,
+        transcript_hash: phase!("hash", transcript_hash(moves)),
+        status: STATUS_OK,
+        ..Default::default()
+    };
+
+    let mut board = puzzle.clone();
+    let illegal = phase!("replay", replay_moves(&puzzle, &mut board, moves));
+    match illegal {
+        Some(index) => values.illegal_move = index as u32,
+        None => values.valid = phase!("validate", board.validate()),
+    }
+    values
+}
+
+// plays `moves` on `board` up to the first illegal one (see check_transcript) and returns its
+// index, none when they were all legal. hosts use it to keep track of the board a checkpoint
+// chain commits to.
+pub fn replay_moves(puzzle: &Board, board: &mut Board, moves: &[Move]) -> Option<usize> {
+    moves.iter().position(|m| !play_move(puzzle, board, m))
+}
+
+// makes the move on `board` if it's legal.
+fn play_move(puzzle: &Board, board: &mut Board, m: &Move) -> bool {
+    let cell = m.cell as usize;
+    if cell >= 81 || m.value > 9 || puzzle.cells[cell] != 0 {
+        return false;
+    }
+    if m.value != 0 && !board.can_place(cell, m.value) {
+        return false;
+    }
+    board.cells[cell] = m.value;
+    true
+}
+
+// the next link of a checkpoint chain: opens the previous link's board, replays `moves` on it
+// with the same rules as check_transcript and commits the board it ends up with. `previous`
+// holds the previous link's values and board, `vkey` the key digest its proof was verified
+// against (zeros without a previous link). the guest verifies that proof before calling this.
+pub fn extend_checkpoint(
+    puzzle: Board,
+    previous: Option<(&CheckpointValues, &[u8])>,
+    vkey: [u8; 32],
+    moves: &[Move],
+    salt: &[u8; 32],
+) -> Result<CheckpointValues, InputError> {
+    let mut values = CheckpointValues {
+        puzzle_hash: phase!("hash", puzzle_hash(&puzzle)),
+        legal: true,
+        previous_vkey: vkey,
+        status: STATUS_OK,
+        ..Default::default()
+    };
+
+    let mut board = puzzle.clone();
+    if let Some((previous, previous_board)) = previous {
+        if previous.puzzle_hash != values.puzzle_hash
+            || (previous.previous_vkey != [0; 32] && previous.previous_vkey != vkey)
+        {
+            return Err(InputError::CheckpointMismatch);
+        }
+        board.cells = decode_cells(previous_board)?;
+        if phase!("hash", solution_hash(&board, salt)) != previous.board_commitment {
+            return Err(InputError::CheckpointMismatch);
+        }
+        values.move_count = previous.move_count;
+        values.legal = previous.legal;
+    }
+
+    values.move_count += moves.len() as u32;
+    // once a move was illegal the chain stays that way, there is nothing left to replay.
+    if values.legal {
+        values.legal = phase!("replay", replay_moves(&puzzle, &mut board, moves)).is_none();
+    }
+    values.solved = values.legal && phase!("validate", board.validate());
+    values.board_commitment = phase!("hash", solution_hash(&board, salt));
+    Ok(values)
+}
+
+// sha256 over the moves as [cell, value] byte pairs, in order.
+pub fn transcript_hash(moves: &[Move]) -> [u8; 32] {
+    let bytes: Vec<u8> = moves.iter().flat_map(|m| [m.cell, m.value]).collect();
+    sha256(&bytes)
+}
+
+// sha256(puzzle hash || valid || status), the result of one submission in a batch.
+pub fn result_leaf(values: &PublicValues) -> [u8; 32] {
+    let mut leaf = [0; 34];
+    leaf[..32].copy_from_slice(&values.puzzle_hash);
+    leaf[32] = values.valid as u8;
+    leaf[33] = values.status;
+    sha256(&leaf)
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut pair = [0; 64];
+    pair[..32].copy_from_slice(left);
+    pair[32..].copy_from_slice(right);
+    sha256(&pair)
+}
+
+// binary sha256 merkle tree, an odd node at the end of a level is paired with itself.
+// the root of no leaves is all zeros.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    if leaves.is_empty() {
+        return [0; 32];
+    }
+    let mut level = Vec::from(leaves);
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+    }
+    level[0]
+}
+
+// the sibling hashes from `leaves[index]` up to the merkle root, bottom first.
+pub fn merkle_path(leaves: &[[u8; 32]], mut index: usize) -> Vec<[u8; 32]> {
+    let mut path = Vec::new();
+    let mut level = Vec::from(leaves);
+    while level.len() > 1 {
+        path.push(*level.get(index ^ 1).unwrap_or(&level[index]));
+        level = level
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+        index /= 2;
+    }
+    path
+}
+
+// whether `leaf` sits at `index` of the tree with `root`, given its merkle_path.
+pub fn verify_merkle_path(
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    mut index: usize,
+    path: &[[u8; 32]],
+) -> bool {
+    let mut node = *leaf;
+    for sibling in path {
+        node = match index % 2 {
+            0 => hash_pair(&node, sibling),
+            _ => hash_pair(sibling, &node),
+        };
+        index /= 2;
+    }
+    node == *root
+}
+
+// salt of a single cell's leaf, sha256(salt || index). revealing it opens that one cell, the
+// session salt and the other cells' salts can't be derived from it.
+pub fn cell_salt(salt: &[u8; 32], index: u8) -> [u8; 32] {
+    let mut preimage = [0; 33];
+    preimage[..32].copy_from_slice(salt);
+    preimage[32] = index;
+    sha256(&preimage)
+}
+
+// sha256(cell salt || index || value). without the salt a leaf could be opened by trying the
+// nine values.
+pub fn cell_leaf(cell_salt: &[u8; 32], index: u8, value: u8) -> [u8; 32] {
+    let mut preimage = [0; 34];
+    preimage[..32].copy_from_slice(cell_salt);
+    preimage[32] = index;
+    preimage[33] = value;
+    sha256(&preimage)
+}
+
+fn cell_leaves(board: &Board, salt: &[u8; 32]) -> Vec<[u8; 32]> {
+    (0..81u8)
+        .map(|i| cell_leaf(&cell_salt(salt, i), i, board.cells[i as usize]))
+        .collect()
+}
+
+// merkle root over the 81 cell leaves in row-wise order, committed as `cells_root`. it uses the
+// same salt as the solution commitment.
+pub fn cells_root(board: &Board, salt: &[u8; 32]) -> [u8; 32] {
+    merkle_root(&cell_leaves(board, salt))
+}
+
+// everything needed to show one cell of a committed board against its cells root, without
+// revealing any other cell. the basis for hints and progressive disclosure.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellOpening {
+    pub index: u8,
+    pub value: u8,
+    pub salt: [u8; 32],
+    pub path: Vec<[u8; 32]>,
+}
+
+// open cell `index` of the board committed under `salt`, done by whoever kept the salt.
+pub fn open_cell(board: &Board, salt: &[u8; 32], index: u8) -> CellOpening {
+    CellOpening {
+        index,
+        value: board.cells[index as usize],
+        salt: cell_salt(salt, index),
+        path: merkle_path(&cell_leaves(board, salt), index as usize),
+    }
+}
+
+pub fn verify_cell(cells_root: &[u8; 32], opening: &CellOpening) -> bool {
+    opening.index < 81
+        && verify_merkle_path(
+            cells_root,
+            &cell_leaf(&opening.salt, opening.index, opening.value),
+            opening.index as usize,
+            &opening.path,
+        )
+}
+
+// fold the per-submission results of a batch into what the batch guest commits.
+pub fn aggregate(results: &[PublicValues]) -> BatchValues {
+    let leaves: Vec<[u8; 32]> = results.iter().map(result_leaf).collect();
+    BatchValues {
+        count: results.len() as u32,
+        valid_count: results.iter().filter(|values| values.valid).count() as u32,
+        results_root: merkle_root(&leaves),
+    }
+}
+
+// openvm has no commit of its own, the guest can only `reveal(word, index)` one u32 at a
+// time. a 32 byte digest takes eight slots, as little endian words in byte order.
+pub fn digest_to_words(digest: &[u8; 32]) -> [u32; 8] {
+    let mut words = [0; 8];
+    for (word, bytes) in words.iter_mut().zip(digest.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words
+}
+
+// host side of `digest_to_words`, reassembles the digest from the eight revealed words.
+pub fn digest_from_words(words: &[u32; 8]) -> [u8; 32] {
+    let mut digest = [0; 32];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(words) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+// reveal slots used by the openvm guest: valid, difficulty, clue count, epoch (2 words),
+// reward address (5 words), status, then the puzzle hash, policy hash and organizer key
+// (8 words each), the seed, the solution commitment and nullifier (8 words each), the variant,
+// the logic version, the cells root, the givens hash and the nonce (8 words each).
+pub const REVEAL_PUZZLE_HASH: usize = 11;
+pub const REVEAL_POLICY_HASH: usize = REVEAL_PUZZLE_HASH + 8;
+pub const REVEAL_ORGANIZER_KEY: usize = REVEAL_POLICY_HASH + 8;
+pub const REVEAL_SEED: usize = REVEAL_ORGANIZER_KEY + 8;
+pub const REVEAL_SOLUTION_COMMITMENT: usize = REVEAL_SEED + 1;
+pub const REVEAL_NULLIFIER: usize = REVEAL_SOLUTION_COMMITMENT + 8;
+pub const REVEAL_VARIANT: usize = REVEAL_NULLIFIER + 8;
+pub const REVEAL_LOGIC_VERSION: usize = REVEAL_VARIANT + 1;
+pub const REVEAL_CELLS_ROOT: usize = REVEAL_LOGIC_VERSION + 1;
+pub const REVEAL_GIVENS_HASH: usize = REVEAL_CELLS_ROOT + 8;
+pub const REVEAL_NONCE: usize = REVEAL_GIVENS_HASH + 8;
+pub const REVEAL_SLOTS: usize = REVEAL_NONCE + 8;
+
+// lay the public values out over the reveal slots, index i goes to `reveal(words[i], i)`.
+pub fn to_reveal_words(values: &PublicValues) -> [u32; REVEAL_SLOTS] {
+    let mut words = [0; REVEAL_SLOTS];
+    words[0] = values.valid as u32;
+    words[1] = values.difficulty as u32;
+    words[2] = values.clue_count as u32;
+    words[3] = values.epoch as u32;
+    words[4] = (values.epoch >> 32) as u32;
+    for (word, bytes) in words[5..10]
+        .iter_mut()
+        .zip(values.reward_address.chunks_exact(4))
+    {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+    words[10] = values.status as u32;
+    for (start, digest) in [
+        (REVEAL_PUZZLE_HASH, &values.puzzle_hash),
+        (REVEAL_POLICY_HASH, &values.policy_hash),
+        (REVEAL_ORGANIZER_KEY, &values.organizer_key),
+        (REVEAL_SOLUTION_COMMITMENT, &values.solution_commitment),
+        (REVEAL_NULLIFIER, &values.nullifier),
+        (REVEAL_CELLS_ROOT, &values.cells_root),
+        (REVEAL_GIVENS_HASH, &values.givens_hash),
+        (REVEAL_NONCE, &values.nonce),
+    ] {
+        words[start..start + 8].copy_from_slice(&digest_to_words(digest));
+    }
+    words[REVEAL_SEED] = values.seed;
+    words[REVEAL_VARIANT] = values.variant as u32;
+    words[REVEAL_LOGIC_VERSION] = values.logic_version;
+    words
+}
+
+// read the public values back from the revealed words on the host.
+pub fn from_reveal_words(words: &[u32; REVEAL_SLOTS]) -> PublicValues {
+    let mut reward_address = [0; 20];
+    for (bytes, word) in reward_address.chunks_exact_mut(4).zip(&words[5..10]) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    let digest_at = |start: usize| digest_from_words(words[start..start + 8].try_into().unwrap());
+    PublicValues {
+        puzzle_hash: digest_at(REVEAL_PUZZLE_HASH),
+        solution_commitment: digest_at(REVEAL_SOLUTION_COMMITMENT),
+        valid: words[0] != 0,
+        difficulty: words[1] as u8,
+        seed: words[REVEAL_SEED],
+        nullifier: digest_at(REVEAL_NULLIFIER),
+        clue_count: words[2] as u8,
+        epoch: words[3] as u64 | (words[4] as u64) << 32,
+        reward_address,
+        status: words[10] as u8,
+        policy_hash: digest_at(REVEAL_POLICY_HASH),
+        organizer_key: digest_at(REVEAL_ORGANIZER_KEY),
+        variant: words[REVEAL_VARIANT] as u8,
+        logic_version: words[REVEAL_LOGIC_VERSION],
+        cells_root: digest_at(REVEAL_CELLS_ROOT),
+        givens_hash: digest_at(REVEAL_GIVENS_HASH),
+        nonce: digest_at(REVEAL_NONCE),
+    }
+}
+
+// public values a guest can commit in place of a proof for malformed input. the default
+// is the zero values, the types that have a status field put the error code in there.
+pub trait GuestOutput: Default {
+    fn from_error(_: InputError) -> Self {
+        Self::default()
+    }
+}
+
+impl GuestOutput for PublicValues {
+    fn from_error(e: InputError) -> Self {
+        PublicValues {
+            status: e.code(),
+            logic_version: LOGIC_VERSION,
+            ..Default::default()
+        }
+    }
+}
+
+impl GuestOutput for ProgressValues {
+    fn from_error(e: InputError) -> Self {
+        ProgressValues {
+            status: e.code(),
+            ..Default::default()
+        }
+    }
+}
+
+impl GuestOutput for TranscriptValues {
+    fn from_error(e: InputError) -> Self {
+        TranscriptValues {
+            status: e.code(),
+            ..Default::default()
+        }
+    }
+}
+
+impl GuestOutput for CheckpointValues {
+    fn from_error(e: InputError) -> Self {
+        CheckpointValues {
+            status: e.code(),
+            ..Default::default()
+        }
+    }
+}
+
+impl GuestOutput for SolvabilityValues {}
+impl GuestOutput for BatchValues {}
+impl GuestOutput for UniquenessValues {}
+
+// turn the outcome of reading and verifying the inputs into what gets committed.
+// by default malformed input aborts the guest, so no proof exists for it. with the panic-free
+// feature `GuestOutput::from_error` is committed instead, so the prover always gets a proof
+// describing what went wrong.
+pub fn finish<T: GuestOutput>(result: Result<T, InputError>) -> T {
+    match result {
+        Ok(values) => values,
+        #[cfg(feature = "panic-free")]
+        Err(e) => T::from_error(e),
+        #[cfg(not(feature = "panic-free"))]
+        Err(e) => panic!("invalid input: {:?}", e),
+    }
+}
+
+#[cfg(test)]
+mod io_tests {
+    use super::*;
+    use alloc::vec;
+    use sudoku::core::variant::VARIANT_X;
+
+    #[test]
+    fn test_puzzle_from_cells() {
+        let board = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let hash = puzzle_hash(&board);
+        assert_eq!(puzzle_from_cells(&board.cells, &hash), Ok(board));
+    }
+
+    #[test]
+    fn test_puzzle_from_packed_cells() {
+        let board = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let hash = puzzle_hash(&board);
+        assert_eq!(puzzle_from_cells(&board.to_packed(), &hash), Ok(board));
+    }
+
+    #[test]
+    fn test_decode_cells() {
+        let board = puzzle_from_seed(42, Difficulty::Easy as u8).unwrap();
+        assert_eq!(decode_cells(&board.cells), Ok(board.cells));
+        assert_eq!(decode_cells(&board.to_packed()), Ok(board.cells));
+        assert_eq!(decode_cells(&[]), Err(InputError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_puzzle_from_cells_hash_mismatch() {
+        let board = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let mut hash = puzzle_hash(&board);
+        hash[0] ^= 1;
+        assert_eq!(
+            puzzle_from_cells(&board.cells, &hash),
+            Err(InputError::PuzzleHashMismatch)
+        );
+    }
+
+    #[test]
+    fn test_puzzle_from_seed_difficulty() {
+        assert_eq!(
+            puzzle_from_seed(666, DIFFICULTY_UNRATED),
+            Err(InputError::InvalidDifficulty)
+        );
+        assert_eq!(
+            puzzle_from_seed(666, Difficulty::Hard as u8),
+            Ok(Board::from_seed(666, Some(Difficulty::Hard)))
+        );
+    }
+
+    #[test]
+    fn test_check_givens() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        assert_eq!(check_givens(&puzzle, &puzzle.cells), Ok(()));
+
+        // filling in an empty cell is fine, changing a given isn't.
+        let mut input = puzzle.cells;
+        let empty = input.iter().position(|&x| x == 0).unwrap();
+        input[empty] = 1;
+        assert_eq!(check_givens(&puzzle, &input), Ok(()));
+        input[0] = input[0] % 9 + 1;
+        assert_eq!(
+            check_givens(&puzzle, &input),
+            Err(InputError::GivenMismatch)
+        );
+        assert_eq!(InputError::GivenMismatch.code(), 6);
+    }
+
+    #[test]
+    fn test_givens_mask() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let mask = givens_mask(&puzzle);
+        let bits: u32 = mask.iter().map(|byte| byte.count_ones()).sum();
+        assert_eq!(bits, puzzle.clue_count() as u32);
+        for (i, &cell) in puzzle.cells.iter().enumerate() {
+            assert_eq!(mask[i / 8] >> (i % 8) & 1 == 1, cell != 0);
+        }
+        // only 81 of the 88 bits are cells.
+        assert_eq!(mask[10] >> 1, 0);
+
+        let values = verify_solution(puzzle.clone(), 2, &[0; 81], &[0; 32]).unwrap();
+        assert_eq!(values.givens_hash, givens_hash(&puzzle));
+        assert_eq!(values.status, InputError::GivenMismatch.code());
+    }
+
+    #[test]
+    fn test_verify_solution() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = [
+            9, 2, 7, 1, 3, 6, 8, 4, 5, 1, 3, 4, 2, 5, 8, 6, 7, 9, 5, 6, 8, 4, 7, 9, 1, 3, 2, 2, 7,
+            1, 8, 4, 3, 5, 9, 6, 6, 5, 3, 9, 2, 1, 4, 8, 7, 4, 8, 9, 7, 6, 5, 2, 1, 3, 7, 1, 2, 5,
+            9, 4, 3, 6, 8, 8, 9, 6, 3, 1, 2, 7, 5, 4, 3, 4, 5, 6, 8, 7, 9, 2, 1,
+        ];
+        let values = verify_solution(puzzle.clone(), 1, &solution, &[0; 32]).unwrap();
+        assert!(values.valid);
+        assert_eq!(values.status, STATUS_OK);
+        assert_eq!(values.clue_count, puzzle.clue_count());
+        assert_eq!(values.puzzle_hash, puzzle_hash(&puzzle));
+        let board = Board::from_array(solution).unwrap();
+        assert_eq!(values.solution_commitment, solution_hash(&board, &[0; 32]));
+        assert_ne!(
+            verify_solution(puzzle.clone(), 1, &solution, &[1; 32])
+                .unwrap()
+                .solution_commitment,
+            values.solution_commitment
+        );
+
+        let packed = Board::from_array(solution).unwrap().to_packed();
+        assert_eq!(
+            verify_solution(puzzle.clone(), 1, &packed, &[0; 32]),
+            Ok(values)
+        );
+
+        let mut overwritten = solution;
+        overwritten[3] = 2;
+        let values = verify_solution(puzzle.clone(), 1, &overwritten, &[0; 32]).unwrap();
+        assert!(!values.valid);
+        assert_eq!(values.status, InputError::GivenMismatch.code());
+        assert_eq!(values.solution_commitment, [0; 32]);
+
+        assert_eq!(
+            verify_solution(puzzle, 1, &[0; 3], &[0; 32]),
+            Err(InputError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_verify_variant() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+
+        let classic = verify_variant(
+            puzzle.clone(),
+            1,
+            &Variant::Classic,
+            &solution.cells,
+            &[0; 32],
+        );
+        assert_eq!(
+            classic,
+            verify_solution(puzzle.clone(), 1, &solution.cells, &[0; 32])
+        );
+
+        // the generated puzzles are classic, this solution repeats a value on the diagonal.
+        let x = verify_variant(puzzle.clone(), 1, &Variant::X, &solution.cells, &[0; 32]).unwrap();
+        assert!(!x.valid);
+        assert_eq!(x.status, STATUS_OK);
+        assert_eq!(x.variant, VARIANT_X);
+        assert_eq!(x.puzzle_hash, Variant::X.puzzle_hash(&puzzle));
+
+        assert_eq!(decode_variant(&[VARIANT_X]), Ok(Variant::X));
+        assert_eq!(decode_variant(&[7]), Err(InputError::InvalidVariant));
+    }
+
+    #[test]
+    fn test_verify_input() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let source = PuzzleSource::Seed {
+            seed: 2200,
+            difficulty: Difficulty::Easy as u8,
+        };
+        let no_signature = |_: &[u8; 32], _: &[u8; 32], _: &[u8; 64]| false;
+
+        let mut input = ProofInput::new(source, solution.cells.to_vec());
+        input.epoch = 5;
+        input.reward_address = [0xab; 20];
+        input.nonce = [0x42; 32];
+        let values = verify_input(&input, no_signature).unwrap();
+        assert!(values.valid);
+        assert_eq!(values.logic_version, LOGIC_VERSION);
+        assert_eq!((values.seed, values.epoch), (2200, 5));
+        assert_eq!(
+            values,
+            bind(
+                verify_solution(puzzle.clone(), 1, &solution.cells, &[0; 32]).unwrap(),
+                2200,
+                5,
+                [0xab; 20],
+                [0x42; 32]
+            )
+        );
+
+        input.organizer = Some(([1; 32], [0; 64].to_vec()));
+        assert_eq!(
+            verify_input(&input, no_signature),
+            Err(InputError::BadSignature)
+        );
+
+        let supplied = PuzzleSource::Puzzle {
+            cells: puzzle.cells.to_vec(),
+            expected_hash: puzzle_hash(&puzzle),
+        };
+        assert_eq!(supplied.load(), Ok((puzzle, DIFFICULTY_UNRATED, 0)));
+    }
+
+    #[test]
+    fn test_check_solvable() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let salt = [7; 32];
+        let values = check_solvable(&puzzle, &salt);
+        assert!(values.solvable);
+        assert_eq!(values.puzzle_hash, puzzle_hash(&puzzle));
+
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        assert_eq!(values.solution_hash, solution_hash(&solution, &salt));
+
+        // two 1s in the first row, nothing to find.
+        let mut cells = [0; 81];
+        cells[0] = 1;
+        cells[1] = 1;
+        let values = check_solvable(&Board { cells }, &salt);
+        assert!(!values.solvable);
+        assert_eq!(values.solution_hash, [0; 32]);
+    }
+
+    #[test]
+    fn test_check_unique() {
+        let puzzle = Board::from_array(
+            "000000010400000000020000000000050407008000300001090000300400200050100000000806000"
+                .bytes()
+                .map(|b| b - b'0')
+                .collect::<Vec<u8>>()
+                .try_into()
+                .unwrap(),
+        )
+        .unwrap();
+        let values = check_unique(&puzzle);
+        assert!(values.unique);
+        assert_eq!(values.puzzle_hash, puzzle_hash(&puzzle));
+
+        // an empty board has plenty of solutions.
+        assert!(!check_unique(&Board { cells: [0; 81] }).unique);
+    }
+
+    #[test]
+    fn test_check_progress() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let empty: Vec<usize> = (0..81).filter(|&i| puzzle.cells[i] == 0).collect();
+
+        // fill in half of the empty cells from the solution.
+        let mut partial = puzzle.cells;
+        for &i in &empty[..empty.len() / 2] {
+            partial[i] = solution.cells[i];
+        }
+        let values = check_progress(puzzle.clone(), &partial).unwrap();
+        assert!(values.consistent);
+        assert_eq!(values.status, STATUS_OK);
+        assert_eq!(values.clue_count, puzzle.clue_count());
+        assert_eq!(values.filled as usize, empty.len() / 2);
+
+        // a clash with a given in the same row.
+        let mut clashing = puzzle.cells;
+        let row = empty[0] / 9;
+        let given = (row * 9..row * 9 + 9)
+            .find(|&i| puzzle.cells[i] != 0)
+            .unwrap();
+        clashing[empty[0]] = puzzle.cells[given];
+        let values = check_progress(puzzle.clone(), &clashing).unwrap();
+        assert!(!values.consistent);
+        assert_eq!(values.filled, 0);
+
+        let mut overwritten = partial;
+        overwritten[given] = overwritten[given] % 9 + 1;
+        let values = check_progress(puzzle, &overwritten).unwrap();
+        assert_eq!(values.status, InputError::GivenMismatch.code());
+    }
+
+    #[test]
+    fn test_check_transcript() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let empty: Vec<u8> = (0..81).filter(|&i| puzzle.cells[i as usize] == 0).collect();
+        let solve: Vec<Move> = empty
+            .iter()
+            .map(|&cell| Move {
+                cell,
+                value: solution.cells[cell as usize],
+            })
+            .collect();
+
+        let values = check_transcript(puzzle.clone(), &solve);
+        assert!(values.valid);
+        assert_eq!(values.move_count, solve.len() as u32);
+        assert_eq!(values.illegal_move, values.move_count);
+        assert_eq!(values.transcript_hash, transcript_hash(&solve));
+
+        // a wrong guess that gets erased again still counts as a legal session.
+        let first = empty[0];
+        let wrong = (1..=9)
+            .find(|&v| v != solution.cells[first as usize] && puzzle.can_place(first as usize, v))
+            .unwrap();
+        let mut corrected = vec![
+            Move {
+                cell: first,
+                value: wrong,
+            },
+            Move {
+                cell: first,
+                value: 0,
+            },
+        ];
+        corrected.extend(&solve);
+        assert!(check_transcript(puzzle.clone(), &corrected).valid);
+
+        // stopping halfway is legal but not solved.
+        let values = check_transcript(puzzle.clone(), &solve[..solve.len() / 2]);
+        assert!(!values.valid);
+        assert_eq!(values.illegal_move, values.move_count);
+
+        // touching a given.
+        let given = (0..81).find(|&i| puzzle.cells[i as usize] != 0).unwrap();
+        let mut overwrite = solve.clone();
+        overwrite.insert(
+            3,
+            Move {
+                cell: given,
+                value: 0,
+            },
+        );
+        let values = check_transcript(puzzle.clone(), &overwrite);
+        assert!(!values.valid);
+        assert_eq!(values.illegal_move, 3);
+
+        // placing a value the row already has.
+        let row = first as usize / 9;
+        let clash = (row * 9..row * 9 + 9)
+            .find(|&i| puzzle.cells[i] != 0)
+            .map(|i| puzzle.cells[i])
+            .unwrap();
+        let values = check_transcript(
+            puzzle,
+            &[Move {
+                cell: first,
+                value: clash,
+            }],
+        );
+        assert!(!values.valid);
+        assert_eq!(values.illegal_move, 0);
+    }
+
+    #[test]
+    fn test_extend_checkpoint() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let moves: Vec<Move> = (0..81)
+            .filter(|&i| puzzle.cells[i] == 0)
+            .map(|i| Move {
+                cell: i as u8,
+                value: solution.cells[i],
+            })
+            .collect();
+        let (first_half, second_half) = moves.split_at(moves.len() / 2);
+        let salt = [7; 32];
+        let vkey = [0xaa; 32];
+
+        let first = extend_checkpoint(puzzle.clone(), None, [0; 32], first_half, &salt).unwrap();
+        assert!(first.legal && !first.solved);
+        assert_eq!(first.move_count, first_half.len() as u32);
+        let mut board = puzzle.clone();
+        for m in first_half {
+            board.cells[m.cell as usize] = m.value;
+        }
+        assert_eq!(first.board_commitment, solution_hash(&board, &salt));
+
+        let second = extend_checkpoint(
+            puzzle.clone(),
+            Some((&first, &board.cells)),
+            vkey,
+            second_half,
+            &salt,
+        )
+        .unwrap();
+        assert!(second.legal && second.solved);
+        assert_eq!(second.move_count, moves.len() as u32);
+        assert_eq!(second.board_commitment, solution_hash(&solution, &salt));
+        assert_eq!(second.previous_vkey, vkey);
+
+        // a board the previous link didn't commit to.
+        assert_eq!(
+            extend_checkpoint(
+                puzzle.clone(),
+                Some((&first, &puzzle.cells)),
+                vkey,
+                second_half,
+                &salt
+            ),
+            Err(InputError::CheckpointMismatch)
+        );
+        // a previous link that was verified against another program's key.
+        assert_eq!(
+            extend_checkpoint(
+                puzzle.clone(),
+                Some((&second, &solution.cells)),
+                [0xbb; 32],
+                &[],
+                &salt
+            ),
+            Err(InputError::CheckpointMismatch)
+        );
+
+        // an illegal move sticks to the rest of the chain.
+        let given = (0..81).find(|&i| puzzle.cells[i] != 0).unwrap() as u8;
+        let erase_given = [Move {
+            cell: given,
+            value: 0,
+        }];
+        let broken = extend_checkpoint(puzzle.clone(), None, [0; 32], &erase_given, &salt).unwrap();
+        assert!(!broken.legal);
+        let rest = extend_checkpoint(
+            puzzle.clone(),
+            Some((&broken, &puzzle.cells)),
+            vkey,
+            &moves,
+            &salt,
+        )
+        .unwrap();
+        assert!(!rest.legal && !rest.solved);
+    }
+
+    #[test]
+    fn test_merkle_path() {
+        let leaves: Vec<[u8; 32]> = (0..5u8).map(|i| [i; 32]).collect();
+        let root = merkle_root(&leaves);
+        for (i, leaf) in leaves.iter().enumerate() {
+            let path = merkle_path(&leaves, i);
+            assert_eq!(path.len(), 3);
+            assert!(verify_merkle_path(&root, leaf, i, &path));
+        }
+        // the last leaf of an odd level is paired with itself, the others only verify in place.
+        for (i, leaf) in leaves.iter().enumerate().take(4) {
+            let path = merkle_path(&leaves, i);
+            assert!(!verify_merkle_path(&root, leaf, i ^ 1, &path));
+        }
+        assert!(merkle_path(&leaves[..1], 0).is_empty());
+    }
+
+    #[test]
+    fn test_cell_openings() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let salt = [3; 32];
+        let values = verify_solution(puzzle, 1, &solution.cells, &salt).unwrap();
+        assert_eq!(values.cells_root, cells_root(&solution, &salt));
+
+        for index in 0..81 {
+            let opening = open_cell(&solution, &salt, index);
+            assert_eq!(opening.value, solution.cells[index as usize]);
+            assert!(verify_cell(&values.cells_root, &opening));
+        }
+
+        let mut wrong_value = open_cell(&solution, &salt, 40);
+        wrong_value.value = wrong_value.value % 9 + 1;
+        assert!(!verify_cell(&values.cells_root, &wrong_value));
+        let mut wrong_salt = open_cell(&solution, &salt, 40);
+        wrong_salt.salt = [3; 32];
+        assert!(!verify_cell(&values.cells_root, &wrong_salt));
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        assert_eq!(merkle_root(&[]), [0; 32]);
+        assert_eq!(merkle_root(&[[1; 32]]), [1; 32]);
+
+        let (a, b, c) = ([1; 32], [2; 32], [3; 32]);
+        assert_eq!(merkle_root(&[a, b]), sha256(&[[1; 32], [2; 32]].concat()));
+        assert_eq!(
+            merkle_root(&[a, b, c]),
+            hash_pair(&hash_pair(&a, &b), &hash_pair(&c, &c))
+        );
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let valid = PublicValues {
+            puzzle_hash: [1; 32],
+            valid: true,
+            ..Default::default()
+        };
+        let invalid = PublicValues {
+            puzzle_hash: [2; 32],
+            status: InputError::GivenMismatch.code(),
+            ..Default::default()
+        };
+        let batch = aggregate(&[valid.clone(), invalid.clone(), valid.clone()]);
+        assert_eq!(batch.count, 3);
+        assert_eq!(batch.valid_count, 2);
+        assert_eq!(
+            batch.results_root,
+            merkle_root(&[
+                result_leaf(&valid),
+                result_leaf(&invalid),
+                result_leaf(&valid)
+            ])
+        );
+        // the order of the submissions is part of the commitment.
+        assert_ne!(
+            aggregate(&[invalid, valid.clone(), valid]).results_root,
+            batch.results_root
+        );
+    }
+
+    #[test]
+    fn test_apply_policy() {
+        let puzzle = puzzle_from_seed(2200, Difficulty::Easy as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let values = verify_solution(puzzle.clone(), 1, &solution.cells, &[0; 32]).unwrap();
+
+        let accepted = apply_policy(values.clone(), &Policy::NONE);
+        assert!(accepted.valid);
+        assert_eq!(accepted.policy_hash, Policy::NONE.hash());
+
+        let hard_only = Policy {
+            min_difficulty: Difficulty::Hard as u8,
+            max_clues: 81,
+        };
+        let rejected = apply_policy(values.clone(), &hard_only);
+        assert!(!rejected.valid);
+        assert_eq!(rejected.status, InputError::PolicyViolation.code());
+        assert_eq!(rejected.policy_hash, hard_only.hash());
+
+        let few_clues = Policy {
+            min_difficulty: DIFFICULTY_UNRATED,
+            max_clues: puzzle.clue_count() - 1,
+        };
+        assert!(!apply_policy(values, &few_clues).valid);
+        assert_ne!(few_clues.hash(), hard_only.hash());
+
+        assert_eq!(Policy::from_bytes(&hard_only.to_bytes()), Ok(hard_only));
+        assert_eq!(Policy::from_bytes(&[1]), Err(InputError::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_check_organizer() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let values = verify_solution(puzzle, 2, &[0; 81], &[0; 32]).unwrap();
+        let key = [7; 32];
+        // stands in for ed25519, a "signature" is the key followed by the message.
+        let verify = |key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]| {
+            signature[..32] == key[..] && signature[32..] == message[..]
+        };
+
+        let mut signature = [0; 64];
+        signature[..32].copy_from_slice(&key);
+        signature[32..].copy_from_slice(&values.puzzle_hash);
+        let signed = check_organizer(values.clone(), &key, &signature, verify).unwrap();
+        assert_eq!(signed.organizer_key, key);
+        assert_eq!(signed.puzzle_hash, values.puzzle_hash);
+
+        assert_eq!(
+            check_organizer(values.clone(), &[8; 32], &signature, verify),
+            Err(InputError::BadSignature)
+        );
+        assert_eq!(
+            check_organizer(values, &key, &signature[..63], verify),
+            Err(InputError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_bind() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let values = verify_solution(puzzle, 2, &[0; 81], &[0; 32]).unwrap();
+        let bound = bind(values.clone(), 666, 3, [0xab; 20], [7; 32]);
+        assert_eq!((bound.seed, bound.epoch, bound.nonce), (666, 3, [7; 32]));
+        assert_eq!(bound.nullifier, nullifier(&values.puzzle_hash, &[0xab; 20]));
+        // the nonce binds the proof to a request, not to the reward.
+        let rebound = bind(values.clone(), 666, 3, [0xab; 20], [8; 32]);
+        assert_eq!(rebound.nullifier, bound.nullifier);
+        assert_ne!(
+            bind(values, 666, 3, [0xac; 20], [7; 32]).nullifier,
+            bound.nullifier
+        );
+    }
+
+    #[test]
+    fn test_abi_round_trip() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let values = verify_solution(puzzle, 2, &[0; 81], &[9; 32]).unwrap();
+        let values = apply_policy(bind(values, 666, 7, [0xab; 20], [0xcd; 32]), &Policy::NONE);
+        let bytes = values.abi_encode();
+        assert_eq!(bytes[..32], values.puzzle_hash);
+        // uint32 seed, right aligned big endian in the fifth word.
+        assert_eq!(bytes[4 * 32 + 28..5 * 32], 666u32.to_be_bytes());
+        assert_eq!(bytes[8 * 32 + 12..9 * 32], [0xab; 20]);
+        assert_eq!(bytes[13 * 32 + 28..14 * 32], LOGIC_VERSION.to_be_bytes());
+        assert_eq!(bytes[14 * 32..15 * 32], values.cells_root);
+        assert_eq!(bytes[15 * 32..16 * 32], values.givens_hash);
+        assert_eq!(bytes[16 * 32..], [0xcd; 32]);
+        assert_eq!(PublicValues::abi_decode(&bytes), Ok(values));
+
+        let mut dirty = bytes;
+        dirty[2 * 32] = 1;
+        assert_eq!(
+            PublicValues::abi_decode(&dirty),
+            Err(InputError::InvalidEncoding)
+        );
+        assert_eq!(
+            PublicValues::abi_decode(&bytes[1..]),
+            Err(InputError::InvalidEncoding)
+        );
+    }
+
+    #[test]
+    fn test_digest_words_round_trip() {
+        let digest = puzzle_hash(&puzzle_from_seed(666, Difficulty::Medium as u8).unwrap());
+        let words = digest_to_words(&digest);
+        assert_eq!(words[0].to_le_bytes(), digest[..4]);
+        assert_eq!(digest_from_words(&words), digest);
+    }
+
+    #[test]
+    fn test_reveal_words_round_trip() {
+        let puzzle = puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let mut values = verify_solution(puzzle, 2, &[0; 81], &[0; 32]).unwrap();
+        values.organizer_key = [0xcd; 32];
+        let values = bind(
+            values,
+            0x1234_5678,
+            0x0102_0304_0506_0708,
+            [0xab; 20],
+            [0xef; 32],
+        );
+        let values = apply_policy(values, &Policy::NONE);
+        let words = to_reveal_words(&values);
+        assert_eq!(words[3], 0x0506_0708);
+        assert_eq!(words[4], 0x0102_0304);
+        assert_eq!(from_reveal_words(&words), values);
+    }
+
+    #[test]
+    #[cfg(feature = "panic-free")]
+    fn test_finish_commits_error_code() {
+        let values: PublicValues = finish(Err(InputError::PuzzleHashMismatch));
+        assert!(!values.valid);
+        assert_eq!(values.status, InputError::PuzzleHashMismatch.code());
+        assert_eq!(values.logic_version, LOGIC_VERSION);
+    }
+
+    #[test]
+    #[cfg(not(feature = "panic-free"))]
+    #[should_panic(expected = "invalid input")]
+    fn test_finish_panics() {
+        let _: PublicValues = finish(Err(InputError::PuzzleHashMismatch));
+    }
+
+    #[test]
+    fn test_puzzle_from_cells_bad_input() {
+        let hash = [0; 32];
+        assert_eq!(
+            puzzle_from_cells(&[0; 80], &hash),
+            Err(InputError::InvalidEncoding)
+        );
+        assert_eq!(
+            puzzle_from_cells(&[10; 81], &hash),
+            Err(InputError::InvalidPuzzle)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+// postcard messages for microcontroller clients, the no_std end of the crate: a board, the moves
+// of a session or a puzzle id, sent to a host prover over serial or BLE and back. a message is
+// postcard (varints, no field names) framed with COBS, so it has no zero byte but its last and a
+// reader on a byte stream collects bytes up to the zero and decodes them:
+//
+//     cobs(tag | body) 0x00
+//
+// the tag says which of the three it is, 0 a board, 1 moves, 2 a puzzle id. a board's body is
+// its 41 packed bytes (`Board::to_packed`) behind their length, moves are their count and a
+// (cell, value) pair each, and a puzzle id its seed as a varint, difficulty and variant. a board
+// is 45 bytes on the wire and an id at most 10. encoding writes into the caller's buffer and
+// decoding works in place, nothing allocates but the moves' vec.
+//
+//     let frame = postcard::encode_board(&board, &mut buf)?;
+//     uart.write_all(frame)?;
+//     ...
+//     let board = postcard::decode_board(&mut received)?;
+//
+// decoding checks what the other encodings check: cells 0-9, an id's difficulty and variant.
+// a frame holding another message than the one asked for is refused.
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+use sudoku::core::board::{Board, Difficulty, PACKED_LEN};
+use sudoku::core::variant::{VARIANT_CLASSIC, VARIANT_X};
+
+use crate::puzzle_id::{PuzzleId, PuzzleIdError};
+use crate::Move;
+
+// a board framed: tag, length and cells, then COBS' overhead byte and the terminator. buffers
+// this size hold any board or puzzle id.
+pub const BOARD_FRAME_LEN: usize = 1 + 1 + PACKED_LEN + 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PostcardError {
+    // the buffer is too small to encode into, or the frame isn't COBS or postcard.
+    Postcard(::postcard::Error),
+    // a well-formed frame holding another message.
+    UnexpectedMessage,
+    // a board that doesn't unpack to 81 cells of 0-9.
+    InvalidCells,
+    InvalidPuzzleId(PuzzleIdError),
+}
+
+// the order of the variants is the tag on the wire, new ones go last.
+#[derive(Serialize, Deserialize)]
+enum Message<'a> {
+    Board(&'a [u8]),
+    Moves(Cow<'a, [Move]>),
+    PuzzleId {
+        seed: u32,
+        difficulty: u8,
+        variant: u8,
+    },
+}
+
+fn encode<'b>(message: &Message, buf: &'b mut [u8]) -> Result<&'b mut [u8], PostcardError> {
+    ::postcard::to_slice_cobs(message, buf).map_err(PostcardError::Postcard)
+}
+
+fn decode(frame: &mut [u8]) -> Result<Message<'_>, PostcardError> {
+    ::postcard::from_bytes_cobs(frame).map_err(PostcardError::Postcard)
+}
+
+// the frame written at the start of `buf`, terminator included.
+pub fn encode_board<'b>(board: &Board, buf: &'b mut [u8]) -> Result<&'b mut [u8], PostcardError> {
+    encode(&Message::Board(&board.to_packed()), buf)
+}
+
+// decodes the frame in place, `frame` is garbage afterwards.
+pub fn decode_board(frame: &mut [u8]) -> Result<Board, PostcardError> {
+    match decode(frame)? {
+        Message::Board(packed) => {
+            Board::from_packed(packed).map_err(|_| PostcardError::InvalidCells)
+        }
+        _ => Err(PostcardError::UnexpectedMessage),
+    }
+}
+
+pub fn encode_moves<'b>(moves: &[Move], buf: &'b mut [u8]) -> Result<&'b mut [u8], PostcardError> {
+    encode(&Message::Moves(Cow::Borrowed(moves)), buf)
+}
+
+// the moves as they were sent, replay_moves is what checks them against a puzzle.
+pub fn decode_moves(frame: &mut [u8]) -> Result<Vec<Move>, PostcardError> {
+    match decode(frame)? {
+        Message::Moves(moves) => Ok(moves.into_owned()),
+        _ => Err(PostcardError::UnexpectedMessage),
+    }
+}
+
+pub fn encode_puzzle_id<'b>(
+    id: &PuzzleId,
+    buf: &'b mut [u8],
+) -> Result<&'b mut [u8], PostcardError> {
+    let message = Message::PuzzleId {
+        seed: id.seed,
+        difficulty: id.difficulty as u8,
+        variant: id.variant,
+    };
+    encode(&message, buf)
+}
+
+pub fn decode_puzzle_id(frame: &mut [u8]) -> Result<PuzzleId, PostcardError> {
+    match decode(frame)? {
+        Message::PuzzleId {
+            seed,
+            difficulty,
+            variant,
+        } => {
+            let difficulty = Difficulty::from_u8(difficulty).ok_or(
+                PostcardError::InvalidPuzzleId(PuzzleIdError::InvalidDifficulty),
+            )?;
+            if ![VARIANT_CLASSIC, VARIANT_X].contains(&variant) {
+                return Err(PostcardError::InvalidPuzzleId(
+                    PuzzleIdError::UnsupportedVariant,
+                ));
+            }
+            Ok(PuzzleId {
+                seed,
+                difficulty,
+                variant,
+            })
+        }
+        _ => Err(PostcardError::UnexpectedMessage),
+    }
+}
+
+#[cfg(test)]
+mod postcard_tests {
+    use super::*;
+    use sudoku::core::variant::Variant;
+
+    #[test]
+    fn test_round_trips() {
+        let mut buf = [0u8; 256];
+        let board = Board::from_seed(42, Some(Difficulty::Hard));
+        let frame = encode_board(&board, &mut buf).unwrap();
+        assert_eq!(frame.len(), BOARD_FRAME_LEN);
+        // COBS leaves the terminator as the only zero.
+        assert_eq!(frame.iter().position(|&b| b == 0), Some(frame.len() - 1));
+        assert_eq!(decode_board(frame), Ok(board));
+
+        let moves = [Move { cell: 2, value: 4 }, Move { cell: 80, value: 0 }];
+        let frame = encode_moves(&moves, &mut buf).unwrap();
+        assert_eq!(frame.len(), 1 + 1 + 2 * 2 + 2);
+        assert_eq!(decode_moves(frame), Ok(moves.to_vec()));
+        let frame = encode_moves(&[], &mut buf).unwrap();
+        assert_eq!(decode_moves(frame), Ok(Vec::new()));
+
+        for seed in [0, u32::MAX] {
+            let id = PuzzleId::new(seed, Difficulty::Medium, &Variant::X).unwrap();
+            let frame = encode_puzzle_id(&id, &mut buf).unwrap();
+            assert!(frame.len() <= 10);
+            assert_eq!(decode_puzzle_id(frame), Ok(id));
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut buf = [0u8; 256];
+        let board = Board::from_seed(42, Some(Difficulty::Hard));
+        assert!(matches!(
+            encode_board(&board, &mut [0u8; BOARD_FRAME_LEN - 1]),
+            Err(PostcardError::Postcard(_))
+        ));
+
+        let frame = encode_board(&board, &mut buf).unwrap();
+        assert_eq!(decode_moves(frame), Err(PostcardError::UnexpectedMessage));
+        let frame = encode_board(&board, &mut buf).unwrap();
+        let len = frame.len();
+        assert!(matches!(
+            decode_board(&mut frame[..len - 10]),
+            Err(PostcardError::Postcard(_))
+        ));
+        assert!(matches!(
+            decode_board(&mut [0x02, 0x07, 0x00]),
+            Err(PostcardError::Postcard(_))
+        ));
+
+        // a board whose first cell is 15.
+        let mut packed = board.to_packed();
+        packed[0] |= 0x0f;
+        let frame = encode(&Message::Board(&packed), &mut buf).unwrap();
+        assert_eq!(decode_board(frame), Err(PostcardError::InvalidCells));
+
+        let frame = encode(
+            &Message::PuzzleId {
+                seed: 1,
+                difficulty: 9,
+                variant: VARIANT_CLASSIC,
+            },
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            decode_puzzle_id(frame),
+            Err(PostcardError::InvalidPuzzleId(
+                PuzzleIdError::InvalidDifficulty
+            ))
+        );
+        let killer = Variant::Killer(Vec::new()).kind();
+        let frame = encode(
+            &Message::PuzzleId {
+                seed: 1,
+                difficulty: Difficulty::Easy as u8,
+                variant: killer,
+            },
+            &mut buf,
+        )
+        .unwrap();
+        assert_eq!(
+            decode_puzzle_id(frame),
+            Err(PostcardError::InvalidPuzzleId(
+                PuzzleIdError::UnsupportedVariant
+            ))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+// protobuf messages of the data model, for services talking gRPC or passing puzzles and proofs
+// through message queues. the messages are generated by prost from
+// proto/zk_sudoku/v1/sudoku.proto at build time (see build.rs), this module converts them to and
+// from the types the rest of the crate uses. converting into a message always works, converting
+// back checks what protobuf can't express: board and hash lengths, values that fit a u8, known
+// backends and cages on the board.
+//
+//     let message = v1::ProofEnvelope::from(&envelope);
+//     let bytes = prost::Message::encode_to_vec(&message);
+//     let envelope = ProofEnvelope::try_from(&v1::ProofEnvelope::decode(&bytes[..])?)?;
+use alloc::vec::Vec;
+
+use sudoku::core::board::Board;
+use sudoku::core::variant::{Cage, Variant};
+
+use crate::envelope::{Backend, ProofEnvelope, ProvedOn};
+use crate::PublicValues;
+
+pub mod v1 {
+    include!(concat!(env!("OUT_DIR"), "/zk_sudoku.v1.rs"));
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtoError {
+    // a board that isn't 81 cells of 0-9.
+    InvalidCells,
+    // the named byte string has the wrong length.
+    InvalidLength(&'static str),
+    // the named number doesn't fit the type the guests commit.
+    OutOfRange(&'static str),
+    UnknownBackend(i32),
+    UnknownProvedOn(i32),
+    InvalidVariant(&'static str),
+}
+
+impl v1::Puzzle {
+    pub fn new(puzzle: &Board, variant: &Variant) -> Self {
+        v1::Puzzle {
+            cells: puzzle.cells.to_vec(),
+            variant: Some(variant.into()),
+            id: None,
+            difficulty: None,
+        }
+    }
+
+    pub fn board(&self) -> Result<Board, ProtoError> {
+        board(&self.cells)
+    }
+
+    // classic when the message has no variant.
+    pub fn rules(&self) -> Result<Variant, ProtoError> {
+        self.variant
+            .as_ref()
+            .map_or(Ok(Variant::Classic), Variant::try_from)
+    }
+}
+
+impl v1::Solution {
+    pub fn board(&self) -> Result<Board, ProtoError> {
+        board(&self.cells)
+    }
+}
+
+impl From<&Variant> for v1::Variant {
+    fn from(variant: &Variant) -> Self {
+        let (kind, cages) = match variant {
+            Variant::Classic => (v1::variant::Kind::Classic, Vec::new()),
+            Variant::X => (v1::variant::Kind::X, Vec::new()),
+            Variant::Killer(cages) => (
+                v1::variant::Kind::Killer,
+                cages
+                    .iter()
+                    .map(|cage| v1::Cage {
+                        sum: cage.sum as u32,
+                        cells: cage.cells.clone(),
+                    })
+                    .collect(),
+            ),
+        };
+        v1::Variant {
+            kind: kind as i32,
+            cages,
+        }
+    }
+}
+
+// the cages are checked the way the guests' decoding checks them.
+impl TryFrom<&v1::Variant> for Variant {
+    type Error = ProtoError;
+
+    fn try_from(message: &v1::Variant) -> Result<Self, ProtoError> {
+        let kind = v1::variant::Kind::try_from(message.kind)
+            .map_err(|_| ProtoError::InvalidVariant("unknown kind"))?;
+        if kind != v1::variant::Kind::Killer && !message.cages.is_empty() {
+            return Err(ProtoError::InvalidVariant(
+                "cages on a variant without them",
+            ));
+        }
+        let variant = match kind {
+            v1::variant::Kind::Classic => Variant::Classic,
+            v1::variant::Kind::X => Variant::X,
+            v1::variant::Kind::Killer => Variant::Killer(
+                message
+                    .cages
+                    .iter()
+                    .map(|cage| {
+                        Ok(Cage {
+                            sum: small(cage.sum, "sum")?,
+                            cells: cage.cells.clone(),
+                        })
+                    })
+                    .collect::<Result<_, ProtoError>>()?,
+            ),
+        };
+        if let Variant::Killer(cages) = &variant {
+            if cages.iter().any(|cage| cage.cells.len() > 81) {
+                return Err(ProtoError::InvalidVariant("cage larger than the board"));
+            }
+        }
+        Variant::from_bytes(&variant.to_bytes()).map_err(ProtoError::InvalidVariant)
+    }
+}
+
+impl From<&PublicValues> for v1::PublicValues {
+    fn from(values: &PublicValues) -> Self {
+        v1::PublicValues {
+            puzzle_hash: values.puzzle_hash.to_vec(),
+            solution_commitment: values.solution_commitment.to_vec(),
+            valid: values.valid,
+            difficulty: values.difficulty as u32,
+            seed: values.seed,
+            nullifier: values.nullifier.to_vec(),
+            clue_count: values.clue_count as u32,
+            epoch: values.epoch,
+            reward_address: values.reward_address.to_vec(),
+            status: values.status as u32,
+            policy_hash: values.policy_hash.to_vec(),
+            organizer_key: values.organizer_key.to_vec(),
+            variant: values.variant as u32,
+            logic_version: values.logic_version,
+            cells_root: values.cells_root.to_vec(),
+            givens_hash: values.givens_hash.to_vec(),
+            nonce: values.nonce.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<&v1::PublicValues> for PublicValues {
+    type Error = ProtoError;
+
+    fn try_from(message: &v1::PublicValues) -> Result<Self, ProtoError> {
+        Ok(PublicValues {
+            puzzle_hash: bytes(&message.puzzle_hash, "puzzle_hash")?,
+            solution_commitment: bytes(&message.solution_commitment, "solution_commitment")?,
+            valid: message.valid,
+            difficulty: small(message.difficulty, "difficulty")?,
+            seed: message.seed,
+            nullifier: bytes(&message.nullifier, "nullifier")?,
+            clue_count: small(message.clue_count, "clue_count")?,
+            epoch: message.epoch,
+            reward_address: bytes(&message.reward_address, "reward_address")?,
+            status: small(message.status, "status")?,
+            policy_hash: bytes(&message.policy_hash, "policy_hash")?,
+            organizer_key: bytes(&message.organizer_key, "organizer_key")?,
+            variant: small(message.variant, "variant")?,
+            logic_version: message.logic_version,
+            cells_root: bytes(&message.cells_root, "cells_root")?,
+            givens_hash: bytes(&message.givens_hash, "givens_hash")?,
+            nonce: bytes(&message.nonce, "nonce")?,
+        })
+    }
+}
+
+impl From<&ProofEnvelope> for v1::ProofEnvelope {
+    fn from(envelope: &ProofEnvelope) -> Self {
+        let backend = match envelope.backend {
+            Backend::Sp1 => v1::Backend::Sp1,
+            Backend::OpenVm => v1::Backend::Openvm,
+            Backend::Jolt => v1::Backend::Jolt,
+        };
+        let proved_on = match envelope.proved_on {
+            ProvedOn::Cpu => v1::ProvedOn::Cpu,
+            ProvedOn::Cuda => v1::ProvedOn::Cuda,
+            ProvedOn::Network => v1::ProvedOn::Network,
+        };
+        v1::ProofEnvelope {
+            backend: backend as i32,
+            proved_on: proved_on as i32,
+            vkey_hash: envelope.vkey_hash.clone(),
+            program_digest: envelope.program_digest.clone(),
+            public_values: envelope.public_values.clone(),
+            proof: envelope.proof.clone(),
+        }
+    }
+}
+
+impl TryFrom<&v1::ProofEnvelope> for ProofEnvelope {
+    type Error = ProtoError;
+
+    // the message's enums use the envelope's numbering, unspecified (0) is unknown to both.
+    fn try_from(message: &v1::ProofEnvelope) -> Result<Self, ProtoError> {
+        let backend = u8::try_from(message.backend)
+            .ok()
+            .and_then(Backend::from_u8)
+            .ok_or(ProtoError::UnknownBackend(message.backend))?;
+        let proved_on = u8::try_from(message.proved_on)
+            .ok()
+            .and_then(ProvedOn::from_u8)
+            .ok_or(ProtoError::UnknownProvedOn(message.proved_on))?;
+        Ok(ProofEnvelope {
+            backend,
+            proved_on,
+            vkey_hash: message.vkey_hash.clone(),
+            program_digest: message.program_digest.clone(),
+            public_values: message.public_values.clone(),
+            proof: message.proof.clone(),
+        })
+    }
+}
+
+fn board(cells: &[u8]) -> Result<Board, ProtoError> {
+    let cells: [u8; 81] = cells.try_into().map_err(|_| ProtoError::InvalidCells)?;
+    Board::from_array(cells).map_err(|_| ProtoError::InvalidCells)
+}
+
+fn bytes<const N: usize>(value: &[u8], name: &'static str) -> Result<[u8; N], ProtoError> {
+    value
+        .try_into()
+        .map_err(|_| ProtoError::InvalidLength(name))
+}
+
+fn small(value: u32, name: &'static str) -> Result<u8, ProtoError> {
+    u8::try_from(value).map_err(|_| ProtoError::OutOfRange(name))
+}
+
+#[cfg(test)]
+mod proto_tests {
+    use super::*;
+    use crate::envelope::MOCK_PROOF;
+    use alloc::vec;
+    use prost::Message;
+    use sudoku::core::board::Difficulty;
+
+    #[test]
+    fn test_round_trips() {
+        let puzzle = Board::from_seed(42, Some(Difficulty::Hard));
+        let killer = Variant::Killer(vec![Cage {
+            sum: 17,
+            cells: vec![0, 9],
+        }]);
+        let message = v1::Puzzle::new(&puzzle, &killer);
+        let decoded = v1::Puzzle::decode(&message.encode_to_vec()[..]).unwrap();
+        assert_eq!(decoded.board(), Ok(puzzle));
+        assert_eq!(decoded.rules(), Ok(killer));
+
+        let values = PublicValues {
+            puzzle_hash: [1; 32],
+            valid: true,
+            difficulty: 3,
+            epoch: u64::MAX,
+            reward_address: [2; 20],
+            status: 4,
+            nonce: [5; 32],
+            ..Default::default()
+        };
+        let message = v1::PublicValues::from(&values);
+        let decoded = v1::PublicValues::decode(&message.encode_to_vec()[..]).unwrap();
+        assert_eq!(PublicValues::try_from(&decoded), Ok(values));
+
+        let envelope = ProofEnvelope {
+            backend: Backend::OpenVm,
+            proved_on: ProvedOn::Cuda,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![8; 32],
+            public_values: vec![9; 544],
+            proof: MOCK_PROOF.to_vec(),
+        };
+        let message = v1::ProofEnvelope::from(&envelope);
+        assert_eq!(message.backend(), v1::Backend::Openvm);
+        let decoded = v1::ProofEnvelope::decode(&message.encode_to_vec()[..]).unwrap();
+        assert_eq!(ProofEnvelope::try_from(&decoded), Ok(envelope));
+    }
+
+    #[test]
+    fn test_errors() {
+        let puzzle = v1::Puzzle {
+            cells: vec![0; 80],
+            ..Default::default()
+        };
+        assert_eq!(puzzle.board(), Err(ProtoError::InvalidCells));
+        assert_eq!(puzzle.rules(), Ok(Variant::Classic));
+
+        let values = v1::PublicValues {
+            puzzle_hash: vec![1; 31],
+            ..v1::PublicValues::from(&PublicValues::default())
+        };
+        assert_eq!(
+            PublicValues::try_from(&values),
+            Err(ProtoError::InvalidLength("puzzle_hash"))
+        );
+        let values = v1::PublicValues {
+            status: 256,
+            ..v1::PublicValues::from(&PublicValues::default())
+        };
+        assert_eq!(
+            PublicValues::try_from(&values),
+            Err(ProtoError::OutOfRange("status"))
+        );
+
+        let envelope = v1::ProofEnvelope::default();
+        assert_eq!(
+            ProofEnvelope::try_from(&envelope),
+            Err(ProtoError::UnknownBackend(0))
+        );
+        let cage = v1::Variant {
+            kind: v1::variant::Kind::X as i32,
+            cages: vec![v1::Cage {
+                sum: 3,
+                cells: vec![0, 1],
+            }],
+        };
+        assert!(Variant::try_from(&cage).is_err());
+    }
+}
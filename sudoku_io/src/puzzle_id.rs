@@ -0,0 +1,20 @@
+// puzzle ids are defined in the sudoku crate, so its command line can print them without
+// depending on this one. re-exported here, where the hosts and bundles have always found them.
+pub use sudoku::core::puzzle_id::*;
+
+#[cfg(test)]
+mod puzzle_id_tests {
+    use super::*;
+    use crate::puzzle_from_seed;
+    use sudoku::core::board::Difficulty;
+    use sudoku::core::variant::Variant;
+
+    #[test]
+    fn test_puzzle_is_what_the_guests_generate() {
+        let id = PuzzleId::new(666, Difficulty::Medium, &Variant::Classic).unwrap();
+        assert_eq!(
+            id.puzzle().ok(),
+            puzzle_from_seed(666, Difficulty::Medium as u8).ok()
+        );
+    }
+}
@@ -0,0 +1,94 @@
+// SCALE encodings for Substrate chains, what the ink! contract in ink/ takes and returns. the
+// contract verifies the same SP1 Groth16 proofs as the solidity verifier and gets them the same
+// way: the proof bytes and the ABI encoded public values they commit, here as the arguments of
+// its `submit_solution` message,
+//
+//     selector (4 bytes) | compact len | proof | compact len | public values
+//
+// which is what `SubmitSolution::call_data` builds for hosts signing the extrinsic themselves.
+// `PublicValues` is Encode, Decode and TypeInfo with this feature, the contract returns the
+// decoded values of an accepted proof and its metadata describes them.
+//
+//     let call = SubmitSolution { proof: proof.bytes(), public_values: proof.public_values.to_vec() };
+//     let data = call.call_data();
+use alloc::vec::Vec;
+
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+
+// the first four bytes of blake2b-256("submit_solution"), ink!'s selector for a message of that
+// name. the contract pins it explicitly so renaming the message doesn't change it.
+pub const SUBMIT_SOLUTION_SELECTOR: [u8; 4] = [0xde, 0x56, 0x6a, 0x5d];
+
+// the arguments of the contract's `submit_solution`: a Groth16 proof
+// (`SP1ProofWithPublicValues::bytes`) and the public values it commits, ABI encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct SubmitSolution {
+    pub proof: Vec<u8>,
+    pub public_values: Vec<u8>,
+}
+
+impl SubmitSolution {
+    pub fn call_data(&self) -> Vec<u8> {
+        let mut data = SUBMIT_SOLUTION_SELECTOR.to_vec();
+        self.encode_to(&mut data);
+        data
+    }
+
+    // the arguments back out of call data, None if it's another message's or truncated.
+    pub fn from_call_data(data: &[u8]) -> Option<SubmitSolution> {
+        let mut args = data.strip_prefix(&SUBMIT_SOLUTION_SELECTOR[..])?;
+        let call = SubmitSolution::decode(&mut args).ok()?;
+        args.is_empty().then_some(call)
+    }
+}
+
+#[cfg(test)]
+mod scale_tests {
+    use super::*;
+    use crate::{PublicValues, LOGIC_VERSION};
+
+    #[test]
+    fn test_call_data() {
+        let values = PublicValues {
+            valid: true,
+            logic_version: LOGIC_VERSION,
+            ..Default::default()
+        };
+        let call = SubmitSolution {
+            proof: alloc::vec![7; 260],
+            public_values: values.abi_encode().to_vec(),
+        };
+        let data = call.call_data();
+        assert_eq!(data[..4], SUBMIT_SOLUTION_SELECTOR);
+        // 260 and the values' length need two byte compact lengths.
+        assert_eq!(data.len(), 4 + 2 + 260 + 2 + call.public_values.len());
+        assert_eq!(SubmitSolution::from_call_data(&data), Some(call.clone()));
+
+        assert_eq!(
+            SubmitSolution::from_call_data(&data[..data.len() - 1]),
+            None
+        );
+        assert_eq!(SubmitSolution::from_call_data(&data[4..]), None);
+        let mut longer = data.clone();
+        longer.push(0);
+        assert_eq!(SubmitSolution::from_call_data(&longer), None);
+    }
+
+    #[test]
+    fn test_public_values() {
+        let values = PublicValues {
+            puzzle_hash: [1; 32],
+            valid: true,
+            difficulty: 2,
+            seed: 42,
+            epoch: 2204,
+            reward_address: [3; 20],
+            logic_version: LOGIC_VERSION,
+            ..Default::default()
+        };
+        let bytes = values.encode();
+        assert_eq!(PublicValues::decode(&mut &bytes[..]), Ok(values));
+        assert!(PublicValues::decode(&mut &bytes[..bytes.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,303 @@
+// the Solidity side of the public values: every struct a guest commits, as alloy types, so the
+// guests, the hosts and the contracts encode and decode one definition. the verifier program's
+// struct is read from sol/PublicValuesStruct.sol, the file the SP1 script's `evm` binary writes
+// next to the contracts, and the hand-written PublicValues::abi_encode the guests use is tested
+// against it. the other programs commit their structs with the helpers below.
+use alloc::vec::Vec;
+
+use alloy_sol_types::{sol, SolType};
+
+use crate::{
+    BatchValues, CheckpointValues, InputError, ProgressValues, PublicValues, SolvabilityValues,
+    TranscriptValues, UniquenessValues,
+};
+
+// the Solidity source of PublicValuesStruct, for writing it out next to the contracts.
+pub const PUBLIC_VALUES_STRUCT: &str = include_str!("../sol/PublicValuesStruct.sol");
+
+sol!("sol/PublicValuesStruct.sol");
+
+sol! {
+    struct SolvabilityPublicValuesStruct {
+        bytes32 puzzleHash;
+        bool solvable;
+        bytes32 solutionHash;
+    }
+
+    struct UniquenessPublicValuesStruct {
+        bytes32 puzzleHash;
+        bool unique;
+    }
+
+    struct ProgressPublicValuesStruct {
+        bytes32 puzzleHash;
+        bool consistent;
+        uint8 clueCount;
+        uint8 filled;
+        uint8 status;
+    }
+
+    struct TranscriptPublicValuesStruct {
+        bytes32 puzzleHash;
+        bool valid;
+        uint32 moveCount;
+        uint32 illegalMove;
+        bytes32 transcriptHash;
+        uint8 status;
+    }
+
+    struct CheckpointPublicValuesStruct {
+        bytes32 puzzleHash;
+        bytes32 boardCommitment;
+        uint32 moveCount;
+        bool legal;
+        bool solved;
+        bytes32 previousVkey;
+        uint8 status;
+    }
+
+    struct BatchPublicValuesStruct {
+        uint32 count;
+        uint32 validCount;
+        bytes32 resultsRoot;
+    }
+}
+
+// From both ways between a values type and its struct, field by field, and abi_encode /
+// abi_decode on the values type going through the struct.
+macro_rules! sol_values {
+    ($values:ident, $sol:ident { $($field:ident: $sol_field:ident),* $(,)? }) => {
+        impl From<&$values> for $sol {
+            fn from(values: &$values) -> Self {
+                $sol {
+                    $($sol_field: values.$field.into(),)*
+                }
+            }
+        }
+
+        impl From<&$sol> for $values {
+            fn from(values: &$sol) -> Self {
+                $values {
+                    $($field: values.$sol_field.into(),)*
+                }
+            }
+        }
+
+        impl $values {
+            pub fn abi_encode(&self) -> Vec<u8> {
+                $sol::abi_encode(&$sol::from(self))
+            }
+
+            pub fn abi_decode(bytes: &[u8]) -> Result<$values, InputError> {
+                $sol::abi_decode(bytes, true)
+                    .map(|values| (&values).into())
+                    .map_err(|_| InputError::InvalidEncoding)
+            }
+        }
+    };
+}
+
+sol_values!(
+    SolvabilityValues,
+    SolvabilityPublicValuesStruct {
+        puzzle_hash: puzzleHash,
+        solvable: solvable,
+        solution_hash: solutionHash,
+    }
+);
+
+sol_values!(
+    UniquenessValues,
+    UniquenessPublicValuesStruct {
+        puzzle_hash: puzzleHash,
+        unique: unique,
+    }
+);
+
+sol_values!(
+    ProgressValues,
+    ProgressPublicValuesStruct {
+        puzzle_hash: puzzleHash,
+        consistent: consistent,
+        clue_count: clueCount,
+        filled: filled,
+        status: status,
+    }
+);
+
+sol_values!(
+    TranscriptValues,
+    TranscriptPublicValuesStruct {
+        puzzle_hash: puzzleHash,
+        valid: valid,
+        move_count: moveCount,
+        illegal_move: illegalMove,
+        transcript_hash: transcriptHash,
+        status: status,
+    }
+);
+
+sol_values!(
+    CheckpointValues,
+    CheckpointPublicValuesStruct {
+        puzzle_hash: puzzleHash,
+        board_commitment: boardCommitment,
+        move_count: moveCount,
+        legal: legal,
+        solved: solved,
+        previous_vkey: previousVkey,
+        status: status,
+    }
+);
+
+sol_values!(
+    BatchValues,
+    BatchPublicValuesStruct {
+        count: count,
+        valid_count: validCount,
+        results_root: resultsRoot,
+    }
+);
+
+// PublicValues keeps its own abi_encode and abi_decode, which the guests use without alloy.
+impl From<&PublicValues> for PublicValuesStruct {
+    fn from(values: &PublicValues) -> Self {
+        PublicValuesStruct {
+            puzzleHash: values.puzzle_hash.into(),
+            solutionCommitment: values.solution_commitment.into(),
+            valid: values.valid,
+            difficulty: values.difficulty,
+            seed: values.seed,
+            nullifier: values.nullifier.into(),
+            clueCount: values.clue_count,
+            epoch: values.epoch,
+            rewardAddress: values.reward_address.into(),
+            status: values.status,
+            policyHash: values.policy_hash.into(),
+            organizerKey: values.organizer_key.into(),
+            variant: values.variant,
+            logicVersion: values.logic_version,
+            cellsRoot: values.cells_root.into(),
+            givensHash: values.givens_hash.into(),
+            nonce: values.nonce.into(),
+        }
+    }
+}
+
+impl From<&PublicValuesStruct> for PublicValues {
+    fn from(values: &PublicValuesStruct) -> Self {
+        PublicValues {
+            puzzle_hash: values.puzzleHash.into(),
+            solution_commitment: values.solutionCommitment.into(),
+            valid: values.valid,
+            difficulty: values.difficulty,
+            seed: values.seed,
+            nullifier: values.nullifier.into(),
+            clue_count: values.clueCount,
+            epoch: values.epoch,
+            reward_address: values.rewardAddress.into(),
+            status: values.status,
+            policy_hash: values.policyHash.into(),
+            organizer_key: values.organizerKey.into(),
+            variant: values.variant,
+            logic_version: values.logicVersion,
+            cells_root: values.cellsRoot.into(),
+            givens_hash: values.givensHash.into(),
+            nonce: values.nonce.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod sol_tests {
+    use super::*;
+
+    fn public_values() -> PublicValues {
+        PublicValues {
+            puzzle_hash: [1; 32],
+            solution_commitment: [2; 32],
+            valid: true,
+            difficulty: 3,
+            seed: 0xdead_beef,
+            nullifier: [4; 32],
+            clue_count: 30,
+            epoch: u64::MAX - 1,
+            reward_address: [5; 20],
+            status: 7,
+            policy_hash: [6; 32],
+            organizer_key: [8; 32],
+            variant: 2,
+            logic_version: crate::LOGIC_VERSION,
+            cells_root: [9; 32],
+            givens_hash: [10; 32],
+            nonce: [11; 32],
+        }
+    }
+
+    // the guests' hand-written encoding is the Solidity struct's.
+    #[test]
+    fn test_public_values_layout() {
+        let values = public_values();
+        let encoded = PublicValuesStruct::abi_encode(&PublicValuesStruct::from(&values));
+        assert_eq!(encoded, values.abi_encode());
+        let decoded = PublicValuesStruct::abi_decode(&values.abi_encode(), true).unwrap();
+        assert_eq!(PublicValues::from(&decoded), values);
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let solvability = SolvabilityValues {
+            puzzle_hash: [1; 32],
+            solvable: true,
+            solution_hash: [2; 32],
+        };
+        let decoded = SolvabilityValues::abi_decode(&solvability.abi_encode());
+        assert_eq!(decoded, Ok(solvability));
+
+        let transcript = TranscriptValues {
+            puzzle_hash: [3; 32],
+            valid: false,
+            move_count: 60,
+            illegal_move: 12,
+            transcript_hash: [4; 32],
+            status: 1,
+        };
+        let decoded = TranscriptValues::abi_decode(&transcript.abi_encode());
+        assert_eq!(decoded, Ok(transcript));
+
+        let checkpoint = CheckpointValues {
+            puzzle_hash: [5; 32],
+            board_commitment: [6; 32],
+            move_count: 9,
+            legal: true,
+            solved: false,
+            previous_vkey: [7; 32],
+            status: 0,
+        };
+        let decoded = CheckpointValues::abi_decode(&checkpoint.abi_encode());
+        assert_eq!(decoded, Ok(checkpoint));
+
+        let batch = BatchValues {
+            count: 4,
+            valid_count: 3,
+            results_root: [8; 32],
+        };
+        assert_eq!(BatchValues::abi_decode(&batch.abi_encode()), Ok(batch));
+        assert_eq!(
+            BatchValues::abi_decode(&[0; 31]),
+            Err(InputError::InvalidEncoding)
+        );
+    }
+
+    // the contracts compile against the same file.
+    #[test]
+    fn test_contracts_struct() {
+        extern crate std;
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../contracts/src/fixtures/PublicValuesStruct.sol"
+        );
+        let contracts = std::fs::read_to_string(path).unwrap();
+        assert_eq!(contracts, PUBLIC_VALUES_STRUCT);
+    }
+}
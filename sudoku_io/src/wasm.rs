@@ -0,0 +1,227 @@
+// checking a .zkproof bundle in the browser, without a backend: the bundle is decoded, its
+// verifying key hash and program digest compared to the ones the page pins (what `zk-sudoku
+// program` prints for the deployed guest), the logic version to this crate's, and for a seeded
+// puzzle the committed puzzle hash to the puzzle its id regenerates. what the page gets back is
+// the public values, the same the host's verify-bundle prints.
+//
+// this is the public values and vkey half of verification, the proof itself isn't checked: the
+// envelopes hold SP1's compressed proofs and OpenVM's app proofs, which only their SDKs verify.
+// a page that can't trust whoever hands it the bundle needs the proof checked on chain or by a
+// verifying service as well.
+//
+//     import init, { checkBundle } from "./sudoku_io.js";
+//     await init();
+//     const checked = checkBundle(bytes, "0x00a1...", "0x5e2f...");
+//     checked.valid, checked.puzzleId, checked.nullifier
+//
+// built like the sudoku crate's bindings, `cargo rustc --lib --release --target
+// wasm32-unknown-unknown --features wasm --crate-type cdylib` and wasm-bindgen on the output.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::bundle::ProofBundle;
+use crate::{PublicValues, LOGIC_VERSION};
+
+// what a bundle proves, byte strings as 0x hex.
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedBundle {
+    pub backend: String,
+    #[wasm_bindgen(js_name = provedOn)]
+    pub proved_on: String,
+    // a mock bundle proves nothing, checkBundle refuses it unless told otherwise.
+    pub mock: bool,
+    #[wasm_bindgen(js_name = puzzleId)]
+    pub puzzle_id: Option<String>,
+    #[wasm_bindgen(js_name = logicVersion)]
+    pub logic_version: u32,
+    #[wasm_bindgen(js_name = puzzleHash)]
+    pub puzzle_hash: String,
+    #[wasm_bindgen(js_name = solutionCommitment)]
+    pub solution_commitment: String,
+    pub valid: bool,
+    pub difficulty: u8,
+    #[wasm_bindgen(js_name = clueCount)]
+    pub clue_count: u8,
+    pub seed: u32,
+    pub nullifier: String,
+    pub epoch: u64,
+    #[wasm_bindgen(js_name = rewardAddress)]
+    pub reward_address: String,
+    pub status: u8,
+    #[wasm_bindgen(js_name = policyHash)]
+    pub policy_hash: String,
+    #[wasm_bindgen(js_name = organizerKey)]
+    pub organizer_key: String,
+    pub variant: u8,
+    #[wasm_bindgen(js_name = cellsRoot)]
+    pub cells_root: String,
+    #[wasm_bindgen(js_name = givensHash)]
+    pub givens_hash: String,
+    pub nonce: String,
+}
+
+// the bundle's public values, once it's shown to come from the pinned program. vkey_hash and
+// program_digest are 0x hex, mocks are refused unless allow_mock is set.
+#[wasm_bindgen(js_name = checkBundle)]
+pub fn check_bundle(
+    bundle: &[u8],
+    vkey_hash: &str,
+    program_digest: &str,
+    allow_mock: Option<bool>,
+) -> Result<CheckedBundle, JsError> {
+    check(
+        bundle,
+        vkey_hash,
+        program_digest,
+        allow_mock.unwrap_or(false),
+    )
+    .map_err(|e| JsError::new(&e))
+}
+
+fn check(
+    bytes: &[u8],
+    vkey_hash: &str,
+    program_digest: &str,
+    allow_mock: bool,
+) -> Result<CheckedBundle, String> {
+    let bundle = ProofBundle::decode(bytes).map_err(|e| format!("not a proof bundle ({:?})", e))?;
+    let envelope = &bundle.envelope;
+    if envelope.is_mock() && !allow_mock {
+        return Err("a mock proof proves nothing".into());
+    }
+    if envelope.vkey_hash != unhex(vkey_hash)? {
+        return Err(format!(
+            "the bundle's vkey hash {} isn't the pinned one",
+            hex(&envelope.vkey_hash)
+        ));
+    }
+    if envelope.program_digest != unhex(program_digest)? {
+        return Err(format!(
+            "the bundle's program digest {} isn't the pinned one",
+            hex(&envelope.program_digest)
+        ));
+    }
+    // decode already checked the manifest against them.
+    let values = PublicValues::abi_decode(&envelope.public_values)
+        .map_err(|e| format!("malformed public values ({:?})", e))?;
+    if values.logic_version != LOGIC_VERSION {
+        return Err(format!(
+            "the guest was built with logic version {}, this page expects {}",
+            values.logic_version, LOGIC_VERSION
+        ));
+    }
+    if let Some(id) = &bundle.puzzle_id {
+        let puzzle = id
+            .puzzle()
+            .map_err(|e| format!("can't generate the puzzle ({:?})", e))?;
+        if id.variant().puzzle_hash(&puzzle) != values.puzzle_hash {
+            return Err(format!("the proof isn't for the puzzle {}", id.encode()));
+        }
+    }
+    Ok(CheckedBundle {
+        backend: envelope.backend.name().into(),
+        proved_on: envelope.proved_on.name().into(),
+        mock: envelope.is_mock(),
+        puzzle_id: bundle.puzzle_id.map(|id| id.encode()),
+        logic_version: values.logic_version,
+        puzzle_hash: hex(&values.puzzle_hash),
+        solution_commitment: hex(&values.solution_commitment),
+        valid: values.valid,
+        difficulty: values.difficulty,
+        clue_count: values.clue_count,
+        seed: values.seed,
+        nullifier: hex(&values.nullifier),
+        epoch: values.epoch,
+        reward_address: hex(&values.reward_address),
+        status: values.status,
+        policy_hash: hex(&values.policy_hash),
+        organizer_key: hex(&values.organizer_key),
+        variant: values.variant,
+        cells_root: hex(&values.cells_root),
+        givens_hash: hex(&values.givens_hash),
+        nonce: hex(&values.nonce),
+    })
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::from("0x"), |s, b| s + &format!("{:02x}", b))
+}
+
+fn unhex(value: &str) -> Result<Vec<u8>, String> {
+    let digits = value.strip_prefix("0x").unwrap_or(value);
+    digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| match core::str::from_utf8(pair) {
+            Ok(pair) if pair.len() == 2 => u8::from_str_radix(pair, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<_>>()
+        .ok_or_else(|| format!("'{}' isn't hex", value))
+}
+
+#[cfg(test)]
+mod wasm_tests {
+    use super::*;
+    use crate::envelope::{Backend, ProofEnvelope, ProvedOn, MOCK_PROOF};
+    use crate::{verify_input, ProofInput, PuzzleSource};
+    use alloc::vec;
+    use sudoku::core::board::Difficulty;
+    use sudoku::core::solver::DancingLinks;
+
+    fn bundle(proof: &[u8]) -> Vec<u8> {
+        let puzzle = crate::puzzle_from_seed(666, Difficulty::Medium as u8).unwrap();
+        let solution = DancingLinks::solve_board(&puzzle).unwrap();
+        let input = ProofInput::new(
+            PuzzleSource::Seed {
+                seed: 666,
+                difficulty: Difficulty::Medium as u8,
+            },
+            solution.cells.to_vec(),
+        );
+        let values = verify_input(&input, |_, _, _| false).unwrap();
+        let envelope = ProofEnvelope {
+            backend: Backend::Sp1,
+            proved_on: ProvedOn::Network,
+            vkey_hash: vec![7; 32],
+            program_digest: vec![5; 32],
+            public_values: values.abi_encode().to_vec(),
+            proof: proof.to_vec(),
+        };
+        ProofBundle::new(envelope).unwrap().encode()
+    }
+
+    // checkBundle itself only differs in its errors, which need a javascript host.
+    #[test]
+    fn test_check() {
+        let (vkey, digest) = (hex(&[7; 32]), hex(&[5; 32]));
+        let checked = check(&bundle(&[9; 100]), &vkey, &digest, false).unwrap();
+        assert_eq!(checked.backend, "sp1");
+        assert_eq!(checked.proved_on, "network");
+        assert_eq!(checked.puzzle_id.as_deref(), Some("zk1-aaaafgqcaawqi"));
+        assert!(checked.valid);
+        assert_eq!(checked.seed, 666);
+        assert_eq!(checked.puzzle_hash.len(), 2 + 64);
+
+        let other = hex(&[8; 32]);
+        assert!(check(&bundle(&[9; 100]), &other, &digest, false)
+            .unwrap_err()
+            .contains("vkey hash"));
+        assert!(check(&bundle(&[9; 100]), &vkey, &other, false)
+            .unwrap_err()
+            .contains("program digest"));
+        assert!(check(&bundle(MOCK_PROOF), &vkey, &digest, false).is_err());
+        assert!(
+            check(&bundle(MOCK_PROOF), &vkey, &digest, true)
+                .unwrap()
+                .mock
+        );
+        assert!(check(b"not a bundle", &vkey, &digest, false).is_err());
+    }
+}